@@ -1,21 +1,22 @@
 use crate::errors::QuickexError;
-use crate::events::{publish_admin_changed, publish_contract_paused};
-use soroban_sdk::{symbol_short, Address, Env, Symbol};
-
-#[allow(dead_code)]
-const ADMIN_KEY: Symbol = symbol_short!("ADMIN");
-#[allow(dead_code)]
-const PAUSED_KEY: Symbol = symbol_short!("PAUSED");
+use crate::events::{
+    publish_admin_changed, publish_admin_transfer_proposed, publish_contract_paused,
+    publish_shutdown,
+};
+use crate::storage;
+use crate::types::Role;
+use soroban_sdk::{Address, Env, String, Vec};
 
 /// Initialize the contract with an admin address
 #[allow(dead_code)]
 pub fn initialize(env: &Env, admin: Address) -> Result<(), QuickexError> {
-    if has_admin(env) {
+    if is_initialized(env) {
         return Err(QuickexError::AlreadyInitialized);
     }
 
-    env.storage().instance().set(&ADMIN_KEY, &admin);
-    env.storage().instance().set(&PAUSED_KEY, &false);
+    storage::set_admin(env, &admin);
+    storage::set_initialized(env);
+    storage::set_paused(env, false);
 
     Ok(())
 }
@@ -23,33 +24,49 @@ pub fn initialize(env: &Env, admin: Address) -> Result<(), QuickexError> {
 /// Check if admin has been initialized
 #[allow(dead_code)]
 pub fn has_admin(env: &Env) -> bool {
-    env.storage().instance().has(&ADMIN_KEY)
+    storage::get_admin(env).is_some()
+}
+
+/// Check whether `initialize` has run.
+///
+/// Relying on the admin entry alone is not enough: it lives in persistent storage and, if
+/// its TTL ever lapsed, looked absent once archived, letting anyone call `initialize` and
+/// take over the contract. The permanent initialized flag closes that gap, and the admin
+/// entry is also bumped on every admin operation. Deployments initialized before the flag
+/// existed are still recognised by their admin entry.
+pub fn is_initialized(env: &Env) -> bool {
+    storage::is_initialized(env) || has_admin(env)
 }
 
 /// Get the current admin address
 #[allow(dead_code)]
 pub fn get_admin(env: &Env) -> Option<Address> {
-    env.storage().instance().get(&ADMIN_KEY)
+    storage::get_admin(env)
 }
 
-/// Require that the caller is the admin
-#[allow(dead_code)]
+/// Require that the caller is the admin; bumps the admin entry's TTL on success
 pub fn require_admin(env: &Env, caller: &Address) -> Result<(), QuickexError> {
     caller.require_auth();
 
     match get_admin(env) {
-        Some(admin) if admin == *caller => Ok(()),
+        Some(admin) if admin == *caller => {
+            storage::bump_admin_ttl(env);
+            Ok(())
+        }
         _ => Err(QuickexError::Unauthorized),
     }
 }
 
-/// Set a new admin address (Admin only)
-#[allow(dead_code)]
+/// Set a new admin address immediately (Admin only).
+///
+/// Any pending two-step proposal is withdrawn, so a previously proposed admin cannot
+/// `accept_admin` afterwards and take over from `new_admin`.
 pub fn set_admin(env: &Env, caller: Address, new_admin: Address) -> Result<(), QuickexError> {
     require_admin(env, &caller)?;
 
     let old_admin = get_admin(env).unwrap();
-    env.storage().instance().set(&ADMIN_KEY, &new_admin);
+    storage::set_admin(env, &new_admin);
+    storage::remove_pending_admin(env);
 
     let timestamp = env.ledger().timestamp();
     publish_admin_changed(env, old_admin, new_admin, timestamp);
@@ -57,27 +74,159 @@ pub fn set_admin(env: &Env, caller: Address, new_admin: Address) -> Result<(), Q
     Ok(())
 }
 
-/// Set the paused state (Admin only)
-#[allow(dead_code)]
-pub fn set_paused(env: &Env, caller: Address, new_state: bool) -> Result<(), QuickexError> {
+/// Propose `new_admin` as the next admin (Admin only); it takes over once it accepts
+pub fn propose_admin(env: &Env, caller: Address, new_admin: Address) -> Result<(), QuickexError> {
     require_admin(env, &caller)?;
 
-    env.storage().instance().set(&PAUSED_KEY, &new_state);
+    storage::set_pending_admin(env, &new_admin);
+
+    let timestamp = env.ledger().timestamp();
+    publish_admin_transfer_proposed(env, caller, new_admin, timestamp);
+
+    Ok(())
+}
+
+/// Complete a proposed admin transfer; `new_admin` must be the pending admin
+pub fn accept_admin(env: &Env, new_admin: Address) -> Result<(), QuickexError> {
+    new_admin.require_auth();
+
+    match storage::get_pending_admin(env) {
+        Some(pending) if pending == new_admin => {}
+        _ => return Err(QuickexError::Unauthorized),
+    }
+
+    let old_admin = get_admin(env).ok_or(QuickexError::Unauthorized)?;
+    storage::set_admin(env, &new_admin);
+    storage::remove_pending_admin(env);
 
     let timestamp = env.ledger().timestamp();
-    publish_contract_paused(env, new_state, timestamp);
+    publish_admin_changed(env, old_admin, new_admin, timestamp);
+
+    Ok(())
+}
+
+/// Withdraw a pending admin proposal (Admin only)
+pub fn cancel_admin_transfer(env: &Env, caller: Address) -> Result<(), QuickexError> {
+    require_admin(env, &caller)?;
+
+    storage::remove_pending_admin(env);
 
     Ok(())
 }
 
+/// Grant `role` to `account` (Admin only); replaces any role it already holds.
+///
+/// `Role::Admin` cannot be granted; the admin is moved with `set_admin` instead.
+pub fn grant_role(
+    env: &Env,
+    caller: Address,
+    account: Address,
+    role: Role,
+) -> Result<(), QuickexError> {
+    require_admin(env, &caller)?;
+
+    if role == Role::Admin {
+        return Err(QuickexError::InvalidInput);
+    }
+    storage::set_role(env, &account, role)
+}
+
+/// Revoke the role held by `account`, if any (Admin only)
+pub fn revoke_role(env: &Env, caller: Address, account: Address) -> Result<(), QuickexError> {
+    require_admin(env, &caller)?;
+
+    storage::remove_role(env, &account);
+    Ok(())
+}
+
+/// Get the role of `account`; the admin always reports `Role::Admin`
+pub fn get_role(env: &Env, account: &Address) -> Option<Role> {
+    if get_admin(env).as_ref() == Some(account) {
+        return Some(Role::Admin);
+    }
+    storage::get_role(env, account)
+}
+
+/// Get every privileged account: the admin, if set, followed by role holders
+pub fn get_all_admins(env: &Env) -> Vec<Address> {
+    let mut accounts = Vec::new(env);
+    let admin = get_admin(env);
+    if let Some(admin) = &admin {
+        accounts.push_back(admin.clone());
+    }
+    for member in storage::get_role_members(env).iter() {
+        if admin.as_ref() != Some(&member) {
+            accounts.push_back(member);
+        }
+    }
+    accounts
+}
+
+/// Set the paused state (Admin only).
+///
+/// `reason` is stored while paused and cleared on unpause. The time of the transition to
+/// paused is recorded; pausing an already paused contract keeps the original time.
+pub fn set_paused(
+    env: &Env,
+    caller: Address,
+    new_state: bool,
+    reason: Option<String>,
+) -> Result<(), QuickexError> {
+    require_admin(env, &caller)?;
+
+    let timestamp = env.ledger().timestamp();
+    if new_state && !is_paused(env) {
+        storage::set_paused_since(env, timestamp);
+    } else if !new_state {
+        storage::remove_paused_since(env);
+    }
+
+    storage::set_paused(env, new_state);
+    match (&reason, new_state) {
+        (Some(reason), true) => storage::set_pause_reason(env, reason),
+        _ => storage::remove_pause_reason(env),
+    }
+
+    publish_contract_paused(env, new_state, reason.filter(|_| new_state), timestamp);
+
+    Ok(())
+}
+
+/// Permanently shut the contract down (Admin only).
+///
+/// One-way: there is no way to undo it, and a second call fails with `ContractShutdown`.
+pub fn shutdown(env: &Env, caller: Address) -> Result<(), QuickexError> {
+    require_admin(env, &caller)?;
+    require_not_shutdown(env)?;
+
+    storage::set_shutdown(env);
+    publish_shutdown(env, caller, env.ledger().timestamp());
+
+    Ok(())
+}
+
+/// Require that the contract has not been shut down.
+///
+/// Shutdown policy: only operations that put new funds into escrow call this. Withdrawals,
+/// refunds and cancels stay open so users can exit.
+pub fn require_not_shutdown(env: &Env) -> Result<(), QuickexError> {
+    if storage::is_shutdown(env) {
+        return Err(QuickexError::ContractShutdown);
+    }
+    Ok(())
+}
+
 /// Check if the contract is paused
 pub fn is_paused(env: &Env) -> bool {
-    env.storage().instance().get(&PAUSED_KEY).unwrap_or(false)
+    storage::is_paused(env)
 }
 
-/// Require that the contract is not paused
-/// This helper function should be called at the start of deposit/withdraw functions
-#[allow(dead_code)]
+/// Require that the contract is not paused.
+///
+/// Pause policy: every entrypoint that lets users move funds or change their own state
+/// (deposits, withdrawals, cancels, refunds, memo and privacy updates) calls this first.
+/// Read-only getters and admin operations stay callable so the admin can respond to the
+/// incident and unpause.
 pub fn require_not_paused(env: &Env) -> Result<(), QuickexError> {
     if is_paused(env) {
         return Err(QuickexError::ContractPaused);
@@ -1,26 +1,67 @@
 use crate::errors::QuickexError;
-use soroban_sdk::{xdr::ToXdr, Address, Bytes, BytesN, Env};
+use crate::storage::get_hash_algorithm;
+use crate::types::HashAlgorithm;
+use soroban_sdk::{xdr::ToXdr, Address, Bytes, BytesN, Env, Vec};
 
-pub fn create_amount_commitment(
+/// Minimum salt length in bytes; shorter salts make committed amounts guessable
+pub const MIN_SALT_LEN: u32 = 16;
+
+/// Highest amount bucket; bucket `k` covers `[10^k, 10^(k+1) - 1]` and the last one is
+/// open-ended up to `i128::MAX`
+pub const MAX_BUCKET_ID: u32 = 38;
+
+/// Domain tag prepended to bucketed commitment preimages so they can never collide
+/// with exact-amount commitments
+const BUCKET_DOMAIN: &[u8] = b"quickex:bucket";
+
+/// Domain tag prepended to recipient-list commitment preimages so they can never collide
+/// with single-recipient commitments
+const RECIPIENTS_DOMAIN: &[u8] = b"quickex:recipients";
+
+/// Domain tag prepended to signer commitment preimages so they can never collide with
+/// address-based commitments
+const SIGNER_DOMAIN: &[u8] = b"quickex:signer";
+
+/// Check the salt length bounds shared by every commitment variant
+fn validate_salt(salt: &Bytes) -> Result<(), QuickexError> {
+    // Cap salt length as a safeguard
+    if salt.len() > 1024 {
+        return Err(QuickexError::InvalidSalt);
+    }
+
+    // Require enough salt to keep the amount hidden
+    if salt.len() < MIN_SALT_LEN {
+        return Err(QuickexError::WeakSalt);
+    }
+    Ok(())
+}
+
+/// Build the exact preimage hashed by [`create_amount_commitment`].
+///
+/// Layout: `owner.to_xdr() || token.to_xdr() || amount (16-byte big-endian i128) || salt`.
+/// Binding the token means a commitment for one asset never matches a deposit of another.
+/// Inputs are validated the same way as for the commitment.
+pub fn build_commitment_preimage(
     env: &Env,
     owner: Address,
+    token: Address,
     amount: i128,
     salt: Bytes,
-) -> Result<BytesN<32>, QuickexError> {
+) -> Result<Bytes, QuickexError> {
     if amount < 0 {
         return Err(QuickexError::InvalidAmount);
     }
 
-    // Cap salt length as a safeguard
-    if salt.len() > 1024 {
-        return Err(QuickexError::InvalidSalt);
-    }
+    validate_salt(&salt)?;
 
     let mut payload = Bytes::new(env);
 
     // Append owner (Address) - using XDR serialization for consistency
     payload.append(&owner.to_xdr(env));
 
+    // Append token (Address), binding the commitment to one asset
+    payload.append(&token.to_xdr(env));
+
     // Serialize amount (i128) to big-endian bytes
     let amount_bytes: [u8; 16] = amount.to_be_bytes();
 
@@ -32,19 +73,217 @@ pub fn create_amount_commitment(
     // Append salt
     payload.append(&salt);
 
-    // Return SHA256 hash
-    Ok(env.crypto().sha256(&payload).into())
+    Ok(payload)
+}
+
+/// Hash a commitment preimage with `algorithm`
+pub fn hash_with(env: &Env, algorithm: HashAlgorithm, preimage: &Bytes) -> BytesN<32> {
+    match algorithm {
+        HashAlgorithm::Sha256 => env.crypto().sha256(preimage).into(),
+        HashAlgorithm::Keccak256 => env.crypto().keccak256(preimage).into(),
+    }
+}
+
+/// Hash a commitment preimage with the configured [`HashAlgorithm`]
+pub fn hash_preimage(env: &Env, preimage: &Bytes) -> BytesN<32> {
+    hash_with(env, get_hash_algorithm(env), preimage)
+}
+
+pub fn create_amount_commitment(
+    env: &Env,
+    owner: Address,
+    token: Address,
+    amount: i128,
+    salt: Bytes,
+) -> Result<BytesN<32>, QuickexError> {
+    let payload = build_commitment_preimage(env, owner, token, amount, salt)?;
+
+    Ok(hash_preimage(env, &payload))
 }
 
 pub fn verify_amount_commitment(
     env: &Env,
     commitment: BytesN<32>,
     owner: Address,
+    token: Address,
     amount: i128,
     salt: Bytes,
 ) -> bool {
-    match create_amount_commitment(env, owner, amount, salt) {
+    match create_amount_commitment(env, owner, token, amount, salt) {
         Ok(hash) => hash == commitment,
         Err(_) => false,
     }
 }
+
+/// Inclusive amount range `[10^bucket_id, 10^(bucket_id + 1) - 1]` of a bucket, or `None`
+/// above [`MAX_BUCKET_ID`]
+pub fn bucket_bounds(bucket_id: u32) -> Option<(i128, i128)> {
+    if bucket_id > MAX_BUCKET_ID {
+        return None;
+    }
+    let min = 10i128.pow(bucket_id);
+    let max = 10i128
+        .checked_pow(bucket_id + 1)
+        .map_or(i128::MAX, |next| next - 1);
+    Some((min, max))
+}
+
+/// Check whether `amount` falls inside bucket `bucket_id`
+pub fn is_in_bucket(amount: i128, bucket_id: u32) -> bool {
+    match bucket_bounds(bucket_id) {
+        Some((min, max)) => amount >= min && amount <= max,
+        None => false,
+    }
+}
+
+/// Build the preimage hashed by [`create_bucketed_commitment`]:
+/// `"quickex:bucket" || owner.to_xdr() || token.to_xdr() || bucket_id (4-byte big-endian)
+/// || salt`
+pub fn build_bucketed_preimage(
+    env: &Env,
+    owner: Address,
+    token: Address,
+    bucket_id: u32,
+    salt: Bytes,
+) -> Result<Bytes, QuickexError> {
+    if bucket_id > MAX_BUCKET_ID {
+        return Err(QuickexError::InvalidInput);
+    }
+
+    validate_salt(&salt)?;
+
+    let mut payload = Bytes::from_slice(env, BUCKET_DOMAIN);
+    payload.append(&owner.to_xdr(env));
+    payload.append(&token.to_xdr(env));
+    payload.extend_from_array(&bucket_id.to_be_bytes());
+    payload.append(&salt);
+
+    Ok(payload)
+}
+
+/// Commit to an amount bucket instead of an exact amount.
+///
+/// Hashes [`build_bucketed_preimage`] with the configured [`HashAlgorithm`]. The
+/// commitment binds only the order of magnitude of the amount, but the exact token, so a
+/// bucket agreed for one asset never matches another.
+pub fn create_bucketed_commitment(
+    env: &Env,
+    owner: Address,
+    token: Address,
+    bucket_id: u32,
+    salt: Bytes,
+) -> Result<BytesN<32>, QuickexError> {
+    let payload = build_bucketed_preimage(env, owner, token, bucket_id, salt)?;
+
+    Ok(hash_preimage(env, &payload))
+}
+
+/// Hash of an allowed-recipients list, `recipients.to_xdr()` hashed with `algorithm`.
+///
+/// The list is hashed in the order given, so depositor and recipient must agree on it.
+pub fn recipient_list_hash(
+    env: &Env,
+    algorithm: HashAlgorithm,
+    recipients: &Vec<Address>,
+) -> BytesN<32> {
+    hash_with(env, algorithm, &recipients.to_xdr(env))
+}
+
+/// Build the preimage of a recipient-list commitment:
+/// `"quickex:recipients" || list_hash || token.to_xdr() || amount (16-byte big-endian) ||
+/// salt`, where `list_hash` is [`recipient_list_hash`] under the same algorithm.
+pub fn build_recipients_preimage(
+    env: &Env,
+    list_hash: &BytesN<32>,
+    token: Address,
+    amount: i128,
+    salt: Bytes,
+) -> Result<Bytes, QuickexError> {
+    if amount < 0 {
+        return Err(QuickexError::InvalidAmount);
+    }
+
+    validate_salt(&salt)?;
+
+    let mut payload = Bytes::from_slice(env, RECIPIENTS_DOMAIN);
+    payload.append(&list_hash.clone().into());
+    payload.append(&token.to_xdr(env));
+    payload.extend_from_array(&amount.to_be_bytes());
+    payload.append(&salt);
+
+    Ok(payload)
+}
+
+/// Commit to a list of allowed recipients instead of a single one.
+///
+/// Hashes [`build_recipients_preimage`] with the configured [`HashAlgorithm`], which also
+/// hashes the list. Whoever holds the salt may withdraw, but only to an address on the
+/// list.
+pub fn create_recipients_commitment(
+    env: &Env,
+    recipients: &Vec<Address>,
+    token: Address,
+    amount: i128,
+    salt: Bytes,
+) -> Result<BytesN<32>, QuickexError> {
+    let algorithm = get_hash_algorithm(env);
+    let list_hash = recipient_list_hash(env, algorithm, recipients);
+    let payload = build_recipients_preimage(env, &list_hash, token, amount, salt)?;
+
+    Ok(hash_with(env, algorithm, &payload))
+}
+
+/// Build the preimage hashed by [`create_signer_commitment`]:
+/// `"quickex:signer" || pubkey || token.to_xdr() || amount (16-byte big-endian) || salt`
+pub fn build_signer_preimage(
+    env: &Env,
+    pubkey: &BytesN<32>,
+    token: Address,
+    amount: i128,
+    salt: Bytes,
+) -> Result<Bytes, QuickexError> {
+    if amount < 0 {
+        return Err(QuickexError::InvalidAmount);
+    }
+
+    validate_salt(&salt)?;
+
+    let mut payload = Bytes::from_slice(env, SIGNER_DOMAIN);
+    payload.append(&pubkey.clone().into());
+    payload.append(&token.to_xdr(env));
+    payload.extend_from_array(&amount.to_be_bytes());
+    payload.append(&salt);
+
+    Ok(payload)
+}
+
+/// Commit to an Ed25519 public key instead of a recipient address.
+///
+/// Hashes [`build_signer_preimage`] with the configured [`HashAlgorithm`]. The escrow is
+/// withdrawn with a signature by the key, which picks the recipient, so the recipient
+/// never has to authorize the transaction itself.
+pub fn create_signer_commitment(
+    env: &Env,
+    pubkey: &BytesN<32>,
+    token: Address,
+    amount: i128,
+    salt: Bytes,
+) -> Result<BytesN<32>, QuickexError> {
+    let payload = build_signer_preimage(env, pubkey, token, amount, salt)?;
+
+    Ok(hash_preimage(env, &payload))
+}
+
+/// Message signed to withdraw a signer-committed escrow:
+/// `commitment || to.to_xdr() || amount (16-byte big-endian)`
+pub fn build_withdraw_message(
+    env: &Env,
+    commitment: &BytesN<32>,
+    to: &Address,
+    amount: i128,
+) -> Bytes {
+    let mut message = Bytes::from_array(env, &commitment.to_array());
+    message.append(&to.clone().to_xdr(env));
+    message.extend_from_array(&amount.to_be_bytes());
+    message
+}
@@ -0,0 +1,80 @@
+#![cfg(test)]
+//! Known-answer vectors for `create_amount_commitment`. Off-chain clients build the same
+//! preimage (`owner.to_xdr() || token.to_xdr() || amount (16-byte big-endian) || salt`)
+//! and hash it with SHA-256; these vectors pin that construction so any change to it fails
+//! here before it breaks an integration. The expected hashes were computed independently
+//! of the contract from the raw XDR bytes; never regenerate them from contract output.
+use soroban_sdk::{Address, Bytes, BytesN, Env, String};
+
+use crate::{QuickexContract, QuickexContractClient};
+
+struct Vector {
+    owner: &'static str,
+    token: &'static str,
+    amount: i128,
+    salt: &'static [u8],
+    commitment: &'static str,
+}
+
+const VECTORS: [Vector; 3] = [
+    // Account owner with every key byte 0x01; contract token with every byte 0x02
+    Vector {
+        owner: "GAAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQDZ7H",
+        token: "CABAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAFNSZ",
+        amount: 1000,
+        salt: b"quickex-vector-01",
+        commitment: "3cb0d892f1db4e937c9d88647dd39bd3f4794414d60a2560a6e5a1d62e16b186",
+    },
+    // Account owner with key bytes 0x00..=0x1f; zero amount; 32 zero bytes of salt
+    Vector {
+        owner: "GAAACAQDAQCQMBYIBEFAWDANBYHRAEISCMKBKFQXDAMRUGY4DUPB7JZX",
+        token: "CCV2XK5LVOV2XK5LVOV2XK5LVOV2XK5LVOV2XK5LVOV2XK5LVOV2XMCW",
+        amount: 0,
+        salt: &[0; 32],
+        commitment: "098168ebba0c84dfce80daecdd5c61f0023b94f16743c40c77983140d67289f9",
+    },
+    // Contract owner; maximum amount; shortest accepted salt
+    Vector {
+        owner: "CAIRCEIRCEIRCEIRCEIRCEIRCEIRCEIRCEIRCEIRCEIRCEIRCEIRDB3V",
+        token: "CD7777777777777777777777777777777777777777777777777767GY",
+        amount: i128::MAX,
+        salt: &[0x5a; 16],
+        commitment: "9fa89895be42cef05f1163eb7c1c5e704d96e2041ebb4c00f83013a1cf71f1a6",
+    },
+];
+
+/// Decode a 64-character lowercase hex string
+fn hex32(env: &Env, hex: &str) -> BytesN<32> {
+    let digit = |c: u8| match c {
+        b'0'..=b'9' => c - b'0',
+        b'a'..=b'f' => c - b'a' + 10,
+        _ => panic!("invalid hex digit"),
+    };
+    let hex = hex.as_bytes();
+    assert_eq!(hex.len(), 64);
+    let mut bytes = [0u8; 32];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = digit(hex[2 * i]) << 4 | digit(hex[2 * i + 1]);
+    }
+    BytesN::from_array(env, &bytes)
+}
+
+#[test]
+fn test_amount_commitment_known_answer_vectors() {
+    let env = Env::default();
+    let contract_id = env.register(QuickexContract, ());
+    let client = QuickexContractClient::new(&env, &contract_id);
+
+    for vector in VECTORS.iter() {
+        let owner = Address::from_string(&String::from_str(&env, vector.owner));
+        let token = Address::from_string(&String::from_str(&env, vector.token));
+        let salt = Bytes::from_slice(&env, vector.salt);
+        let expected = hex32(&env, vector.commitment);
+
+        assert_eq!(
+            client.create_amount_commitment(&owner, &token, &vector.amount, &salt),
+            expected
+        );
+        assert!(client.verify_amount_commitment(&expected, &owner, &token, &vector.amount, &salt));
+    }
+}
@@ -1,4 +1,4 @@
-use soroban_sdk::contracterror;
+use soroban_sdk::{contracterror, Env, String};
 
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
@@ -16,4 +16,99 @@ pub enum QuickexError {
     InvalidCommitment = 10,
     ContractPaused = 11,
     CommitmentAlreadyExists = 12,
+    FeeTooHigh = 13,
+    TransferShortfall = 14,
+    EscrowCancelled = 15,
+    MemoTooLong = 16,
+    AmountBelowMinimum = 17,
+    AmountAboveMaximum = 18,
+    TokenNotAllowed = 19,
+    InvalidInput = 20,
+    EscrowExpired = 21,
+    EscrowNotExpired = 22,
+    /// No longer returned: deposits skip the Merkle insert once the tree is full. The code
+    /// stays reserved so later codes keep their values.
+    MerkleTreeFull = 23,
+    ReclaimWindowActive = 24,
+    WeakSalt = 25,
+    NativeTokenNotSet = 26,
+    InvalidStatusTransition = 27,
+    SwapRateNotSet = 28,
+    InsufficientReserve = 29,
+    ArithmeticOverflow = 30,
+    RecipientBlocked = 31,
+    FeeCollectorNotSet = 32,
+    SaltReused = 33,
+    TooManyPendingEscrows = 34,
+    DepositDeadlinePassed = 35,
+    AmountMismatch = 36,
+    TooManyRoleMembers = 37,
+    WithdrawCooldownActive = 38,
+    RecipientNotAllowed = 39,
+    BatchTooLarge = 40,
+    ContractShutdown = 41,
+}
+
+impl QuickexError {
+    /// Short human-readable description of the error, for frontends to display
+    pub fn message(self) -> &'static str {
+        match self {
+            QuickexError::AlreadyInitialized => "Contract is already initialized",
+            QuickexError::Unauthorized => "Caller is not authorized for this operation",
+            QuickexError::PrivacyAlreadySet => "Privacy level is already set to this value",
+            QuickexError::InvalidPrivacyLevel => "Privacy level is not valid",
+            QuickexError::InvalidAmount => "Amount must be positive",
+            QuickexError::InvalidSalt => "Salt is too long",
+            QuickexError::CommitmentMismatch => "Commitment does not match the given values",
+            QuickexError::CommitmentNotFound => "No escrow exists for this commitment",
+            QuickexError::AlreadySpent => "Escrow has already been withdrawn or refunded",
+            QuickexError::InvalidCommitment => {
+                "Escrow does not match the requested token or amount"
+            }
+            QuickexError::ContractPaused => "Contract is paused",
+            QuickexError::CommitmentAlreadyExists => "An escrow already exists for this commitment",
+            QuickexError::FeeTooHigh => "Fee is above the allowed maximum",
+            QuickexError::TransferShortfall => {
+                "Contract balance is too small to back the imported escrows"
+            }
+            QuickexError::EscrowCancelled => "Escrow was cancelled by the depositor",
+            QuickexError::MemoTooLong => "Memo is too long",
+            QuickexError::AmountBelowMinimum => "Amount is below the minimum for this token",
+            QuickexError::AmountAboveMaximum => "Amount is above the maximum for this token",
+            QuickexError::TokenNotAllowed => "Token is not on the allowlist",
+            QuickexError::InvalidInput => "Input is not valid",
+            QuickexError::EscrowExpired => "Escrow has expired and can only be refunded",
+            QuickexError::EscrowNotExpired => "Escrow has not expired yet",
+            QuickexError::MerkleTreeFull => "Commitment tree is full",
+            QuickexError::ReclaimWindowActive => {
+                "Only the depositor may reclaim during the reclaim window"
+            }
+            QuickexError::WeakSalt => "Salt is too weak",
+            QuickexError::NativeTokenNotSet => "Native token is not configured",
+            QuickexError::InvalidStatusTransition => "Escrow cannot move to the requested status",
+            QuickexError::SwapRateNotSet => "No swap rate is configured for this token pair",
+            QuickexError::InsufficientReserve => "Contract reserve is too small for this payout",
+            QuickexError::ArithmeticOverflow => "Amount calculation overflowed",
+            QuickexError::RecipientBlocked => "Recipient is blocked",
+            QuickexError::FeeCollectorNotSet => "Fee collector is not configured",
+            QuickexError::SaltReused => "Salt has already been used by this depositor",
+            QuickexError::TooManyPendingEscrows => "Depositor has too many pending escrows",
+            QuickexError::DepositDeadlinePassed => "Deposit deadline has passed",
+            QuickexError::AmountMismatch => {
+                "Contract received a different amount than was transferred"
+            }
+            QuickexError::TooManyRoleMembers => "Role has too many members",
+            QuickexError::WithdrawCooldownActive => {
+                "Recipient must wait for the withdrawal cooldown to end"
+            }
+            QuickexError::RecipientNotAllowed => "Recipient is not on the escrow's allowed list",
+            QuickexError::BatchTooLarge => "Too many items in one call",
+            QuickexError::ContractShutdown => "Contract is shut down and accepts no new deposits",
+        }
+    }
+
+    /// [`message`](QuickexError::message) as a contract `String`
+    pub fn to_string(self, env: &Env) -> String {
+        String::from_str(env, self.message())
+    }
 }
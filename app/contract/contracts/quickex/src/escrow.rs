@@ -0,0 +1,840 @@
+use soroban_sdk::{token, Address, Bytes, BytesN, Env, Map, Vec};
+
+use crate::admin;
+use crate::commitment;
+use crate::errors::QuickexError;
+use crate::events::{
+    publish_auth_failed, publish_commitment_status_changed, publish_deposit,
+    publish_escrow_expired, publish_escrow_refunded, publish_escrow_topped_up,
+    publish_escrow_transferred, publish_private_deposit, publish_withdraw_toggled,
+};
+use crate::fees;
+use crate::merkle;
+use crate::privacy;
+use crate::storage::{
+    add_owner_escrow, add_total_escrowed, decrement_counter, get_admin, get_allowed_token_count,
+    get_amount_bounds, get_counter, get_default_expiry, get_deposit_fee_bps, get_escrow,
+    get_fee_bps, get_fee_collector, get_hash_algorithm, get_last_withdraw,
+    get_max_pending_per_owner, get_reclaim_window, get_swap_rate, get_total_escrowed,
+    get_withdraw_cooldown, has_escrow, increment_counter, is_allowed_token, is_blocked,
+    is_salt_used, mark_salt_used, put_escrow, remove_owner_escrow, set_last_withdraw, set_memo,
+    status_count_key, sub_total_escrowed, DataKey, MAX_MEMO_LEN,
+};
+use crate::transfer;
+use crate::types::{
+    convert_amount, is_admin_reclaimable, is_expired, transition_status, EscrowEntry, EscrowStatus,
+    HashAlgorithm, Payout, SwapTarget, WithdrawReceipt, NO_EXPIRY,
+};
+
+/// Check a deposit of `amount` of `token` against the configured deposit rules
+pub fn validate_deposit(env: &Env, token: &Address, amount: i128) -> Result<(), QuickexError> {
+    admin::require_not_shutdown(env)?;
+
+    if !is_token_allowed(env, token) {
+        return Err(QuickexError::TokenNotAllowed);
+    }
+
+    let bounds = get_amount_bounds(env, token);
+    if amount < bounds.min {
+        return Err(QuickexError::AmountBelowMinimum);
+    }
+    if amount > bounds.max {
+        return Err(QuickexError::AmountAboveMaximum);
+    }
+    Ok(())
+}
+
+/// Check whether `token` may be deposited.
+///
+/// An empty allowlist allows every token; once any token is added, only listed tokens
+/// are accepted.
+pub fn is_token_allowed(env: &Env, token: &Address) -> bool {
+    get_allowed_token_count(env) == 0 || is_allowed_token(env, token)
+}
+
+/// Whether `commitment` may be used for a new escrow.
+///
+/// A commitment is taken while its escrow is active (`Pending` or `Expired`) and stays
+/// taken once it has been nullified: spent, refunded and cancelled entries are kept in
+/// storage as the nullifier record, so a used commitment can never be funded again. Both
+/// cases are the same lookup; keep every "is this commitment free" check going through here
+/// so they cannot drift apart.
+pub fn is_commitment_available(env: &Env, commitment: &BytesN<32>) -> bool {
+    !has_escrow(env, &commitment.clone().into())
+}
+
+/// Resolve the `expires_at` timestamp for a new escrow.
+///
+/// `None` applies the admin-set default lifetime (see `set_default_expiry`), `Some(0)`
+/// means the escrow never expires, and `Some(secs)` expires `secs` seconds from now.
+pub fn resolve_expiry(env: &Env, expires_in: Option<u64>) -> Result<u64, QuickexError> {
+    let secs = expires_in.unwrap_or_else(|| get_default_expiry(env));
+    if secs == 0 {
+        return Ok(NO_EXPIRY);
+    }
+    env.ledger()
+        .timestamp()
+        .checked_add(secs)
+        .ok_or(QuickexError::InvalidInput)
+}
+
+/// Optional settings for a new escrow; `Default` gives a plain deposit
+#[derive(Clone, Default)]
+pub struct DepositOptions {
+    /// Lifetime in seconds, see [`resolve_expiry`]
+    pub expires_in: Option<u64>,
+    /// Opaque note stored under `DataKey::Memo`, at most `MAX_MEMO_LEN` bytes
+    pub memo: Option<Bytes>,
+    /// Token the recipient is paid in, see [`resolve_payout`]
+    pub target_token: Option<Address>,
+    /// Latest ledger timestamp at which the deposit may still execute
+    pub not_after: Option<u64>,
+    /// Accept a re-submission of an existing deposit as success, see [`is_resubmission`]
+    pub idempotent: bool,
+}
+
+/// Fee taken from a deposit of `amount`, see `set_deposit_fee`.
+///
+/// Zero while no deposit fee or no fee collector is configured.
+pub fn deposit_fee(env: &Env, amount: i128) -> i128 {
+    match get_fee_collector(env) {
+        Some(_) => fees::compute_fee(amount, get_deposit_fee_bps(env)),
+        None => 0,
+    }
+}
+
+/// Amount escrowed, and withdrawable by the recipient, for a deposit of `amount`
+pub fn net_deposit_amount(env: &Env, amount: i128) -> i128 {
+    amount - deposit_fee(env, amount)
+}
+
+/// Whether `commitment` already holds a pending escrow of `token` deposited by `from`
+/// for a deposit of `amount`, i.e. the same deposit submitted again after a network retry.
+pub fn is_resubmission(
+    env: &Env,
+    from: &Address,
+    token: &Address,
+    amount: i128,
+    commitment: &BytesN<32>,
+) -> bool {
+    get_escrow(env, &commitment.clone().into()).is_some_and(|entry| {
+        entry.status == EscrowStatus::Pending
+            && entry.owner == *from
+            && entry.token == *token
+            && entry.amount == net_deposit_amount(env, amount)
+    })
+}
+
+/// Resolve what a deposit of `amount` of `token` pays out on withdrawal.
+///
+/// No target, or a target equal to `token`, pays out `token` itself. Otherwise the admin
+/// rate for the pair is fixed on the entry (`SwapRateNotSet` if there is none) and the
+/// converted amount must not overflow.
+pub fn resolve_payout(
+    env: &Env,
+    token: &Address,
+    amount: i128,
+    target_token: Option<Address>,
+) -> Result<Payout, QuickexError> {
+    let target = match target_token {
+        Some(target) if target != *token => target,
+        _ => return Ok(Payout::Deposited),
+    };
+    let rate = get_swap_rate(env, token, &target).ok_or(QuickexError::SwapRateNotSet)?;
+    convert_amount(amount, &rate).ok_or(QuickexError::InvalidAmount)?;
+    Ok(Payout::Swap(SwapTarget {
+        token: target,
+        rate,
+    }))
+}
+
+/// Escrow `amount` of `token` from `from` under `commitment`.
+///
+/// Validates the deposit, records the pending entry and appends the commitment to the
+/// Merkle tree, then pulls the tokens in. If a deposit fee is configured it is forwarded
+/// to the fee collector and the escrow records the net amount, which is what `commitment`
+/// must commit to. The contract itself may not be the depositor (`InvalidInput`): it
+/// would escrow funds it already holds. If `from` has privacy enabled the deposit event
+/// omits the amount. With `options.idempotent`, an identical re-submission returns `Ok`
+/// without transferring again; any other reuse of the commitment still fails with
+/// `CommitmentAlreadyExists`. Callers are responsible for authorization and for rejecting
+/// non-positive amounts.
+pub fn deposit(
+    env: &Env,
+    from: &Address,
+    token: &Address,
+    amount: i128,
+    commitment: &BytesN<32>,
+    options: DepositOptions,
+) -> Result<(), QuickexError> {
+    if *from == env.current_contract_address() {
+        return Err(QuickexError::InvalidInput);
+    }
+    if options.idempotent && is_resubmission(env, from, token, amount, commitment) {
+        return Ok(());
+    }
+    if options
+        .not_after
+        .is_some_and(|deadline| env.ledger().timestamp() > deadline)
+    {
+        return Err(QuickexError::DepositDeadlinePassed);
+    }
+    validate_deposit(env, token, amount)?;
+    let fee = deposit_fee(env, amount);
+    let net_amount = amount - fee;
+    let expires_at = resolve_expiry(env, options.expires_in)?;
+    let payout = resolve_payout(env, token, net_amount, options.target_token)?;
+
+    if let Some(memo) = &options.memo {
+        if memo.len() > MAX_MEMO_LEN {
+            return Err(QuickexError::MemoTooLong);
+        }
+    }
+
+    if !is_commitment_available(env, commitment) {
+        return Err(QuickexError::CommitmentAlreadyExists);
+    }
+
+    let max_pending = get_max_pending_per_owner(env);
+    if max_pending > 0
+        && get_counter(env, &DataKey::OwnerPendingCount(from.clone())) >= u64::from(max_pending)
+    {
+        return Err(QuickexError::TooManyPendingEscrows);
+    }
+
+    let entry = EscrowEntry {
+        token: token.clone(),
+        amount: net_amount,
+        owner: from.clone(),
+        status: EscrowStatus::Pending,
+        created_at: env.ledger().timestamp(),
+        expires_at,
+        payout,
+        hash_algorithm: get_hash_algorithm(env),
+    };
+    create(env, commitment, &entry)?;
+    merkle::insert(env, commitment);
+    increment_counter(env, &DataKey::TotalDeposits);
+
+    let memo_hash = options.memo.map(|memo| {
+        set_memo(env, &commitment.clone().into(), &memo);
+        env.crypto().sha256(&memo).into()
+    });
+
+    transfer::transfer_in(env, token, from, amount)?;
+    forward_deposit_fee(env, token, fee);
+    announce_deposit(env, from, commitment, token, net_amount, memo_hash);
+
+    Ok(())
+}
+
+/// Send a deposit fee already held by the contract to the fee collector
+fn forward_deposit_fee(env: &Env, token: &Address, fee: i128) {
+    if fee <= 0 {
+        return;
+    }
+    if let Some(collector) = get_fee_collector(env) {
+        token::Client::new(env, token).transfer(&env.current_contract_address(), &collector, &fee);
+    }
+}
+
+/// Publish the deposit event for a new escrow, omitting the amount if `from` has privacy
+/// enabled
+fn announce_deposit(
+    env: &Env,
+    from: &Address,
+    commitment: &BytesN<32>,
+    token: &Address,
+    amount: i128,
+    memo_hash: Option<BytesN<32>>,
+) {
+    if privacy::get_privacy(env, from.clone()) {
+        publish_private_deposit(env, commitment.clone(), token.clone(), memo_hash);
+    } else {
+        publish_deposit(env, commitment.clone(), token.clone(), amount, memo_hash);
+    }
+}
+
+/// Add `additional_amount` to `from`'s pending escrow under `old_commitment`, moving it to
+/// a new commitment.
+///
+/// The commitment binds the amount, so the escrow cannot grow in place. Instead the old
+/// entry is marked `Spent`, which nullifies `old_commitment`, and a new pending entry for
+/// the combined amount is created under `commitment(from, token, combined, new_salt)`, the
+/// same commitment `deposit` would produce. The new entry keeps the old token, payout and
+/// expiry; the old memo is not carried over. Any deposit fee applies to
+/// `additional_amount` only. Returns the new commitment. Callers are responsible for
+/// authorization and for rejecting non-positive amounts.
+pub fn top_up(
+    env: &Env,
+    from: &Address,
+    old_commitment: &BytesN<32>,
+    additional_amount: i128,
+    new_salt: Bytes,
+) -> Result<BytesN<32>, QuickexError> {
+    let mut old_entry = load_withdrawable(env, old_commitment, |_| true)?;
+    if old_entry.owner != *from {
+        publish_auth_failed(env, from.clone(), "top_up");
+        return Err(QuickexError::Unauthorized);
+    }
+    let token = old_entry.token.clone();
+
+    let fee = deposit_fee(env, additional_amount);
+    let combined = old_entry
+        .amount
+        .checked_add(additional_amount - fee)
+        .ok_or(QuickexError::ArithmeticOverflow)?;
+    validate_deposit(env, &token, combined)?;
+    if let Payout::Swap(swap) = &old_entry.payout {
+        convert_amount(combined, &swap.rate).ok_or(QuickexError::InvalidAmount)?;
+    }
+
+    if is_salt_used(env, from, &new_salt) {
+        return Err(QuickexError::SaltReused);
+    }
+    let new_commitment = commitment::create_amount_commitment(
+        env,
+        from.clone(),
+        token.clone(),
+        combined,
+        new_salt.clone(),
+    )?;
+    if !is_commitment_available(env, &new_commitment) {
+        return Err(QuickexError::CommitmentAlreadyExists);
+    }
+
+    set_status(env, old_commitment, &mut old_entry, EscrowStatus::Spent)?;
+    let new_entry = EscrowEntry {
+        amount: combined,
+        status: EscrowStatus::Pending,
+        created_at: env.ledger().timestamp(),
+        hash_algorithm: get_hash_algorithm(env),
+        ..old_entry
+    };
+    create(env, &new_commitment, &new_entry)?;
+    merkle::insert(env, &new_commitment);
+    increment_counter(env, &DataKey::TotalDeposits);
+    mark_salt_used(env, from, &new_salt);
+
+    transfer::transfer_in(env, &token, from, additional_amount)?;
+    forward_deposit_fee(env, &token, fee);
+    announce_deposit(env, from, &new_commitment, &token, combined, None);
+    publish_escrow_topped_up(env, old_commitment.clone(), new_commitment.clone());
+
+    Ok(new_commitment)
+}
+
+/// Move `old_owner`'s pending escrow under `commitment` to `new_owner` and
+/// `new_commitment`, handing the right to withdraw to whoever can open `new_commitment`.
+///
+/// Withdrawal is gated by knowledge of the commitment opening, so the depositor assigns the
+/// escrow by rebinding it to a commitment the new withdrawer computed, without learning
+/// its opening and without moving funds. The old entry is marked `Spent`, which nullifies
+/// `commitment`, and a pending copy owned by `new_owner` is stored under `new_commitment`,
+/// so the old owner can no longer cancel, refund or edit it. Token, amount, payout and
+/// expiry carry over; the memo does not. Callers are responsible for authorization.
+pub fn transfer_escrow(
+    env: &Env,
+    old_owner: &Address,
+    commitment: &BytesN<32>,
+    new_owner: &Address,
+    new_commitment: &BytesN<32>,
+) -> Result<(), QuickexError> {
+    let mut old_entry = load_withdrawable(env, commitment, |_| true)?;
+    if old_entry.owner != *old_owner {
+        publish_auth_failed(env, old_owner.clone(), "transfer_escrow");
+        return Err(QuickexError::Unauthorized);
+    }
+    if !is_commitment_available(env, new_commitment) {
+        return Err(QuickexError::CommitmentAlreadyExists);
+    }
+
+    let max_pending = get_max_pending_per_owner(env);
+    if new_owner != old_owner
+        && max_pending > 0
+        && get_counter(env, &DataKey::OwnerPendingCount(new_owner.clone()))
+            >= u64::from(max_pending)
+    {
+        return Err(QuickexError::TooManyPendingEscrows);
+    }
+
+    set_status(env, commitment, &mut old_entry, EscrowStatus::Spent)?;
+    let new_entry = EscrowEntry {
+        owner: new_owner.clone(),
+        status: EscrowStatus::Pending,
+        hash_algorithm: get_hash_algorithm(env),
+        ..old_entry
+    };
+    create(env, new_commitment, &new_entry)?;
+    merkle::insert(env, new_commitment);
+    publish_escrow_transferred(env, commitment.clone(), new_commitment.clone());
+
+    Ok(())
+}
+
+/// Whether an escrow in `status` still holds its funds in the contract
+pub fn is_outstanding(status: EscrowStatus) -> bool {
+    matches!(status, EscrowStatus::Pending | EscrowStatus::Expired)
+}
+
+/// Store a newly created escrow entry and announce its initial status.
+///
+/// Fails with `ArithmeticOverflow` if the token's total escrowed amount would overflow.
+pub fn create(env: &Env, commitment: &BytesN<32>, entry: &EscrowEntry) -> Result<(), QuickexError> {
+    if is_outstanding(entry.status) {
+        add_total_escrowed(env, &entry.token, entry.amount)?;
+    }
+    put_escrow(env, &commitment.clone().into(), entry);
+    increment_counter(env, &status_count_key(entry.status));
+    if entry.status == EscrowStatus::Pending {
+        increment_counter(env, &DataKey::OwnerPendingCount(entry.owner.clone()));
+        add_owner_escrow(env, &entry.owner, commitment);
+    }
+    publish_commitment_status_changed(env, commitment.clone(), None, entry.status);
+    Ok(())
+}
+
+/// Move an existing escrow entry to `status`, persisting and announcing the change.
+///
+/// A `Pending -> Expired` move also publishes an `EscrowExpired` event for indexers.
+/// Fails with `InvalidStatusTransition` if [`transition_status`] does not allow the move.
+pub fn set_status(
+    env: &Env,
+    commitment: &BytesN<32>,
+    entry: &mut EscrowEntry,
+    status: EscrowStatus,
+) -> Result<(), QuickexError> {
+    let from = entry.status;
+    entry.status = transition_status(from, status)?;
+    if is_outstanding(from) && !is_outstanding(status) {
+        sub_total_escrowed(env, &entry.token, entry.amount);
+    }
+    put_escrow(env, &commitment.clone().into(), entry);
+    decrement_counter(env, &status_count_key(from));
+    increment_counter(env, &status_count_key(status));
+    let owner_pending = DataKey::OwnerPendingCount(entry.owner.clone());
+    if from == EscrowStatus::Pending {
+        decrement_counter(env, &owner_pending);
+        remove_owner_escrow(env, &entry.owner, commitment);
+    } else if status == EscrowStatus::Pending {
+        increment_counter(env, &owner_pending);
+        add_owner_escrow(env, &entry.owner, commitment);
+    }
+    publish_commitment_status_changed(env, commitment.clone(), Some(from), status);
+    if from == EscrowStatus::Pending && status == EscrowStatus::Expired {
+        publish_escrow_expired(env, commitment.clone(), entry.owner.clone());
+    }
+    Ok(())
+}
+
+/// Find the escrow a commitment opening points at.
+///
+/// `commit` computes the commitment under a given [`HashAlgorithm`]. Every algorithm is
+/// tried, and an escrow only matches under the algorithm recorded on it at deposit, so
+/// changing the configured algorithm never strands pending escrows. Reads only.
+pub fn locate(
+    env: &Env,
+    commit: impl Fn(HashAlgorithm) -> BytesN<32>,
+) -> Option<(BytesN<32>, EscrowEntry)> {
+    HashAlgorithm::ALL.into_iter().find_map(|algorithm| {
+        let commitment = commit(algorithm);
+        get_escrow(env, &commitment.clone().into())
+            .filter(|entry| entry.hash_algorithm == algorithm)
+            .map(|entry| (commitment, entry))
+    })
+}
+
+/// Look up the escrow committed to `owner`, `token`, `amount`, and `salt` and check it can
+/// be spent.
+///
+/// Returns the commitment and entry if the escrow exists, is pending, holds exactly
+/// `amount` of `token` and has not expired. The token check matters for commitments
+/// supplied by the depositor, which could otherwise be funded with a different asset.
+/// Reads only; nothing is written.
+pub fn find_withdrawable(
+    env: &Env,
+    owner: &Address,
+    token: &Address,
+    amount: i128,
+    salt: Bytes,
+) -> Result<(BytesN<32>, EscrowEntry), QuickexError> {
+    let preimage =
+        commitment::build_commitment_preimage(env, owner.clone(), token.clone(), amount, salt)?;
+    open_withdrawable(
+        env,
+        |algorithm| commitment::hash_with(env, algorithm, &preimage),
+        |held| held.token == *token && held.amount == amount,
+    )
+}
+
+/// Locate the escrow `commit` opens (see [`locate`]) and check it can be spent, as
+/// [`load_withdrawable`] does.
+fn open_withdrawable(
+    env: &Env,
+    commit: impl Fn(HashAlgorithm) -> BytesN<32>,
+    opens: impl Fn(&EscrowEntry) -> bool,
+) -> Result<(BytesN<32>, EscrowEntry), QuickexError> {
+    let (commitment, entry) = locate(env, commit).ok_or(QuickexError::CommitmentNotFound)?;
+    let entry = check_withdrawable(env, entry, opens)?;
+    Ok((commitment, entry))
+}
+
+/// Load the escrow under `commitment` and check it can be spent.
+///
+/// The entry must be pending, must satisfy `opens` (the commitment's claims about it),
+/// and must not have expired. Reads only; nothing is written.
+fn load_withdrawable(
+    env: &Env,
+    commitment: &BytesN<32>,
+    opens: impl Fn(&EscrowEntry) -> bool,
+) -> Result<EscrowEntry, QuickexError> {
+    let entry: EscrowEntry =
+        get_escrow(env, &commitment.clone().into()).ok_or(QuickexError::CommitmentNotFound)?;
+    check_withdrawable(env, entry, opens)
+}
+
+fn check_withdrawable(
+    env: &Env,
+    entry: EscrowEntry,
+    opens: impl Fn(&EscrowEntry) -> bool,
+) -> Result<EscrowEntry, QuickexError> {
+    match entry.status {
+        EscrowStatus::Pending => {}
+        EscrowStatus::Cancelled => return Err(QuickexError::EscrowCancelled),
+        _ => return Err(QuickexError::AlreadySpent),
+    }
+
+    if !opens(&entry) {
+        return Err(QuickexError::InvalidCommitment);
+    }
+
+    if is_expired(&entry, env.ledger().timestamp()) {
+        return Err(QuickexError::EscrowExpired);
+    }
+
+    Ok(entry)
+}
+
+/// Spend the escrow committed to `owner`, `token`, `amount`, and `salt`, paying `recipient`.
+///
+/// See [`pay_out`] for how the funds are split. Callers are responsible for authorization.
+pub fn withdraw(
+    env: &Env,
+    owner: &Address,
+    token: &Address,
+    amount: i128,
+    salt: Bytes,
+    recipient: &Address,
+    relayer: Option<(&Address, i128)>,
+) -> Result<WithdrawReceipt, QuickexError> {
+    let (commitment, entry) = find_withdrawable(env, owner, token, amount, salt)?;
+    pay_out(env, &commitment, entry, recipient, relayer)
+}
+
+/// Spend the escrow committed to `owner`, `token`, amount bucket `bucket_id`, and `salt`,
+/// paying `recipient`.
+///
+/// The escrow must hold `token` and its amount must fall inside the committed bucket
+/// (`InvalidCommitment` otherwise). Returns the amount spent. Callers are responsible for
+/// authorization.
+pub fn withdraw_bucketed(
+    env: &Env,
+    owner: &Address,
+    token: &Address,
+    bucket_id: u32,
+    salt: Bytes,
+    recipient: &Address,
+) -> Result<i128, QuickexError> {
+    let preimage =
+        commitment::build_bucketed_preimage(env, owner.clone(), token.clone(), bucket_id, salt)?;
+    let (commitment, entry) = open_withdrawable(
+        env,
+        |algorithm| commitment::hash_with(env, algorithm, &preimage),
+        |held| held.token == *token && commitment::is_in_bucket(held.amount, bucket_id),
+    )?;
+    let amount = entry.amount;
+    pay_out(env, &commitment, entry, recipient, None)?;
+    Ok(amount)
+}
+
+/// Spend the escrow committed to the allowed-recipients list `recipients`, `token`,
+/// `amount`, and `salt`, paying `recipient`.
+///
+/// `recipient` must be on the list (`RecipientNotAllowed` otherwise); a list that differs
+/// from the committed one yields a different commitment and fails with
+/// `CommitmentNotFound`. Callers are responsible for authorization.
+pub fn withdraw_to_allowed(
+    env: &Env,
+    recipients: &Vec<Address>,
+    token: &Address,
+    amount: i128,
+    salt: Bytes,
+    recipient: &Address,
+) -> Result<WithdrawReceipt, QuickexError> {
+    if !recipients.contains(recipient) {
+        return Err(QuickexError::RecipientNotAllowed);
+    }
+    // The list itself is hashed with each algorithm too, so the preimage differs per algorithm
+    let mut preimages = Map::new(env);
+    for algorithm in HashAlgorithm::ALL {
+        let list_hash = commitment::recipient_list_hash(env, algorithm, recipients);
+        let preimage = commitment::build_recipients_preimage(
+            env,
+            &list_hash,
+            token.clone(),
+            amount,
+            salt.clone(),
+        )?;
+        preimages.set(algorithm, preimage);
+    }
+    let (commitment, entry) = open_withdrawable(
+        env,
+        |algorithm| commitment::hash_with(env, algorithm, &preimages.get(algorithm).unwrap()),
+        |held| held.token == *token && held.amount == amount,
+    )?;
+    pay_out(env, &commitment, entry, recipient, None)
+}
+
+/// Spend the escrow committed to `pubkey`, `token`, `amount`, and `salt`, paying `to`.
+///
+/// `signature` must be a valid Ed25519 signature by `pubkey` over
+/// [`commitment::build_withdraw_message`], so only the key holder can choose `to`. The
+/// host aborts the call if the signature does not verify. Nobody needs to authorize.
+pub fn withdraw_signed(
+    env: &Env,
+    to: &Address,
+    token: &Address,
+    amount: i128,
+    salt: Bytes,
+    pubkey: &BytesN<32>,
+    signature: &BytesN<64>,
+) -> Result<WithdrawReceipt, QuickexError> {
+    let preimage = commitment::build_signer_preimage(env, pubkey, token.clone(), amount, salt)?;
+    let (commitment, entry) = open_withdrawable(
+        env,
+        |algorithm| commitment::hash_with(env, algorithm, &preimage),
+        |held| held.token == *token && held.amount == amount,
+    )?;
+    let message = commitment::build_withdraw_message(env, &commitment, to, amount);
+    env.crypto().ed25519_verify(pubkey, &message, signature);
+    pay_out(env, &commitment, entry, to, None)
+}
+
+/// Check that `recipient` is not within the withdrawal cooldown.
+///
+/// With a cooldown of `n` ledgers configured, a recipient that withdrew at ledger `s` may
+/// withdraw again from ledger `s + n`; earlier attempts fail with
+/// `WithdrawCooldownActive`. Reads only; nothing is written.
+pub fn check_withdraw_cooldown(env: &Env, recipient: &Address) -> Result<(), QuickexError> {
+    let cooldown = get_withdraw_cooldown(env);
+    if cooldown == 0 {
+        return Ok(());
+    }
+    match get_last_withdraw(env, recipient) {
+        Some(last) if env.ledger().sequence() < last.saturating_add(cooldown) => {
+            Err(QuickexError::WithdrawCooldownActive)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Pay out a withdrawable escrow to `recipient` and mark it spent.
+///
+/// Fails with `RecipientBlocked` if `recipient` is on the deny-list, and with
+/// `WithdrawCooldownActive` if it withdrew too recently (see [`check_withdraw_cooldown`]).
+/// The entry is marked spent before any token transfer. Escrows with a swap target pay
+/// out the converted amount of the target token from the contract's reserve (its balance
+/// of that token not backing other escrows). If a protocol fee is configured it is sent to
+/// the fee collector; if `relayer` is given, its fee is sent to the relayer. Both fees are
+/// in the payout token. `recipient` receives the remainder, which is reported in the
+/// returned receipt along with the fees.
+fn pay_out(
+    env: &Env,
+    commitment: &BytesN<32>,
+    mut entry: EscrowEntry,
+    recipient: &Address,
+    relayer: Option<(&Address, i128)>,
+) -> Result<WithdrawReceipt, QuickexError> {
+    if is_blocked(env, recipient) {
+        return Err(QuickexError::RecipientBlocked);
+    }
+    check_withdraw_cooldown(env, recipient)?;
+
+    let amount = entry.amount;
+    let (payout_token, payout) = match &entry.payout {
+        Payout::Deposited => (entry.token.clone(), amount),
+        Payout::Swap(swap) => (
+            swap.token.clone(),
+            convert_amount(amount, &swap.rate).ok_or(QuickexError::InvalidAmount)?,
+        ),
+    };
+
+    if let Some((_, relayer_fee)) = relayer {
+        fees::validate_relayer_fee(payout, relayer_fee)?;
+    }
+
+    // A swap payout may only draw on the target token's surplus; the rest backs other
+    // escrows deposited in that token
+    let token_client = token::Client::new(env, &payout_token);
+    if payout_token != entry.token {
+        let reserve = token_client.balance(&env.current_contract_address())
+            - get_total_escrowed(env, &payout_token);
+        if reserve < payout {
+            return Err(QuickexError::InsufficientReserve);
+        }
+    }
+
+    set_status(env, commitment, &mut entry, EscrowStatus::Spent)?;
+    increment_counter(env, &DataKey::TotalWithdrawals);
+    if get_withdraw_cooldown(env) > 0 {
+        set_last_withdraw(env, recipient, env.ledger().sequence());
+    }
+
+    let fee = match get_fee_collector(env) {
+        Some(collector) => {
+            let fee = fees::compute_fee(payout, get_fee_bps(env));
+            if fee > 0 {
+                token_client.transfer(&env.current_contract_address(), &collector, &fee);
+            }
+            fee
+        }
+        None => 0,
+    };
+    let relayer_fee = match relayer {
+        Some((relayer, relayer_fee)) => {
+            if relayer_fee > 0 {
+                token_client.transfer(&env.current_contract_address(), relayer, &relayer_fee);
+            }
+            relayer_fee
+        }
+        None => 0,
+    };
+    let amount_out = payout - fee - relayer_fee;
+    token_client.transfer(&env.current_contract_address(), recipient, &amount_out);
+
+    publish_withdraw_toggled(env, recipient.clone(), commitment.clone());
+
+    Ok(WithdrawReceipt {
+        amount_out,
+        fee: fee + relayer_fee,
+        status: entry.status,
+        timestamp: env.ledger().timestamp(),
+    })
+}
+
+/// Return the funds of an expired escrow to its owner.
+///
+/// Accepts entries that are still `Pending` but past their expiry as well as entries
+/// already marked `Expired`. The owner may reclaim as soon as the escrow expires; the
+/// admin only once the reclaim window has also passed (see [`is_admin_reclaimable`]).
+/// The funds always go to the owner. The entry is marked spent before the transfer.
+/// Callers are responsible for authorization.
+pub fn refund_expired(
+    env: &Env,
+    caller: &Address,
+    commitment: &BytesN<32>,
+) -> Result<EscrowEntry, QuickexError> {
+    let mut entry: EscrowEntry =
+        get_escrow(env, &commitment.clone().into()).ok_or(QuickexError::CommitmentNotFound)?;
+
+    let is_owner = entry.owner == *caller;
+    if !is_owner && get_admin(env).as_ref() != Some(caller) {
+        publish_auth_failed(env, caller.clone(), "refund_expired");
+        return Err(QuickexError::Unauthorized);
+    }
+
+    match entry.status {
+        EscrowStatus::Pending => {
+            if !is_expired(&entry, env.ledger().timestamp()) {
+                return Err(QuickexError::EscrowNotExpired);
+            }
+        }
+        EscrowStatus::Expired => {}
+        EscrowStatus::Cancelled => return Err(QuickexError::EscrowCancelled),
+        EscrowStatus::Spent => return Err(QuickexError::AlreadySpent),
+    }
+
+    if !is_owner && !is_admin_reclaimable(&entry, env.ledger().timestamp(), get_reclaim_window(env))
+    {
+        return Err(QuickexError::ReclaimWindowActive);
+    }
+
+    set_status(env, commitment, &mut entry, EscrowStatus::Spent)?;
+    increment_counter(env, &DataKey::TotalRefunds);
+
+    let token_client = token::Client::new(env, &entry.token);
+    token_client.transfer(&env.current_contract_address(), &entry.owner, &entry.amount);
+
+    publish_escrow_refunded(env, entry.owner.clone(), commitment.clone(), entry.amount);
+
+    Ok(entry)
+}
+
+/// Refund every listed escrow owned by `depositor` that has expired.
+///
+/// Entries that do not exist, belong to someone else, or are not expired (pending past
+/// expiry, or already swept to `Expired`) are skipped. Refunds are summed per token and
+/// paid with one transfer each; the per-token totals are returned. Callers are
+/// responsible for authorization.
+pub fn withdraw_all_expired(
+    env: &Env,
+    depositor: &Address,
+    commitments: &Vec<BytesN<32>>,
+) -> Result<Map<Address, i128>, QuickexError> {
+    let now = env.ledger().timestamp();
+    let mut totals: Map<Address, i128> = Map::new(env);
+    for commitment in commitments.iter() {
+        let Some(mut entry) = get_escrow(env, &commitment.clone().into()) else {
+            continue;
+        };
+        let expired = match entry.status {
+            EscrowStatus::Pending => is_expired(&entry, now),
+            EscrowStatus::Expired => true,
+            EscrowStatus::Spent | EscrowStatus::Cancelled => false,
+        };
+        if entry.owner != *depositor || !expired {
+            continue;
+        }
+
+        set_status(env, &commitment, &mut entry, EscrowStatus::Spent)?;
+        increment_counter(env, &DataKey::TotalRefunds);
+        let total = totals
+            .get(entry.token.clone())
+            .unwrap_or(0)
+            .checked_add(entry.amount)
+            .ok_or(QuickexError::ArithmeticOverflow)?;
+        totals.set(entry.token.clone(), total);
+        publish_escrow_refunded(env, entry.owner.clone(), commitment.clone(), entry.amount);
+    }
+
+    for (token, amount) in totals.iter() {
+        token::Client::new(env, &token).transfer(
+            &env.current_contract_address(),
+            depositor,
+            &amount,
+        );
+    }
+
+    Ok(totals)
+}
+
+/// Mark every listed escrow that is `Pending` and past its expiry as `Expired`.
+///
+/// Commitments that do not exist, are not pending, or have not expired are skipped.
+/// Returns the number of escrows swept. Callers are responsible for authorization.
+pub fn sweep_expired(env: &Env, commitments: &Vec<BytesN<32>>) -> u32 {
+    let now = env.ledger().timestamp();
+    let mut swept = 0;
+    for commitment in commitments.iter() {
+        let Some(mut entry) = get_escrow(env, &commitment.clone().into()) else {
+            continue;
+        };
+        if entry.status == EscrowStatus::Pending
+            && is_expired(&entry, now)
+            && set_status(env, &commitment, &mut entry, EscrowStatus::Expired).is_ok()
+        {
+            swept += 1;
+        }
+    }
+    swept
+}
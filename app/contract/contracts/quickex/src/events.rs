@@ -1,4 +1,17 @@
-use soroban_sdk::{contractevent, Address, BytesN, Env};
+use soroban_sdk::{contractevent, Address, BytesN, Env, String, Symbol};
+
+use crate::storage::{get_event_verbosity, increment_counter, DataKey};
+use crate::types::{EscrowStatus, EventVerbosity};
+
+/// Count one more event published under `topic`, for `get_event_count`
+fn record(env: &Env, topic: &str) {
+    increment_counter(env, &DataKey::EventCount(Symbol::new(env, topic)));
+}
+
+/// Whether optional events of `level` are published at the admin-set verbosity
+fn emits(env: &Env, level: EventVerbosity) -> bool {
+    get_event_verbosity(env) >= level
+}
 
 #[contractevent(topics = ["PrivacyToggled"])]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -13,6 +26,8 @@ pub struct PrivacyToggledEvent {
 #[contractevent(topics = ["WithdrawToggled"])]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct WithdrawToggledEvent {
+    /// Indexed so recipients can filter their own withdrawal history
+    #[topic]
     pub to: Address,
     pub commitment: BytesN<32>,
     pub timestamp: u64,
@@ -21,34 +36,96 @@ pub struct WithdrawToggledEvent {
 #[contractevent(topics = ["Deposit"])]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct DepositToggledEvent {
-    pub commitment: BytesN<32>,
+    #[topic]
     pub token: Address,
+    #[topic]
+    pub commitment: BytesN<32>,
     pub amount: i128,
+    /// SHA-256 of the memo attached with `deposit_with_memo`, if any
+    pub memo_hash: Option<BytesN<32>>,
 }
 
 pub(crate) fn publish_privacy_toggled(env: &Env, owner: Address, enabled: bool, timestamp: u64) {
+    if !emits(env, EventVerbosity::Normal) {
+        return;
+    }
     PrivacyToggledEvent {
         owner,
         enabled,
         timestamp,
     }
     .publish(env);
+    record(env, "PrivacyToggled");
+}
+
+#[contractevent(topics = ["PrivacyLevelChanged"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PrivacyLevelChangedEvent {
+    #[topic]
+    pub account: Address,
+    /// Level before the change; `None` if the account had no level yet
+    pub old_level: Option<u32>,
+    pub new_level: u32,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_privacy_level_changed(
+    env: &Env,
+    account: Address,
+    old_level: Option<u32>,
+    new_level: u32,
+) {
+    if !emits(env, EventVerbosity::Normal) {
+        return;
+    }
+    PrivacyLevelChangedEvent {
+        account,
+        old_level,
+        new_level,
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+    record(env, "PrivacyLevelChanged");
 }
 
-#[allow(dead_code)]
 #[contractevent(topics = ["ContractPaused"])]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ContractPausedEvent {
     pub paused: bool,
+    /// Operator-supplied reason for pausing; always `None` on unpause
+    pub reason: Option<String>,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_contract_paused(
+    env: &Env,
+    paused: bool,
+    reason: Option<String>,
+    timestamp: u64,
+) {
+    ContractPausedEvent {
+        paused,
+        reason,
+        timestamp,
+    }
+    .publish(env);
+    record(env, "ContractPaused");
+}
+
+/// Emitted once, when the admin permanently shuts the contract down
+#[contractevent(topics = ["Shutdown"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ShutdownEvent {
+    #[topic]
+    pub admin: Address,
     pub timestamp: u64,
 }
 
-#[allow(dead_code)]
-pub(crate) fn publish_contract_paused(env: &Env, paused: bool, timestamp: u64) {
-    ContractPausedEvent { paused, timestamp }.publish(env);
+pub(crate) fn publish_shutdown(env: &Env, admin: Address, timestamp: u64) {
+    ShutdownEvent { admin, timestamp }.publish(env);
+    record(env, "Shutdown");
 }
 
-#[allow(dead_code)]
 #[contractevent(topics = ["AdminChanged"])]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct AdminChangedEvent {
@@ -59,7 +136,6 @@ pub struct AdminChangedEvent {
     pub timestamp: u64,
 }
 
-#[allow(dead_code)]
 pub(crate) fn publish_admin_changed(
     env: &Env,
     old_admin: Address,
@@ -72,6 +148,84 @@ pub(crate) fn publish_admin_changed(
         timestamp,
     }
     .publish(env);
+    record(env, "AdminChanged");
+}
+
+#[contractevent(topics = ["AdminTransferProposed"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdminTransferProposedEvent {
+    #[topic]
+    pub current_admin: Address,
+    #[topic]
+    pub pending_admin: Address,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_admin_transfer_proposed(
+    env: &Env,
+    current_admin: Address,
+    pending_admin: Address,
+    timestamp: u64,
+) {
+    AdminTransferProposedEvent {
+        current_admin,
+        pending_admin,
+        timestamp,
+    }
+    .publish(env);
+    record(env, "AdminTransferProposed");
+}
+
+#[contractevent(topics = ["FeeCollectorChanged"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeCollectorChangedEvent {
+    #[topic]
+    pub old_collector: Address,
+    #[topic]
+    pub new_collector: Address,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_fee_collector_changed(
+    env: &Env,
+    old_collector: Address,
+    new_collector: Address,
+) {
+    FeeCollectorChangedEvent {
+        old_collector,
+        new_collector,
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+    record(env, "FeeCollectorChanged");
+}
+
+/// Emitted when a caller is rejected with `Unauthorized`, for security monitoring.
+///
+/// The invocation then fails, so the host records this as a failed-call event: it is
+/// not part of the ledger's contract events but appears in the transaction's
+/// diagnostic events, which is where monitors should look for it.
+#[contractevent(topics = ["AuthFailed"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuthFailedEvent {
+    #[topic]
+    pub caller: Address,
+    /// Entrypoint that rejected the caller, e.g. `refund_expired`
+    pub operation: Symbol,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_auth_failed(env: &Env, caller: Address, operation: &str) {
+    if !emits(env, EventVerbosity::Verbose) {
+        return;
+    }
+    AuthFailedEvent {
+        caller,
+        operation: Symbol::new(env, operation),
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+    record(env, "AuthFailed");
 }
 
 #[contractevent(topics = ["ContractUpgraded"])]
@@ -96,6 +250,7 @@ pub(crate) fn publish_contract_upgraded(
         timestamp,
     }
     .publish(env);
+    record(env, "ContractUpgraded");
 }
 
 pub(crate) fn publish_withdraw_toggled(env: &Env, to: Address, commitment: BytesN<32>) {
@@ -105,13 +260,199 @@ pub(crate) fn publish_withdraw_toggled(env: &Env, to: Address, commitment: Bytes
         timestamp: env.ledger().timestamp(),
     }
     .publish(env);
+    record(env, "WithdrawToggled");
 }
 
-pub(crate) fn publish_deposit(env: &Env, commitment: BytesN<32>, token: Address, amount: i128) {
+pub(crate) fn publish_deposit(
+    env: &Env,
+    commitment: BytesN<32>,
+    token: Address,
+    amount: i128,
+    memo_hash: Option<BytesN<32>>,
+) {
     DepositToggledEvent {
+        token,
         commitment,
+        amount,
+        memo_hash,
+    }
+    .publish(env);
+    record(env, "Deposit");
+}
+
+/// Deposit event emitted instead of [`DepositToggledEvent`] when the depositor has
+/// privacy enabled; it carries no amount so observers cannot see deposit sizes.
+#[contractevent(topics = ["PrivateDeposit"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PrivateDepositEvent {
+    #[topic]
+    pub token: Address,
+    #[topic]
+    pub commitment: BytesN<32>,
+    pub memo_hash: Option<BytesN<32>>,
+}
+
+pub(crate) fn publish_private_deposit(
+    env: &Env,
+    commitment: BytesN<32>,
+    token: Address,
+    memo_hash: Option<BytesN<32>>,
+) {
+    PrivateDepositEvent {
         token,
+        commitment,
+        memo_hash,
+    }
+    .publish(env);
+    record(env, "PrivateDeposit");
+}
+
+#[contractevent(topics = ["CommitmentStatusChanged"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CommitmentStatusChangedEvent {
+    #[topic]
+    pub commitment: BytesN<32>,
+    /// Previous status; `None` when the escrow was just created
+    pub from: Option<EscrowStatus>,
+    pub to: EscrowStatus,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_commitment_status_changed(
+    env: &Env,
+    commitment: BytesN<32>,
+    from: Option<EscrowStatus>,
+    to: EscrowStatus,
+) {
+    if !emits(env, EventVerbosity::Verbose) {
+        return;
+    }
+    CommitmentStatusChangedEvent {
+        commitment,
+        from,
+        to,
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+    record(env, "CommitmentStatusChanged");
+}
+
+#[contractevent(topics = ["DepositCancelled"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DepositCancelledEvent {
+    #[topic]
+    pub depositor: Address,
+    pub commitment: BytesN<32>,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_deposit_cancelled(env: &Env, depositor: Address, commitment: BytesN<32>) {
+    DepositCancelledEvent {
+        depositor,
+        commitment,
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+    record(env, "DepositCancelled");
+}
+
+#[contractevent(topics = ["EscrowRefunded"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowRefundedEvent {
+    #[topic]
+    pub owner: Address,
+    pub commitment: BytesN<32>,
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_escrow_refunded(
+    env: &Env,
+    owner: Address,
+    commitment: BytesN<32>,
+    amount: i128,
+) {
+    EscrowRefundedEvent {
+        owner,
+        commitment,
         amount,
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+    record(env, "EscrowRefunded");
+}
+
+/// Emitted when a pending escrow is marked `Expired`, e.g. by `sweep_expired`
+#[contractevent(topics = ["EscrowExpired"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowExpiredEvent {
+    #[topic]
+    pub commitment: BytesN<32>,
+    /// Depositor who can now reclaim the funds with `refund_expired`
+    pub owner: Address,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_escrow_expired(env: &Env, commitment: BytesN<32>, owner: Address) {
+    if !emits(env, EventVerbosity::Normal) {
+        return;
+    }
+    EscrowExpiredEvent {
+        commitment,
+        owner,
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+    record(env, "EscrowExpired");
+}
+
+/// Emitted when `top_up` replaces a pending escrow with a larger one under a new commitment
+#[contractevent(topics = ["EscrowToppedUp"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowToppedUpEvent {
+    #[topic]
+    pub old_commitment: BytesN<32>,
+    #[topic]
+    pub new_commitment: BytesN<32>,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_escrow_topped_up(
+    env: &Env,
+    old_commitment: BytesN<32>,
+    new_commitment: BytesN<32>,
+) {
+    EscrowToppedUpEvent {
+        old_commitment,
+        new_commitment,
+        timestamp: env.ledger().timestamp(),
+    }
+    .publish(env);
+    record(env, "EscrowToppedUp");
+}
+
+/// Emitted when `transfer_escrow` moves a pending escrow to a commitment chosen by a new
+/// withdrawer
+#[contractevent(topics = ["EscrowTransferred"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowTransferredEvent {
+    #[topic]
+    pub old_commitment: BytesN<32>,
+    #[topic]
+    pub new_commitment: BytesN<32>,
+    pub timestamp: u64,
+}
+
+pub(crate) fn publish_escrow_transferred(
+    env: &Env,
+    old_commitment: BytesN<32>,
+    new_commitment: BytesN<32>,
+) {
+    EscrowTransferredEvent {
+        old_commitment,
+        new_commitment,
+        timestamp: env.ledger().timestamp(),
     }
     .publish(env);
+    record(env, "EscrowTransferred");
 }
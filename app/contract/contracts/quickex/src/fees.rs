@@ -0,0 +1,48 @@
+use soroban_sdk::{Address, Env};
+
+use crate::errors::QuickexError;
+
+/// Basis-point denominator (100% = 10_000 bps)
+pub const BPS_DENOMINATOR: i128 = 10_000;
+
+/// Maximum protocol fee the admin can configure (1000 bps = 10%)
+pub const MAX_FEE_BPS: u32 = 1_000;
+
+/// Maximum relayer fee on a relayed withdrawal (500 bps = 5% of the escrowed amount)
+pub const MAX_RELAYER_FEE_BPS: u32 = 500;
+
+/// Validate a relayer fee of `relayer_fee` on a withdrawal of `amount`
+pub fn validate_relayer_fee(amount: i128, relayer_fee: i128) -> Result<(), QuickexError> {
+    if relayer_fee < 0 {
+        return Err(QuickexError::InvalidAmount);
+    }
+    if relayer_fee > compute_fee(amount, MAX_RELAYER_FEE_BPS) {
+        return Err(QuickexError::FeeTooHigh);
+    }
+    Ok(())
+}
+
+/// Validate a fee setting against [`MAX_FEE_BPS`]
+pub fn validate_fee_bps(fee_bps: u32) -> Result<(), QuickexError> {
+    if fee_bps > MAX_FEE_BPS {
+        return Err(QuickexError::FeeTooHigh);
+    }
+    Ok(())
+}
+
+/// Validate a fee collector address; fees cannot be routed back into this contract
+pub fn validate_fee_collector(env: &Env, collector: &Address) -> Result<(), QuickexError> {
+    if *collector == env.current_contract_address() {
+        return Err(QuickexError::InvalidInput);
+    }
+    Ok(())
+}
+
+/// Compute `amount * fee_bps / 10_000`, rounded down.
+///
+/// The amount is split into quotient and remainder first so the multiplication
+/// cannot overflow for any positive `i128` amount.
+pub fn compute_fee(amount: i128, fee_bps: u32) -> i128 {
+    let bps = fee_bps as i128;
+    (amount / BPS_DENOMINATOR) * bps + (amount % BPS_DENOMINATOR) * bps / BPS_DENOMINATOR
+}
@@ -1,18 +1,43 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, token, Address, Bytes, BytesN, Env, Vec};
+use soroban_sdk::{
+    contract, contractimpl, contractmeta, token, Address, Bytes, BytesN, Env, Map, String, Symbol,
+    Vec,
+};
 
 mod admin;
 mod commitment;
 mod errors;
+mod escrow;
 mod events;
+mod fees;
+mod merkle;
 mod privacy;
 mod storage;
+mod transfer;
 mod types;
 
 use errors::QuickexError;
-use events::publish_withdraw_toggled;
+use escrow::DepositOptions;
 use storage::*;
-use types::{EscrowEntry, EscrowStatus};
+use types::{
+    AmountBounds, BasketItem, ContractConfig, ContractStats, EscrowEntry, EscrowStatus,
+    EventVerbosity, HashAlgorithm, HealthStatus, Role, SplitShare, StatusCounts, SwapRate,
+    TtlConfig, WithdrawReceipt, DEFAULT_AMOUNT_BOUNDS, REDACTED_AMOUNT,
+};
+
+/// Contract interface version, bumped on every release that changes behaviour or storage.
+/// Keep in sync with the `version` contract metadata entry below.
+pub const VERSION: u32 = 1;
+
+/// Maximum number of elements in the `Vec` argument of any batch entrypoint.
+///
+/// Larger inputs fail up front with `BatchTooLarge` instead of running out of resource
+/// budget part-way through. Sized so a full `deposit_basket` with a deposit fee (three
+/// token calls per item) stays well inside the default budget.
+pub const MAX_BATCH: u32 = 20;
+
+contractmeta!(key = "name", val = "quickex");
+contractmeta!(key = "version", val = "1");
 
 /// QuickEx Privacy Contract
 ///
@@ -25,361 +50,2457 @@ pub struct QuickexContract;
 impl QuickexContract {
     /// Withdraw escrowed funds by proving commitment ownership.
     ///
-    /// The caller (`to`) must authorize; the commitment is recomputed from `to`, `amount`, and `salt`
-    /// and must match an existing pending escrow entry.
+    /// The caller (`to`) must authorize; the commitment is recomputed from `to`, `token`,
+    /// `amount`, and `salt` and must match an existing pending escrow entry. If a protocol fee is configured, the fee is
+    /// sent to the fee collector and `to` receives the remainder.
+    ///
+    /// Returns a [`WithdrawReceipt`] with the amount `to` received, the fee deducted, the
+    /// escrow's final status and the ledger timestamp.
+    ///
+    /// The recipient binding lives in the commitment itself: an escrow can only be found from
+    /// the `to` it was committed to. `entry.owner` is the depositor and is deliberately not
+    /// compared with `to`, since `deposit_with_commitment` escrows for someone else.
     ///
     /// # Arguments
     /// * `env` - The contract environment
-    /// * `_token` - Reserved; token is stored in the escrow entry
+    /// * `token` - Token the escrow was deposited in; part of the commitment
     /// * `amount` - Amount to withdraw; must be positive and match the escrow amount
-    /// * `_commitment` - Reserved; commitment is derived from `to`, `amount`, `salt`
+    /// * `_commitment` - Reserved; commitment is derived from `to`, `token`, `amount`, `salt`
     /// * `to` - Recipient address (must authorize the call)
     /// * `salt` - Salt used when creating the original deposit commitment
     ///
     /// # Errors
+    /// * `ContractPaused` - The contract is paused
     /// * `InvalidAmount` - Amount is zero or negative
     /// * `CommitmentNotFound` - No escrow exists for the computed commitment
     /// * `AlreadySpent` - Escrow has already been withdrawn or marked spent
-    /// * `InvalidCommitment` - Escrow amount does not match the requested amount
+    /// * `EscrowCancelled` - Escrow was cancelled by the depositor
+    /// * `InvalidCommitment` - Escrow token or amount does not match the request
+    /// * `EscrowExpired` - Escrow has passed its expiry and can only be refunded
+    /// * `RecipientBlocked` - The recipient is on the admin deny-list
+    /// * `WithdrawCooldownActive` - The recipient withdrew within the withdrawal cooldown
     pub fn withdraw(
         env: Env,
-        _token: &Address,
+        token: &Address,
         amount: i128,
         _commitment: BytesN<32>,
         to: Address,
 
         salt: Bytes,
-    ) -> Result<bool, QuickexError> {
+    ) -> Result<WithdrawReceipt, QuickexError> {
+        admin::require_not_paused(&env)?;
+
         if amount <= 0 {
             return Err(QuickexError::InvalidAmount);
         }
 
         to.require_auth();
 
-        let commitment = commitment::create_amount_commitment(&env, to.clone(), amount, salt)?;
-
-        let entry: EscrowEntry =
-            get_escrow(&env, &commitment.clone().into()).ok_or(QuickexError::CommitmentNotFound)?;
-
-        if entry.status != EscrowStatus::Pending {
-            return Err(QuickexError::AlreadySpent);
-        }
-
-        if entry.amount != amount {
-            return Err(QuickexError::InvalidCommitment);
-        }
-
-        let mut updated_entry = entry.clone();
-        updated_entry.status = EscrowStatus::Spent;
-        put_escrow(&env, &commitment.clone().into(), &updated_entry);
-
-        let token_client = token::Client::new(&env, &entry.token);
-        token_client.transfer(&env.current_contract_address(), &to, &amount);
-
-        publish_withdraw_toggled(&env, to, commitment);
-
-        Ok(true)
+        escrow::withdraw(&env, &to, token, amount, salt, &to, None)
     }
 
-    /// Set a numeric privacy level for an account (legacy/level-based API).
+    /// Withdraw an escrow deposited under a bucketed commitment.
     ///
-    /// Records the level in storage and appends it to the account's privacy history.
-    /// For boolean on/off privacy, prefer [`set_privacy`](QuickexContract::set_privacy).
+    /// The commitment is recomputed from `to`, `token`, `bucket_id`, and `salt` (see
+    /// [`create_bucketed_commitment`](QuickexContract::create_bucketed_commitment)) and the
+    /// escrowed amount must fall inside the bucket. Pays the full escrowed amount, less any
+    /// protocol fee, to `to`.
     ///
     /// # Arguments
     /// * `env` - The contract environment
-    /// * `account` - The account to configure
-    /// * `privacy_level` - Numeric level (0 = off, higher = more privacy; interpretation is application-specific)
-    pub fn enable_privacy(env: Env, account: Address, privacy_level: u32) -> bool {
-        set_privacy_level(&env, &account, privacy_level);
-        add_privacy_history(&env, &account, privacy_level);
-        true
-    }
-
-    /// Get the current numeric privacy level for an account.
+    /// * `to` - Recipient address (must authorize the call)
+    /// * `token` - Token the escrow was deposited in
+    /// * `bucket_id` - Power-of-ten bucket committed to
+    /// * `salt` - Salt used when creating the commitment
     ///
-    /// Returns `None` if no level has been set.
+    /// # Returns
+    /// The escrowed amount that was withdrawn.
     ///
-    /// # Arguments
-    /// * `env` - The contract environment
-    /// * `account` - The account to query
-    pub fn privacy_status(env: Env, account: Address) -> Option<u32> {
-        get_privacy_level(&env, &account)
-    }
+    /// # Errors
+    /// Same as [`withdraw`](QuickexContract::withdraw), except that `InvalidCommitment`
+    /// means the escrowed amount lies outside the bucket, plus:
+    /// * `InvalidInput` - `bucket_id` is above 38
+    pub fn withdraw_bucketed(
+        env: Env,
+        to: Address,
+        token: Address,
+        bucket_id: u32,
+        salt: Bytes,
+    ) -> Result<i128, QuickexError> {
+        admin::require_not_paused(&env)?;
 
-    /// Get the history of privacy level changes for an account.
-    ///
-    /// Returns a vector of levels in chronological order (oldest first).
-    ///
-    /// # Arguments
-    /// * `env` - The contract environment
-    /// * `account` - The account to query
-    pub fn privacy_history(env: Env, account: Address) -> Vec<u32> {
-        get_privacy_history(&env, &account)
+        to.require_auth();
+
+        escrow::withdraw_bucketed(&env, &to, &token, bucket_id, salt, &to)
     }
 
-    /// Enable or disable privacy for an account.
+    /// Withdraw an escrow deposited under a recipient-list commitment.
+    ///
+    /// The commitment is recomputed from `recipients`, `token`, `amount`, and `salt` (see
+    /// [`create_recipients_commitment`](QuickexContract::create_recipients_commitment)) and
+    /// `to` must be on the list. Pays the escrowed amount, less any protocol fee, to `to`.
     ///
     /// # Arguments
     /// * `env` - The contract environment
-    /// * `owner` - The account address to configure
-    /// * `enabled` - `true` to enable privacy, `false` to disable
+    /// * `to` - Recipient address (must authorize the call and be in `recipients`)
+    /// * `recipients` - The allowed-recipients list the commitment was created with
+    /// * `token` - Token the escrow was deposited in
+    /// * `amount` - Amount to withdraw; must be positive and match the escrow amount
+    /// * `salt` - Salt used when creating the commitment
     ///
     /// # Errors
-    /// * `PrivacyAlreadySet` - Privacy state is already at the requested value
-    /// * `InvalidPrivacyLevel` - Invalid internal state (e.g. invalid level transition)
-    pub fn set_privacy(env: Env, owner: Address, enabled: bool) -> Result<(), QuickexError> {
-        privacy::set_privacy(&env, owner, enabled)
-    }
+    /// Same as [`withdraw`](QuickexContract::withdraw), plus:
+    /// * `RecipientNotAllowed` - `to` is not in `recipients`
+    pub fn withdraw_to_allowed(
+        env: Env,
+        to: Address,
+        recipients: Vec<Address>,
+        token: Address,
+        amount: i128,
+        salt: Bytes,
+    ) -> Result<WithdrawReceipt, QuickexError> {
+        admin::require_not_paused(&env)?;
 
-    /// Check the current privacy status of an account
-    ///
-    /// # Arguments
-    /// * `env` - The contract environment
-    /// * `owner` - The account address to query
-    ///
-    /// # Returns
-    /// * `bool` - Current privacy status (true = enabled)
-    pub fn get_privacy(env: Env, owner: Address) -> bool {
-        privacy::get_privacy(&env, owner)
+        if amount <= 0 {
+            return Err(QuickexError::InvalidAmount);
+        }
+
+        to.require_auth();
+
+        escrow::withdraw_to_allowed(&env, &recipients, &token, amount, salt, &to)
     }
 
-    /// Deposit funds and create an escrow entry keyed by a commitment hash.
+    /// Withdraw an escrow deposited under a signer commitment, authorized by signature.
     ///
-    /// Transfers `amount` from `owner` to the contract and stores an escrow keyed by
-    /// `SHA256(owner || amount || salt)`. The owner must authorize the transfer.
+    /// The commitment is recomputed from `pubkey`, `token`, `amount`, and `salt` (see
+    /// [`create_signer_commitment`](QuickexContract::create_signer_commitment)), and
+    /// `signature` must be an Ed25519 signature by `pubkey` over
+    /// [`build_withdraw_message`](QuickexContract::build_withdraw_message) for the
+    /// commitment, `to` and `amount`. No address authorizes the call, so anyone (e.g. a
+    /// relayer paying the network fee) can submit it without being linked to `to`, and
+    /// because `to` is signed it cannot be redirected. Pays the escrowed amount, less any
+    /// protocol fee, to `to`.
     ///
     /// # Arguments
     /// * `env` - The contract environment
-    /// * `token` - The token contract address
-    /// * `amount` - Amount to deposit; must be positive
-    /// * `owner` - Owner of the funds (must authorize)
-    /// * `salt` - Random salt (0–1024 bytes) for uniqueness; same inputs = same commitment
+    /// * `to` - Recipient chosen by the signer (does not authorize)
+    /// * `token` - Token the escrow was deposited in
+    /// * `amount` - Amount to withdraw; must be positive and match the escrow amount
+    /// * `salt` - Salt used when creating the commitment
+    /// * `pubkey` - Ed25519 public key the commitment binds
+    /// * `signature` - Signature by `pubkey` over the withdraw message
     ///
     /// # Errors
-    /// * `InvalidAmount` - Amount is zero or negative
-    /// * `InvalidSalt` - Salt length exceeds 1024 bytes
-    pub fn deposit(
+    /// Same as [`withdraw`](QuickexContract::withdraw). A `signature` that does not verify
+    /// is not reported as a contract error: signature verification traps the host with
+    /// `Error(Crypto, InvalidInput)`, so the whole call reverts and no `AuthFailed` event is
+    /// emitted.
+    pub fn withdraw_signed(
         env: Env,
+        to: Address,
         token: Address,
         amount: i128,
-        owner: Address,
         salt: Bytes,
-    ) -> Result<BytesN<32>, QuickexError> {
+        pubkey: BytesN<32>,
+        signature: BytesN<64>,
+    ) -> Result<WithdrawReceipt, QuickexError> {
+        admin::require_not_paused(&env)?;
+
         if amount <= 0 {
             return Err(QuickexError::InvalidAmount);
         }
 
-        owner.require_auth();
-
-        let commitment = commitment::create_amount_commitment(&env, owner.clone(), amount, salt)?;
-
-        let entry = EscrowEntry {
-            token: token.clone(),
-            amount,
-            owner: owner.clone(),
-            status: EscrowStatus::Pending,
-            created_at: env.ledger().timestamp(),
-        };
-
-        put_escrow(&env, &commitment.clone().into(), &entry);
-
-        let token_client = token::Client::new(&env, &token);
-        token_client.transfer(&owner, env.current_contract_address(), &amount);
-
-        Ok(commitment)
+        escrow::withdraw_signed(&env, &to, &token, amount, salt, &pubkey, &signature)
     }
 
-    /// Create a deterministic commitment hash for an amount (off-chain / pre-deposit use).
+    /// Withdraw escrowed funds to `recipient` and enable privacy for the recipient in one call.
     ///
-    /// Computes `SHA256(owner || amount || salt)`. Not a zero-knowledge proof; same inputs
-    /// always yield the same hash. Use for API shape validation and audit trails.
+    /// Behaves like [`withdraw`](QuickexContract::withdraw) with the commitment recomputed from
+    /// `owner`, `token`, `amount`, and `salt`, but the funds are paid to `recipient`, whose privacy flag
+    /// is switched on so the receipt is hidden.
     ///
     /// # Arguments
     /// * `env` - The contract environment
-    /// * `owner` - The owner address
-    /// * `amount` - Non-negative amount in token base units
-    /// * `salt` - Random bytes (0–1024 bytes) for uniqueness
+    /// * `owner` - Commitment owner (must authorize)
+    /// * `recipient` - Address receiving the funds (must authorize the privacy change)
+    /// * `token` - Token the escrow was deposited in
+    /// * `amount` - Amount to withdraw; must be positive and match the escrow amount
+    /// * `salt` - Salt used when creating the original deposit commitment
     ///
     /// # Errors
-    /// * `InvalidAmount` - Amount is negative
-    /// * `InvalidSalt` - Salt length exceeds 1024 bytes
-    pub fn create_amount_commitment(
+    /// Same as [`withdraw`](QuickexContract::withdraw).
+    pub fn withdraw_private(
         env: Env,
         owner: Address,
+        recipient: Address,
+        token: Address,
         amount: i128,
         salt: Bytes,
-    ) -> Result<BytesN<32>, QuickexError> {
-        commitment::create_amount_commitment(&env, owner, amount, salt)
+    ) -> Result<bool, QuickexError> {
+        admin::require_not_paused(&env)?;
+
+        if amount <= 0 {
+            return Err(QuickexError::InvalidAmount);
+        }
+
+        owner.require_auth();
+
+        escrow::withdraw(&env, &owner, &token, amount, salt, &recipient, None)?;
+        privacy::set_privacy(&env, recipient, true)?;
+
+        Ok(true)
     }
 
-    /// Verify that a commitment hash matches the given `owner`, `amount`, and `salt`.
+    /// Withdraw escrowed funds on behalf of `to`, submitted and paid for by a relayer.
     ///
-    /// Recomputes the commitment and compares. Returns `false` if inputs are invalid or don't match.
+    /// `to` does not sign: knowledge of the salt behind the commitment is the proof of
+    /// entitlement. Because `to` is bound into the commitment, the funds can only ever be
+    /// paid to `to`, so a relayer (or anyone who sees the salt) cannot redirect them. The
+    /// relayer authorizes the call, pays the network fees, and receives `relayer_fee` from
+    /// the escrowed amount, capped at 5% of `amount`. Any protocol fee is taken as well and
+    /// `to` receives the remainder.
+    ///
+    /// The salt becomes public once submitted, so a competing relayer could front-run the
+    /// transaction and collect the relayer fee; the recipient is paid either way.
     ///
     /// # Arguments
     /// * `env` - The contract environment
-    /// * `commitment` - 32-byte commitment hash to verify
-    /// * `owner` - Claimed owner
-    /// * `amount` - Claimed amount (must be non-negative)
-    /// * `salt` - Salt used when creating the commitment
-    pub fn verify_amount_commitment(
+    /// * `relayer` - Submitter receiving the relayer fee (must authorize)
+    /// * `to` - Recipient and commitment owner (does not authorize)
+    /// * `token` - Token the escrow was deposited in
+    /// * `amount` - Amount to withdraw; must be positive and match the escrow amount
+    /// * `salt` - Salt used when creating the original deposit commitment
+    /// * `relayer_fee` - Amount paid to `relayer`; between 0 and 5% of `amount`
+    ///
+    /// # Errors
+    /// Same as [`withdraw`](QuickexContract::withdraw), plus:
+    /// * `InvalidAmount` - `relayer_fee` is negative
+    /// * `FeeTooHigh` - `relayer_fee` exceeds 5% of `amount`
+    pub fn withdraw_relayed(
         env: Env,
-        commitment: BytesN<32>,
-        owner: Address,
+        relayer: Address,
+        to: Address,
+        token: Address,
         amount: i128,
         salt: Bytes,
-    ) -> bool {
-        commitment::verify_amount_commitment(&env, commitment, owner, amount, salt)
-    }
+        relayer_fee: i128,
+    ) -> Result<bool, QuickexError> {
+        admin::require_not_paused(&env)?;
 
-    /// Create an escrow record and increment the global escrow counter.
-    ///
-    /// Returns the new counter value. Parameters `_from`, `_to`, `_amount` are reserved for
-    /// future use; the implementation only increments the counter.
-    ///
-    /// # Arguments
-    /// * `env` - The contract environment
-    /// * `_from` - Reserved (depositor address for future use)
-    /// * `_to` - Reserved (recipient address for future use)
-    /// * `_amount` - Reserved (amount for future use)
-    pub fn create_escrow(env: Env, _from: Address, _to: Address, _amount: u64) -> u64 {
-        increment_escrow_counter(&env)
-    }
+        if amount <= 0 {
+            return Err(QuickexError::InvalidAmount);
+        }
 
-    /// Health check for deployment and monitoring.
-    ///
-    /// Returns `true` if the contract is deployed and callable. No state or auth required.
-    pub fn health_check() -> bool {
-        true
+        relayer.require_auth();
+
+        escrow::withdraw(
+            &env,
+            &to,
+            &token,
+            amount,
+            salt,
+            &to,
+            Some((&relayer, relayer_fee)),
+        )?;
+
+        Ok(true)
     }
 
-    /// Deposit funds using a pre-generated 32-byte commitment hash.
+    /// Cancel a pending deposit and return the funds to the depositor.
     ///
-    /// Transfers `amount` from `from` to the contract and stores an escrow keyed by
-    /// `commitment`. The depositor must authorize. Use when the commitment was created
-    /// off-chain or via [`create_amount_commitment`](QuickexContract::create_amount_commitment).
+    /// Only the address recorded as the escrow owner at deposit time may cancel, and only
+    /// while the escrow is still `Pending`. The escrow is marked `Cancelled` so it can no
+    /// longer be withdrawn.
     ///
     /// # Arguments
     /// * `env` - The contract environment
-    /// * `from` - Depositor (must authorize the token transfer)
-    /// * `token` - Token contract address
-    /// * `amount` - Amount to deposit; must be positive
-    /// * `commitment` - 32-byte commitment hash (must be unique)
+    /// * `depositor` - Original depositor (must authorize and equal the stored owner)
+    /// * `commitment` - 32-byte commitment hash of the escrow to cancel
     ///
     /// # Errors
-    /// * `InvalidAmount` - Amount is zero or negative
-    /// * `CommitmentAlreadyExists` - An escrow for this commitment already exists
-    pub fn deposit_with_commitment(
+    /// * `ContractPaused` - The contract is paused
+    /// * `CommitmentNotFound` - No escrow exists for the commitment
+    /// * `Unauthorized` - `depositor` is not the stored owner of the escrow; an `AuthFailed`
+    ///   event records the attempt
+    /// * `AlreadySpent` - Escrow is no longer pending (withdrawn, expired, or cancelled)
+    pub fn cancel_deposit(
         env: Env,
-        from: Address,
-        token: Address,
-        amount: i128,
+        depositor: Address,
         commitment: BytesN<32>,
     ) -> Result<(), QuickexError> {
-        if amount <= 0 {
-            return Err(QuickexError::InvalidAmount);
-        }
+        admin::require_not_paused(&env)?;
 
-        from.require_auth();
+        depositor.require_auth();
 
-        if has_escrow(&env, &commitment.clone().into()) {
-            return Err(QuickexError::CommitmentAlreadyExists);
+        let mut entry: EscrowEntry =
+            get_escrow(&env, &commitment.clone().into()).ok_or(QuickexError::CommitmentNotFound)?;
+
+        if entry.owner != depositor {
+            events::publish_auth_failed(&env, depositor, "cancel_deposit");
+            return Err(QuickexError::Unauthorized);
         }
 
-        let token_client = token::Client::new(&env, &token);
-        token_client.transfer(&from, env.current_contract_address(), &amount);
+        if entry.status != EscrowStatus::Pending {
+            return Err(QuickexError::AlreadySpent);
+        }
 
-        let entry = EscrowEntry {
-            token: token.clone(),
-            amount,
-            owner: from.clone(),
-            status: EscrowStatus::Pending,
-            created_at: env.ledger().timestamp(),
-        };
+        escrow::set_status(&env, &commitment, &mut entry, EscrowStatus::Cancelled)?;
+        increment_counter(&env, &DataKey::TotalRefunds);
 
-        put_escrow(&env, &commitment.clone().into(), &entry);
+        let token_client = token::Client::new(&env, &entry.token);
+        token_client.transfer(&env.current_contract_address(), &depositor, &entry.amount);
 
-        events::publish_deposit(&env, commitment, token, amount);
+        events::publish_deposit_cancelled(&env, depositor, commitment);
 
         Ok(())
     }
 
-    /// Initialize the contract with an admin address (one-time only).
+    /// Add funds to a pending escrow, moving it to a new commitment.
     ///
-    /// Sets the admin who can pause/unpause, transfer admin, and upgrade the contract.
+    /// The commitment binds the amount, so a top-up cannot keep it. The old escrow is
+    /// marked spent, making `old_commitment` unusable, and the combined amount is escrowed
+    /// under a new commitment computed like [`deposit`](QuickexContract::deposit), from
+    /// `from`, the escrow's token, the combined amount and `new_salt`. Token, payout and
+    /// expiry carry over; the memo does not. Emits `EscrowToppedUp` linking the two
+    /// commitments.
     ///
     /// # Arguments
     /// * `env` - The contract environment
-    /// * `admin` - The admin address to set
+    /// * `from` - Escrow owner (must authorize the transfer and equal the stored owner)
+    /// * `old_commitment` - Commitment of the pending escrow to top up
+    /// * `additional_amount` - Amount to add; must be positive (before any deposit fee)
+    /// * `new_salt` - Fresh salt (16–1024 bytes) for the new commitment
+    ///
+    /// # Returns
+    /// The new commitment.
     ///
     /// # Errors
-    /// * `AlreadyInitialized` - Contract has already been initialized
-    pub fn initialize(env: Env, admin: Address) -> Result<(), QuickexError> {
-        if get_admin(&env).is_some() {
-            return Err(QuickexError::AlreadyInitialized);
+    /// * `ContractPaused` - The contract is paused
+    /// * `InvalidAmount` - `additional_amount` is zero or negative
+    /// * `CommitmentNotFound` - No escrow exists for `old_commitment`
+    /// * `AlreadySpent` / `EscrowCancelled` - The escrow is no longer pending
+    /// * `EscrowExpired` - The escrow has expired and can only be refunded
+    /// * `Unauthorized` - `from` is not the stored owner; an `AuthFailed` event records the
+    ///   attempt
+    /// * `AmountAboveMaximum` - The combined amount exceeds the token's bounds
+    /// * `ArithmeticOverflow` - The combined amount overflows
+    /// * `InvalidSalt` / `WeakSalt` - `new_salt` is too long or too short
+    /// * `SaltReused` - `from` has already deposited with `new_salt`
+    pub fn top_up(
+        env: Env,
+        from: Address,
+        old_commitment: BytesN<32>,
+        additional_amount: i128,
+        new_salt: Bytes,
+    ) -> Result<BytesN<32>, QuickexError> {
+        admin::require_not_paused(&env)?;
+
+        if additional_amount <= 0 {
+            return Err(QuickexError::InvalidAmount);
         }
-        set_admin(&env, &admin);
-        Ok(())
+
+        from.require_auth();
+
+        escrow::top_up(&env, &from, &old_commitment, additional_amount, new_salt)
     }
 
-    /// Pause or unpause the contract (**Admin only**).
+    /// Assign a pending escrow to a new withdrawer without moving funds.
     ///
-    /// When paused, certain operations may be blocked. Caller must equal the stored admin.
+    /// Withdrawal is gated by knowledge of the commitment opening, so the new withdrawer
+    /// computes `new_owner_commitment` (e.g. with
+    /// [`create_amount_commitment`](QuickexContract::create_amount_commitment) over their
+    /// own address, the escrow's token and amount, and a salt only they know) and the
+    /// depositor rebinds the escrow to it. The old escrow is marked spent, making
+    /// `commitment` unusable, and nobody learns the new opening. The new escrow is owned by
+    /// `new_owner`, so the old owner can no longer cancel, refund or edit it and the
+    /// assignment cannot be revoked. Token, amount, payout and expiry carry over; the memo
+    /// does not. Emits `EscrowTransferred` linking the two commitments.
     ///
     /// # Arguments
     /// * `env` - The contract environment
-    /// * `caller` - Caller address (must equal admin)
-    /// * `new_state` - `true` to pause, `false` to unpause
+    /// * `old_owner` - Escrow owner (must authorize and equal the stored owner)
+    /// * `commitment` - Commitment of the pending escrow to transfer
+    /// * `new_owner` - Account that takes over the escrow's owner rights (must authorize)
+    /// * `new_owner_commitment` - Commitment supplied by the new withdrawer
     ///
     /// # Errors
-    /// * `Unauthorized` - Caller is not the admin, or admin not set
-    pub fn set_paused(env: Env, caller: Address, new_state: bool) -> Result<(), QuickexError> {
-        let admin = get_admin(&env).ok_or(QuickexError::Unauthorized)?;
-        if caller != admin {
-            return Err(QuickexError::Unauthorized);
+    /// * `ContractPaused` - The contract is paused
+    /// * `CommitmentNotFound` - No escrow exists for `commitment`
+    /// * `AlreadySpent` / `EscrowCancelled` - The escrow is no longer pending
+    /// * `EscrowExpired` - The escrow has expired and can only be refunded
+    /// * `Unauthorized` - `old_owner` is not the stored owner; an `AuthFailed` event
+    ///   records the attempt
+    /// * `CommitmentAlreadyExists` - An escrow already exists for `new_owner_commitment`
+    /// * `TooManyPendingEscrows` - `new_owner` already holds the maximum pending escrows
+    pub fn transfer_escrow(
+        env: Env,
+        old_owner: Address,
+        commitment: BytesN<32>,
+        new_owner: Address,
+        new_owner_commitment: BytesN<32>,
+    ) -> Result<(), QuickexError> {
+        admin::require_not_paused(&env)?;
+
+        old_owner.require_auth();
+        if new_owner != old_owner {
+            new_owner.require_auth();
         }
-        set_paused(&env, new_state);
-        Ok(())
+
+        escrow::transfer_escrow(
+            &env,
+            &old_owner,
+            &commitment,
+            &new_owner,
+            &new_owner_commitment,
+        )
     }
 
-    /// Transfer admin rights to a new address (**Admin only**).
+    /// Replace the memo attached to a pending escrow.
     ///
-    /// Caller must equal the current admin. The new admin can later transfer again.
+    /// Only the stored escrow owner may update the memo, and only before the escrow is spent.
     ///
     /// # Arguments
     /// * `env` - The contract environment
-    /// * `caller` - Caller address (must equal current admin)
-    /// * `new_admin` - New admin address
+    /// * `owner` - Escrow owner (must authorize and equal the stored owner)
+    /// * `commitment` - 32-byte commitment hash of the escrow
+    /// * `new_memo` - Opaque (typically encrypted) note, at most 256 bytes
     ///
     /// # Errors
-    /// * `Unauthorized` - Caller is not the admin, or admin not set
-    pub fn set_admin(env: Env, caller: Address, new_admin: Address) -> Result<(), QuickexError> {
-        let admin = get_admin(&env).ok_or(QuickexError::Unauthorized)?;
-        if caller != admin {
+    /// * `ContractPaused` - The contract is paused
+    /// * `MemoTooLong` - `new_memo` exceeds 256 bytes
+    /// * `CommitmentNotFound` - No escrow exists for the commitment
+    /// * `Unauthorized` - `owner` is not the stored owner of the escrow
+    /// * `AlreadySpent` - Escrow is no longer pending
+    pub fn update_memo(
+        env: Env,
+        owner: Address,
+        commitment: BytesN<32>,
+        new_memo: Bytes,
+    ) -> Result<(), QuickexError> {
+        admin::require_not_paused(&env)?;
+
+        owner.require_auth();
+
+        if new_memo.len() > MAX_MEMO_LEN {
+            return Err(QuickexError::MemoTooLong);
+        }
+
+        let commitment_bytes: Bytes = commitment.into();
+        let entry = get_escrow(&env, &commitment_bytes).ok_or(QuickexError::CommitmentNotFound)?;
+
+        if entry.owner != owner {
             return Err(QuickexError::Unauthorized);
         }
-        set_admin(&env, &new_admin);
+
+        if entry.status != EscrowStatus::Pending {
+            return Err(QuickexError::AlreadySpent);
+        }
+
+        set_memo(&env, &commitment_bytes, &new_memo);
         Ok(())
     }
 
-    /// Check if the contract is currently paused.
+    /// Get the memo attached to an escrow, if any (read-only).
     ///
-    /// Returns `true` if paused, `false` otherwise.
-    pub fn is_paused(env: Env) -> bool {
-        is_paused(&env)
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `commitment` - 32-byte commitment hash of the escrow
+    pub fn get_escrow_memo(env: Env, commitment: BytesN<32>) -> Option<Bytes> {
+        get_memo(&env, &commitment.into())
     }
 
-    /// Get the current admin address.
+    /// Register a viewing key for an escrow, for selective disclosure.
     ///
-    /// Returns `None` if the contract has not been initialized.
-    pub fn get_admin(env: Env) -> Option<Address> {
-        get_admin(&env)
-    }
-
+    /// Only `SHA256(view_key)` is stored. Whoever the owner shares the viewing key with
+    /// (e.g. an auditor) can confirm it with
+    /// [`verify_view_access`](QuickexContract::verify_view_access) without learning the salt
+    /// that spends the escrow. Registering again replaces the previous key.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `owner` - Escrow owner (must authorize and equal the stored owner)
+    /// * `commitment` - 32-byte commitment hash of the escrow
+    /// * `view_key_hash` - SHA-256 hash of the viewing key
+    ///
+    /// # Errors
+    /// * `ContractPaused` - The contract is paused
+    /// * `CommitmentNotFound` - No escrow exists for the commitment
+    /// * `Unauthorized` - `owner` is not the stored owner of the escrow
+    pub fn set_view_key(
+        env: Env,
+        owner: Address,
+        commitment: BytesN<32>,
+        view_key_hash: BytesN<32>,
+    ) -> Result<(), QuickexError> {
+        admin::require_not_paused(&env)?;
+
+        owner.require_auth();
+
+        let commitment_bytes: Bytes = commitment.into();
+        let entry = get_escrow(&env, &commitment_bytes).ok_or(QuickexError::CommitmentNotFound)?;
+
+        if entry.owner != owner {
+            return Err(QuickexError::Unauthorized);
+        }
+
+        set_view_key_hash(&env, &commitment_bytes, &view_key_hash);
+        Ok(())
+    }
+
+    /// Check whether `view_key` is the viewing key registered for an escrow (read-only).
+    ///
+    /// Returns `false` if no viewing key has been registered.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `commitment` - 32-byte commitment hash of the escrow
+    /// * `view_key` - Viewing key to check
+    pub fn verify_view_access(env: Env, commitment: BytesN<32>, view_key: Bytes) -> bool {
+        let hash: BytesN<32> = env.crypto().sha256(&view_key).into();
+        get_view_key_hash(&env, &commitment.into()) == Some(hash)
+    }
+
+    /// Set a numeric privacy level for an account (legacy/level-based API).
+    ///
+    /// Records the level in storage, appends it to the account's privacy history and
+    /// emits `PrivacyLevelChanged` with the old and new levels.
+    /// For boolean on/off privacy, prefer [`set_privacy`](QuickexContract::set_privacy).
+    ///
+    /// Levels:
+    /// * `0` - Public: no privacy
+    /// * `1` - Basic: hidden from public listings and search
+    /// * `2` - Enhanced: as 1, and amounts are hidden
+    /// * `3` - Maximum: as 2, and counterparties are hidden
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `account` - The account to configure
+    /// * `privacy_level` - Numeric level from 0 to 3
+    ///
+    /// # Errors
+    /// * `ContractPaused` - The contract is paused
+    /// * `InvalidPrivacyLevel` - `privacy_level` is above 3
+    pub fn enable_privacy(
+        env: Env,
+        account: Address,
+        privacy_level: u32,
+    ) -> Result<bool, QuickexError> {
+        admin::require_not_paused(&env)?;
+
+        let old_level = get_privacy_level(&env, &account);
+        set_privacy_level(&env, &account, privacy_level)?;
+        add_privacy_history(&env, &account, privacy_level);
+        events::publish_privacy_level_changed(&env, account, old_level, privacy_level);
+        Ok(true)
+    }
+
+    /// Get the current numeric privacy level for an account.
+    ///
+    /// Returns `None` if no level has been set.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `account` - The account to query
+    pub fn privacy_status(env: Env, account: Address) -> Option<u32> {
+        get_privacy_level(&env, &account)
+    }
+
+    /// Get the history of privacy level changes for an account.
+    ///
+    /// Returns a vector of levels, most recent first.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `account` - The account to query
+    pub fn privacy_history(env: Env, account: Address) -> Vec<u32> {
+        get_privacy_history(&env, &account)
+    }
+
+    /// Get one entry of an account's privacy history.
+    ///
+    /// Uses the same order as [`privacy_history`](QuickexContract::privacy_history): index
+    /// `0` is the current level, `1` the one before it, and so on. Returns `None` if the
+    /// history has no entry at `index`.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `account` - The account to query
+    /// * `index` - Position in the history, most recent first
+    pub fn get_privacy_level_at(env: Env, account: Address, index: u32) -> Option<u32> {
+        get_privacy_history(&env, &account).get(index)
+    }
+
+    /// Get the level an account had before its current privacy level.
+    ///
+    /// Returns `None` if the level has been set fewer than two times.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `account` - The account to query
+    pub fn get_previous_privacy_level(env: Env, account: Address) -> Option<u32> {
+        get_privacy_history(&env, &account).get(1)
+    }
+
+    /// Enable or disable privacy for an account.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `owner` - The account address to configure
+    /// * `enabled` - `true` to enable privacy, `false` to disable
+    ///
+    /// # Errors
+    /// * `ContractPaused` - The contract is paused
+    /// * `PrivacyAlreadySet` - Privacy state is already at the requested value
+    /// * `InvalidPrivacyLevel` - Invalid internal state (e.g. invalid level transition)
+    pub fn set_privacy(env: Env, owner: Address, enabled: bool) -> Result<(), QuickexError> {
+        admin::require_not_paused(&env)?;
+
+        privacy::set_privacy(&env, owner, enabled)
+    }
+
+    /// Enable or disable privacy for several accounts at once (**Admin only**).
+    ///
+    /// Unlike [`set_privacy`](QuickexContract::set_privacy), the accounts do not authorize;
+    /// the admin does. One privacy event is emitted per account.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `admin` - Admin address (must authorize)
+    /// * `accounts` - Accounts to configure, at most 20
+    /// * `enabled` - `true` to enable privacy, `false` to disable
+    ///
+    /// # Errors
+    /// * `Unauthorized` - `admin` is not the admin
+    /// * `BatchTooLarge` - More than [`MAX_BATCH`] (20) accounts were given
+    pub fn set_privacy_batch(
+        env: Env,
+        admin: Address,
+        accounts: Vec<Address>,
+        enabled: bool,
+    ) -> Result<(), QuickexError> {
+        admin::require_admin(&env, &admin)?;
+
+        if accounts.len() > MAX_BATCH {
+            return Err(QuickexError::BatchTooLarge);
+        }
+
+        for account in accounts.iter() {
+            privacy::store_privacy(&env, account, enabled);
+        }
+
+        Ok(())
+    }
+
+    /// Check the current privacy status of an account
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `owner` - The account address to query
+    ///
+    /// # Returns
+    /// * `bool` - Current privacy status (true = enabled); accounts that never set one get
+    ///   the admin default from [`set_default_privacy`](QuickexContract::set_default_privacy)
+    pub fn get_privacy(env: Env, owner: Address) -> bool {
+        privacy::get_privacy(&env, owner)
+    }
+
+    /// Check the privacy status of several accounts at once (read-only).
+    ///
+    /// Returns one flag per account, in the order given, as `get_privacy` would.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `accounts` - Accounts to query, at most 20
+    ///
+    /// # Errors
+    /// * `BatchTooLarge` - More than [`MAX_BATCH`] (20) accounts were given
+    pub fn get_privacy_batch(env: Env, accounts: Vec<Address>) -> Result<Vec<bool>, QuickexError> {
+        if accounts.len() > MAX_BATCH {
+            return Err(QuickexError::BatchTooLarge);
+        }
+
+        let mut flags = Vec::new(&env);
+        for account in accounts.iter() {
+            flags.push_back(privacy::get_privacy(&env, account));
+        }
+        Ok(flags)
+    }
+
+    /// Deposit funds and create an escrow entry keyed by a commitment hash.
+    ///
+    /// Transfers `amount` from `owner` to the contract and stores an escrow keyed by
+    /// `H(owner || token || amount || salt)`, where `H` is the configured [`HashAlgorithm`].
+    /// The owner must authorize the transfer.
+    ///
+    /// Submitting the same deposit again while its escrow is still pending (e.g. a network
+    /// retry) returns the same commitment without a second transfer.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `token` - The token contract address
+    /// * `amount` - Amount to deposit; must be positive
+    /// * `owner` - Owner of the funds (must authorize)
+    /// * `salt` - Random salt (16–1024 bytes) for uniqueness; same inputs = same commitment
+    ///
+    /// # Errors
+    /// * `ContractPaused` - The contract is paused
+    /// * `InvalidAmount` - Amount is zero or negative
+    /// * `InvalidInput` - `owner` is the contract itself
+    /// * `TokenNotAllowed` - Token is not on the allowlist
+    /// * `AmountBelowMinimum` / `AmountAboveMaximum` - Amount is outside the token's bounds
+    /// * `InvalidSalt` - Salt length exceeds 1024 bytes
+    /// * `WeakSalt` - Salt is shorter than 16 bytes
+    /// * `SaltReused` - `owner` has already deposited with this salt, other than an
+    ///   identical re-submission
+    /// * `CommitmentAlreadyExists` - An escrow for this commitment already exists with
+    ///   different parameters
+    pub fn deposit(
+        env: Env,
+        token: Address,
+        amount: i128,
+        owner: Address,
+        salt: Bytes,
+    ) -> Result<BytesN<32>, QuickexError> {
+        admin::require_not_paused(&env)?;
+
+        if amount <= 0 {
+            return Err(QuickexError::InvalidAmount);
+        }
+
+        owner.require_auth();
+
+        let commitment = commitment::create_amount_commitment(
+            &env,
+            owner.clone(),
+            token.clone(),
+            escrow::net_deposit_amount(&env, amount),
+            salt.clone(),
+        )?;
+        if storage::is_salt_used(&env, &owner, &salt)
+            && !escrow::is_resubmission(&env, &owner, &token, amount, &commitment)
+        {
+            return Err(QuickexError::SaltReused);
+        }
+        escrow::deposit(
+            &env,
+            &owner,
+            &token,
+            amount,
+            &commitment,
+            DepositOptions {
+                idempotent: true,
+                ..Default::default()
+            },
+        )?;
+        storage::mark_salt_used(&env, &owner, &salt);
+
+        Ok(commitment)
+    }
+
+    /// Build the exact bytes that `create_amount_commitment` hashes (read-only).
+    ///
+    /// Returns `owner.to_xdr() || token.to_xdr() || amount (16-byte big-endian) || salt`, so
+    /// clients can
+    /// reproduce commitments off-chain without assembling the XDR by hand.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `owner` - The owner address
+    /// * `token` - Token the deposit will be made in
+    /// * `amount` - Non-negative amount in token base units
+    /// * `salt` - Random bytes (16–1024 bytes) for uniqueness
+    ///
+    /// # Errors
+    /// Same as [`create_amount_commitment`](QuickexContract::create_amount_commitment).
+    pub fn build_commitment_preimage(
+        env: Env,
+        owner: Address,
+        token: Address,
+        amount: i128,
+        salt: Bytes,
+    ) -> Result<Bytes, QuickexError> {
+        commitment::build_commitment_preimage(&env, owner, token, amount, salt)
+    }
+
+    /// Compute a commitment together with the preimage it hashes (read-only).
+    ///
+    /// Returns `(create_amount_commitment(..), build_commitment_preimage(..))` in one call,
+    /// so frontends can display and store both without hashing the preimage themselves.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `owner` - The owner address
+    /// * `token` - Token the deposit will be made in
+    /// * `amount` - Non-negative amount in token base units
+    /// * `salt` - Random bytes (16–1024 bytes) for uniqueness
+    ///
+    /// # Errors
+    /// Same as [`create_amount_commitment`](QuickexContract::create_amount_commitment).
+    pub fn prepare_deposit(
+        env: Env,
+        owner: Address,
+        token: Address,
+        amount: i128,
+        salt: Bytes,
+    ) -> Result<(BytesN<32>, Bytes), QuickexError> {
+        let preimage = commitment::build_commitment_preimage(&env, owner, token, amount, salt)?;
+        let commitment = commitment::hash_preimage(&env, &preimage);
+        Ok((commitment, preimage))
+    }
+
+    /// Create a deterministic commitment hash for an amount (off-chain / pre-deposit use).
+    ///
+    /// Computes `H(owner || token || amount || salt)`, where `H` is the admin-selected
+    /// [`HashAlgorithm`] (SHA-256 by default). Not a zero-knowledge proof; same inputs
+    /// always yield the same hash. Use for API shape validation and audit trails.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `owner` - The owner address
+    /// * `token` - Token the deposit will be made in
+    /// * `amount` - Non-negative amount in token base units
+    /// * `salt` - Random bytes (16–1024 bytes) for uniqueness
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - Amount is negative
+    /// * `InvalidSalt` - Salt length exceeds 1024 bytes
+    /// * `WeakSalt` - Salt is shorter than 16 bytes
+    pub fn create_amount_commitment(
+        env: Env,
+        owner: Address,
+        token: Address,
+        amount: i128,
+        salt: Bytes,
+    ) -> Result<BytesN<32>, QuickexError> {
+        commitment::create_amount_commitment(&env, owner, token, amount, salt)
+    }
+
+    /// Verify that a commitment hash matches the given `owner`, `token`, `amount`, and `salt`.
+    ///
+    /// Recomputes the commitment and compares. Returns `false` if inputs are invalid or don't match.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `commitment` - 32-byte commitment hash to verify
+    /// * `owner` - Claimed owner
+    /// * `token` - Claimed token
+    /// * `amount` - Claimed amount (must be non-negative)
+    /// * `salt` - Salt used when creating the commitment
+    pub fn verify_amount_commitment(
+        env: Env,
+        commitment: BytesN<32>,
+        owner: Address,
+        token: Address,
+        amount: i128,
+        salt: Bytes,
+    ) -> bool {
+        commitment::verify_amount_commitment(&env, commitment, owner, token, amount, salt)
+    }
+
+    /// Create a commitment to an amount bucket rather than an exact amount.
+    ///
+    /// Bucket `k` covers amounts `[10^k, 10^(k+1) - 1]` (`k` from 0 to 38; the last bucket
+    /// runs up to `i128::MAX`). Deposit with `deposit_with_commitment` as usual and withdraw
+    /// with [`withdraw_bucketed`](QuickexContract::withdraw_bucketed); only the order of
+    /// magnitude of the amount is bound, and withdrawal checks the escrowed amount is in the
+    /// bucket. The token is bound exactly, as for amount commitments.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `owner` - The owner address
+    /// * `token` - Token the deposit will be made in
+    /// * `bucket_id` - Power-of-ten bucket, 0 to 38
+    /// * `salt` - Random bytes (16–1024 bytes) for uniqueness
+    ///
+    /// # Errors
+    /// * `InvalidInput` - `bucket_id` is above 38
+    /// * `InvalidSalt` - Salt length exceeds 1024 bytes
+    /// * `WeakSalt` - Salt is shorter than 16 bytes
+    pub fn create_bucketed_commitment(
+        env: Env,
+        owner: Address,
+        token: Address,
+        bucket_id: u32,
+        salt: Bytes,
+    ) -> Result<BytesN<32>, QuickexError> {
+        commitment::create_bucketed_commitment(&env, owner, token, bucket_id, salt)
+    }
+
+    /// Verify that a bucketed commitment matches `owner`, `token`, `bucket_id`, and `salt`,
+    /// and that `amount` falls inside the bucket.
+    ///
+    /// Returns `false` if inputs are invalid or don't match.
+    pub fn verify_bucketed_commitment(
+        env: Env,
+        commitment: BytesN<32>,
+        owner: Address,
+        token: Address,
+        bucket_id: u32,
+        amount: i128,
+        salt: Bytes,
+    ) -> bool {
+        commitment::is_in_bucket(amount, bucket_id)
+            && commitment::create_bucketed_commitment(&env, owner, token, bucket_id, salt)
+                .is_ok_and(|hash| hash == commitment)
+    }
+
+    /// Create a commitment that lets the salt holder withdraw only to one of `recipients`.
+    ///
+    /// Binds a hash of the list instead of a single recipient, so the depositor fixes who
+    /// may ultimately be paid before the salt holder picks one. Deposit under it with
+    /// [`deposit_with_commitment`](QuickexContract::deposit_with_commitment) and withdraw
+    /// with [`withdraw_to_allowed`](QuickexContract::withdraw_to_allowed), passing the same
+    /// list in the same order.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `recipients` - Addresses allowed to receive the funds
+    /// * `token` - Token the deposit will be made in
+    /// * `amount` - Non-negative amount in token base units
+    /// * `salt` - Random bytes (16–1024 bytes) for uniqueness
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - Amount is negative
+    /// * `InvalidSalt` - Salt length exceeds 1024 bytes
+    /// * `WeakSalt` - Salt is shorter than 16 bytes
+    pub fn create_recipients_commitment(
+        env: Env,
+        recipients: Vec<Address>,
+        token: Address,
+        amount: i128,
+        salt: Bytes,
+    ) -> Result<BytesN<32>, QuickexError> {
+        commitment::create_recipients_commitment(&env, &recipients, token, amount, salt)
+    }
+
+    /// Create a commitment withdrawn by Ed25519 signature instead of by the recipient.
+    ///
+    /// Binds `pubkey` instead of a recipient address. Deposit under it with
+    /// [`deposit_with_commitment`](QuickexContract::deposit_with_commitment) and withdraw
+    /// with [`withdraw_signed`](QuickexContract::withdraw_signed).
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `pubkey` - Ed25519 public key that will sign the withdrawal
+    /// * `token` - Token the deposit will be made in
+    /// * `amount` - Non-negative amount in token base units
+    /// * `salt` - Random bytes (16–1024 bytes) for uniqueness
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - Amount is negative
+    /// * `InvalidSalt` - Salt length exceeds 1024 bytes
+    /// * `WeakSalt` - Salt is shorter than 16 bytes
+    pub fn create_signer_commitment(
+        env: Env,
+        pubkey: BytesN<32>,
+        token: Address,
+        amount: i128,
+        salt: Bytes,
+    ) -> Result<BytesN<32>, QuickexError> {
+        commitment::create_signer_commitment(&env, &pubkey, token, amount, salt)
+    }
+
+    /// Build the message `withdraw_signed` expects to be signed (read-only).
+    ///
+    /// Returns `commitment || to.to_xdr() || amount (16-byte big-endian)`.
+    pub fn build_withdraw_message(
+        env: Env,
+        commitment: BytesN<32>,
+        to: Address,
+        amount: i128,
+    ) -> Bytes {
+        commitment::build_withdraw_message(&env, &commitment, &to, amount)
+    }
+
+    /// Verify a commitment opening and return the on-chain status of its escrow.
+    ///
+    /// Combines [`verify_amount_commitment`](QuickexContract::verify_amount_commitment) with
+    /// [`get_commitment_state`](QuickexContract::get_commitment_state): returns `None` if
+    /// `owner`, `token`, `amount`, and `salt` do not open `commitment` or no escrow exists for it,
+    /// otherwise the escrow's current status.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `commitment` - 32-byte commitment hash to verify
+    /// * `owner` - Claimed owner
+    /// * `token` - Claimed token
+    /// * `amount` - Claimed amount (must be non-negative)
+    /// * `salt` - Salt used when creating the commitment
+    pub fn verify_and_state(
+        env: Env,
+        commitment: BytesN<32>,
+        owner: Address,
+        token: Address,
+        amount: i128,
+        salt: Bytes,
+    ) -> Option<EscrowStatus> {
+        let entry = get_escrow(&env, &commitment.clone().into())?;
+        let preimage =
+            commitment::build_commitment_preimage(&env, owner, token, amount, salt).ok()?;
+        (commitment::hash_with(&env, entry.hash_algorithm, &preimage) == commitment)
+            .then_some(entry.status)
+    }
+
+    /// Create an escrow record and increment the global escrow counter.
+    ///
+    /// Returns the new counter value. Parameters `_from`, `_to`, `_amount` are reserved for
+    /// future use; the implementation only increments the counter.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `_from` - Reserved (depositor address for future use)
+    /// * `_to` - Reserved (recipient address for future use)
+    /// * `_amount` - Reserved (amount for future use)
+    pub fn create_escrow(
+        env: Env,
+        _from: Address,
+        _to: Address,
+        _amount: u64,
+    ) -> Result<u64, QuickexError> {
+        admin::require_not_paused(&env)?;
+
+        Ok(increment_escrow_counter(&env))
+    }
+
+    /// Health check for deployment and monitoring.
+    ///
+    /// Returns `true` if the contract is deployed and callable. No state or auth required.
+    pub fn health_check() -> bool {
+        true
+    }
+
+    /// Detailed health report for monitoring.
+    ///
+    /// Unlike [`health_check`](QuickexContract::health_check), this reports the pause
+    /// state, whether an admin is set, the escrow counter and the contract version.
+    pub fn get_health(env: Env) -> HealthStatus {
+        HealthStatus {
+            is_paused: is_paused(&env),
+            admin_set: get_admin(&env).is_some(),
+            escrow_counter: get_escrow_counter(&env),
+            version: VERSION,
+        }
+    }
+
+    /// Read every admin-tunable parameter in one call.
+    ///
+    /// Saves clients a round of individual getters. Per-token settings (amount bounds, the
+    /// allowlist, swap rates) are not included; `default_amount_bounds` is what tokens
+    /// without their own bounds use.
+    pub fn get_config(env: Env) -> ContractConfig {
+        ContractConfig {
+            admin: get_admin(&env),
+            paused: is_paused(&env),
+            shutdown: is_shutdown(&env),
+            fee_bps: get_fee_bps(&env),
+            deposit_fee_bps: get_deposit_fee_bps(&env),
+            fee_collector: get_fee_collector(&env),
+            default_expiry: get_default_expiry(&env),
+            reclaim_window: get_reclaim_window(&env),
+            withdraw_cooldown: get_withdraw_cooldown(&env),
+            default_privacy: get_default_privacy(&env),
+            default_amount_bounds: DEFAULT_AMOUNT_BOUNDS,
+            max_basket_size: get_max_basket_size(&env),
+            max_pending_per_owner: get_max_pending_per_owner(&env),
+            hash_algorithm: get_hash_algorithm(&env),
+            event_verbosity: get_event_verbosity(&env),
+            ttl_config: get_ttl_config(&env),
+        }
+    }
+
+    /// Get the contract interface version ([`VERSION`]).
+    ///
+    /// Lets indexers and frontends confirm which build is deployed, e.g. around upgrades.
+    pub fn get_version() -> u32 {
+        VERSION
+    }
+
+    /// Deposit funds using a pre-generated 32-byte commitment hash.
+    ///
+    /// Transfers `amount` from `from` to the contract and stores an escrow keyed by
+    /// `commitment`. The depositor must authorize. Use when the commitment was created
+    /// off-chain or via [`create_amount_commitment`](QuickexContract::create_amount_commitment).
+    ///
+    /// With a deposit fee configured (see `set_deposit_fee`), the escrow holds `amount`
+    /// minus the fee and `commitment` must commit to that net amount
+    /// (`get_net_deposit_amount`).
+    ///
+    /// Re-submitting the same deposit (same `from`, `token`, `amount` and `commitment`)
+    /// while its escrow is still pending is a no-op success, so retried transactions do
+    /// not fail or pay twice.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `from` - Depositor (must authorize the token transfer)
+    /// * `token` - Token contract address
+    /// * `amount` - Amount to deposit; must be positive
+    /// * `commitment` - 32-byte commitment hash (must be unique)
+    ///
+    /// # Errors
+    /// * `ContractPaused` - The contract is paused
+    /// * `InvalidAmount` - Amount is zero or negative
+    /// * `InvalidInput` - `from` is the contract itself
+    /// * `TokenNotAllowed` - Token is not on the allowlist
+    /// * `AmountBelowMinimum` / `AmountAboveMaximum` - Amount is outside the token's bounds
+    /// * `CommitmentAlreadyExists` - An escrow for this commitment already exists with
+    ///   different parameters, or is no longer pending
+    pub fn deposit_with_commitment(
+        env: Env,
+        from: Address,
+        token: Address,
+        amount: i128,
+        commitment: BytesN<32>,
+    ) -> Result<(), QuickexError> {
+        admin::require_not_paused(&env)?;
+
+        if amount <= 0 {
+            return Err(QuickexError::InvalidAmount);
+        }
+
+        from.require_auth();
+
+        escrow::deposit(
+            &env,
+            &from,
+            &token,
+            amount,
+            &commitment,
+            DepositOptions {
+                idempotent: true,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Deposit native XLM under a pre-generated commitment.
+    ///
+    /// Same as [`deposit_with_commitment`](QuickexContract::deposit_with_commitment) with
+    /// the native asset's Stellar Asset Contract as the token, so callers do not need to
+    /// know its address. The address is configured by the admin with `set_native_token`.
+    /// Withdraw with the regular `withdraw`.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `from` - Depositor (must authorize the transfer)
+    /// * `amount` - Amount in stroops; must be positive
+    /// * `commitment` - 32-byte commitment hash (must be unique)
+    ///
+    /// # Errors
+    /// Same as `deposit_with_commitment`, plus:
+    /// * `NativeTokenNotSet` - The admin has not configured the native token
+    pub fn deposit_native(
+        env: Env,
+        from: Address,
+        amount: i128,
+        commitment: BytesN<32>,
+    ) -> Result<(), QuickexError> {
+        admin::require_not_paused(&env)?;
+
+        if amount <= 0 {
+            return Err(QuickexError::InvalidAmount);
+        }
+
+        let token = get_native_token(&env).ok_or(QuickexError::NativeTokenNotSet)?;
+
+        from.require_auth();
+
+        escrow::deposit(
+            &env,
+            &from,
+            &token,
+            amount,
+            &commitment,
+            DepositOptions {
+                idempotent: true,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Deposit funds under a pre-generated commitment with an attached memo.
+    ///
+    /// Same as [`deposit_with_commitment`](QuickexContract::deposit_with_commitment), and
+    /// additionally stores `memo` for the recipient (read it back with `get_escrow_memo`).
+    /// The memo is opaque to the contract and should be encrypted for the recipient; only
+    /// its SHA-256 hash is included in the deposit event.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `from` - Depositor (must authorize the token transfer)
+    /// * `token` - Token contract address
+    /// * `amount` - Amount to deposit; must be positive
+    /// * `commitment` - 32-byte commitment hash (must be unique)
+    /// * `memo` - Opaque note, at most 256 bytes
+    ///
+    /// # Errors
+    /// Same as `deposit_with_commitment`, plus:
+    /// * `MemoTooLong` - `memo` exceeds 256 bytes
+    pub fn deposit_with_memo(
+        env: Env,
+        from: Address,
+        token: Address,
+        amount: i128,
+        commitment: BytesN<32>,
+        memo: Bytes,
+    ) -> Result<(), QuickexError> {
+        admin::require_not_paused(&env)?;
+
+        if amount <= 0 {
+            return Err(QuickexError::InvalidAmount);
+        }
+
+        from.require_auth();
+
+        let options = DepositOptions {
+            memo: Some(memo),
+            ..Default::default()
+        };
+        escrow::deposit(&env, &from, &token, amount, &commitment, options)
+    }
+
+    /// Deposit funds under a pre-generated commitment with an explicit expiry.
+    ///
+    /// Same as [`deposit_with_commitment`](QuickexContract::deposit_with_commitment), but the
+    /// escrow expires `expires_in` seconds after the deposit instead of after the default
+    /// lifetime (`get_default_expiry`, 30 days unless the admin changed it). Pass `0` for an escrow that never expires. After expiry the escrow can no
+    /// longer be withdrawn and the depositor may reclaim it with
+    /// [`refund_expired`](QuickexContract::refund_expired).
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `from` - Depositor (must authorize the token transfer)
+    /// * `token` - Token contract address
+    /// * `amount` - Amount to deposit; must be positive
+    /// * `commitment` - 32-byte commitment hash (must be unique)
+    /// * `expires_in` - Lifetime in seconds, or `0` for no expiry
+    ///
+    /// # Errors
+    /// Same as `deposit_with_commitment`, plus:
+    /// * `InvalidInput` - `expires_in` overflows the ledger timestamp
+    pub fn deposit_with_expiry(
+        env: Env,
+        from: Address,
+        token: Address,
+        amount: i128,
+        commitment: BytesN<32>,
+        expires_in: u64,
+    ) -> Result<(), QuickexError> {
+        admin::require_not_paused(&env)?;
+
+        if amount <= 0 {
+            return Err(QuickexError::InvalidAmount);
+        }
+
+        from.require_auth();
+
+        let options = DepositOptions {
+            expires_in: Some(expires_in),
+            ..Default::default()
+        };
+        escrow::deposit(&env, &from, &token, amount, &commitment, options)
+    }
+
+    /// Deposit funds under a pre-generated commitment, paid out in another token on withdrawal.
+    ///
+    /// Same as [`deposit_with_commitment`](QuickexContract::deposit_with_commitment), but the
+    /// recipient is paid in `target_token` at the admin rate for the pair (see
+    /// `set_swap_rate`), fixed at deposit time. Withdrawal pays from the contract's reserve of
+    /// `target_token`, i.e. its balance beyond what backs escrows deposited in that token,
+    /// and fails with `InsufficientReserve` if it is too small. Passing `token`
+    /// itself as `target_token` is a plain 1:1 deposit. Cancels and refunds return `token`.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `from` - Depositor (must authorize the token transfer)
+    /// * `token` - Token contract address being deposited
+    /// * `amount` - Amount to deposit; must be positive
+    /// * `commitment` - 32-byte commitment hash (must be unique)
+    /// * `target_token` - Token the recipient is paid in
+    ///
+    /// # Errors
+    /// Same as `deposit_with_commitment`, plus:
+    /// * `SwapRateNotSet` - No rate is configured from `token` to `target_token`
+    /// * `InvalidAmount` - The converted amount overflows
+    pub fn deposit_with_swap(
+        env: Env,
+        from: Address,
+        token: Address,
+        amount: i128,
+        commitment: BytesN<32>,
+        target_token: Address,
+    ) -> Result<(), QuickexError> {
+        admin::require_not_paused(&env)?;
+
+        if amount <= 0 {
+            return Err(QuickexError::InvalidAmount);
+        }
+
+        from.require_auth();
+
+        let options = DepositOptions {
+            target_token: Some(target_token),
+            ..Default::default()
+        };
+        escrow::deposit(&env, &from, &token, amount, &commitment, options)
+    }
+
+    /// Deposit funds under a pre-generated commitment, but only until a deadline.
+    ///
+    /// Same as [`deposit_with_commitment`](QuickexContract::deposit_with_commitment), but
+    /// fails once the ledger timestamp is past `not_after`, so a deposit built from a
+    /// time-limited off-chain quote cannot execute after the quote has lapsed.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `from` - Depositor (must authorize the token transfer)
+    /// * `token` - Token contract address
+    /// * `amount` - Amount to deposit; must be positive
+    /// * `commitment` - 32-byte commitment hash (must be unique)
+    /// * `not_after` - Last ledger timestamp (inclusive) at which the deposit may execute
+    ///
+    /// # Errors
+    /// Same as `deposit_with_commitment`, plus:
+    /// * `DepositDeadlinePassed` - The ledger timestamp is past `not_after`
+    pub fn deposit_with_deadline(
+        env: Env,
+        from: Address,
+        token: Address,
+        amount: i128,
+        commitment: BytesN<32>,
+        not_after: u64,
+    ) -> Result<(), QuickexError> {
+        admin::require_not_paused(&env)?;
+
+        if amount <= 0 {
+            return Err(QuickexError::InvalidAmount);
+        }
+
+        from.require_auth();
+
+        let options = DepositOptions {
+            not_after: Some(not_after),
+            ..Default::default()
+        };
+        escrow::deposit(&env, &from, &token, amount, &commitment, options)
+    }
+
+    /// Reclaim the funds of an expired escrow.
+    ///
+    /// Once an escrow has passed its expiry it can no longer be withdrawn; its owner (the
+    /// depositor) may instead call this to get the funds back. For the reclaim window after
+    /// expiry (default 7 days, see `set_reclaim_window`) only the owner may do so; after
+    /// that the admin may also trigger the refund. Either way the funds go to the owner and
+    /// the escrow is marked spent.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Escrow owner or admin (must authorize)
+    /// * `commitment` - 32-byte commitment hash of the escrow
+    ///
+    /// # Errors
+    /// * `ContractPaused` - The contract is paused
+    /// * `CommitmentNotFound` - No escrow exists for the commitment
+    /// * `Unauthorized` - `caller` is neither the stored owner nor the admin; an `AuthFailed`
+    ///   event records the attempt
+    /// * `ReclaimWindowActive` - `caller` is the admin and the reclaim window has not passed
+    /// * `EscrowNotExpired` - Escrow is pending and has not expired yet
+    /// * `AlreadySpent` - Escrow was already withdrawn or refunded
+    /// * `EscrowCancelled` - Escrow was cancelled by the depositor
+    pub fn refund_expired(
+        env: Env,
+        caller: Address,
+        commitment: BytesN<32>,
+    ) -> Result<(), QuickexError> {
+        admin::require_not_paused(&env)?;
+
+        caller.require_auth();
+
+        escrow::refund_expired(&env, &caller, &commitment)?;
+
+        Ok(())
+    }
+
+    /// Reclaim several expired escrows in one call.
+    ///
+    /// Refunds every listed escrow owned by `depositor` that has expired, skipping the
+    /// rest, and pays each token back in a single transfer. Unlike `refund_expired`, only
+    /// the depositor may call this.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `depositor` - Owner of the escrows (must authorize)
+    /// * `commitments` - Commitments to try; ineligible ones are skipped
+    ///
+    /// # Returns
+    /// Total refunded per token; tokens with nothing refunded are absent.
+    ///
+    /// # Errors
+    /// * `ContractPaused` - The contract is paused
+    /// * `BatchTooLarge` - More than [`MAX_BATCH`] (20) commitments
+    /// * `ArithmeticOverflow` - A token's refunds overflow `i128`
+    pub fn withdraw_all_expired(
+        env: Env,
+        depositor: Address,
+        commitments: Vec<BytesN<32>>,
+    ) -> Result<Map<Address, i128>, QuickexError> {
+        admin::require_not_paused(&env)?;
+        if commitments.len() > MAX_BATCH {
+            return Err(QuickexError::BatchTooLarge);
+        }
+
+        depositor.require_auth();
+
+        escrow::withdraw_all_expired(&env, &depositor, &commitments)
+    }
+
+    /// Deposit several assets in one call, each escrowed under its own commitment.
+    ///
+    /// Every item is validated and transferred exactly like
+    /// [`deposit_with_commitment`](QuickexContract::deposit_with_commitment). The number of
+    /// items is capped by the admin-configured maximum basket size (default 10).
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `from` - Depositor (must authorize the token transfers)
+    /// * `items` - Token, amount, and commitment for each escrow in the basket
+    ///
+    /// # Errors
+    /// * `ContractPaused` - The contract is paused
+    /// * `BatchTooLarge` - More than [`MAX_BATCH`] (20) items
+    /// * `InvalidInput` - Basket is empty or larger than the maximum basket size
+    /// * Any error returned by `deposit_with_commitment` for an individual item
+    pub fn deposit_basket(
+        env: Env,
+        from: Address,
+        items: Vec<BasketItem>,
+    ) -> Result<(), QuickexError> {
+        admin::require_not_paused(&env)?;
+        if items.len() > MAX_BATCH {
+            return Err(QuickexError::BatchTooLarge);
+        }
+        if items.is_empty() || items.len() > get_max_basket_size(&env) {
+            return Err(QuickexError::InvalidInput);
+        }
+
+        from.require_auth();
+
+        for item in items.iter() {
+            if item.amount <= 0 {
+                return Err(QuickexError::InvalidAmount);
+            }
+            escrow::deposit(
+                &env,
+                &from,
+                &item.token,
+                item.amount,
+                &item.commitment,
+                DepositOptions::default(),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Deposit `amount` of `token` split between several recipients.
+    ///
+    /// Each share becomes its own pending escrow under the share's commitment, so every
+    /// recipient withdraws only their portion with `withdraw`, proving their own commitment,
+    /// and the depositor can cancel or refund each share separately. The shares must be
+    /// positive and add up to exactly `amount`; each is validated and transferred like
+    /// [`deposit_with_commitment`](QuickexContract::deposit_with_commitment). The number of
+    /// shares is capped by the maximum basket size (default 10).
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `from` - Depositor (must authorize the token transfers)
+    /// * `token` - Token to deposit
+    /// * `amount` - Total amount; must equal the sum of the shares
+    /// * `shares` - Commitment and amount for each recipient
+    ///
+    /// # Errors
+    /// * `ContractPaused` - The contract is paused
+    /// * `BatchTooLarge` - More than [`MAX_BATCH`] (20) shares
+    /// * `InvalidInput` - No shares, or more than the maximum basket size
+    /// * `InvalidAmount` - A share is not positive or the shares do not add up to `amount`
+    /// * Any error returned by `deposit_with_commitment` for an individual share
+    pub fn deposit_split(
+        env: Env,
+        from: Address,
+        token: Address,
+        amount: i128,
+        shares: Vec<SplitShare>,
+    ) -> Result<(), QuickexError> {
+        admin::require_not_paused(&env)?;
+        if shares.len() > MAX_BATCH {
+            return Err(QuickexError::BatchTooLarge);
+        }
+        if shares.is_empty() || shares.len() > get_max_basket_size(&env) {
+            return Err(QuickexError::InvalidInput);
+        }
+
+        let mut total: i128 = 0;
+        for share in shares.iter() {
+            if share.amount <= 0 {
+                return Err(QuickexError::InvalidAmount);
+            }
+            total = total
+                .checked_add(share.amount)
+                .ok_or(QuickexError::InvalidAmount)?;
+        }
+        if total != amount {
+            return Err(QuickexError::InvalidAmount);
+        }
+
+        from.require_auth();
+
+        for share in shares.iter() {
+            escrow::deposit(
+                &env,
+                &from,
+                &token,
+                share.amount,
+                &share.commitment,
+                DepositOptions::default(),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Initialize the contract with an admin address (one-time only).
+    ///
+    /// Sets the admin who can pause/unpause, transfer admin, and upgrade the contract,
+    /// and the TTL settings used to bump escrow entries when they are written.
+    ///
+    /// Initialization is recorded in a flag that lives as long as the contract instance,
+    /// so re-initialization stays blocked even if the admin entry were ever archived. The
+    /// admin entry's TTL is also bumped here and on every admin operation.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `admin` - The admin address to set
+    /// * `ttl_config` - Persistent-entry TTL settings in ledgers; `None` uses
+    ///   `DEFAULT_TTL_CONFIG` (bump to 60 days once 30 days or less remain)
+    ///
+    /// # Errors
+    /// * `AlreadyInitialized` - Contract has already been initialized
+    /// * `InvalidInput` - `ttl_config.threshold` is greater than `ttl_config.extend_to`
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        ttl_config: Option<TtlConfig>,
+    ) -> Result<(), QuickexError> {
+        if admin::is_initialized(&env) {
+            return Err(QuickexError::AlreadyInitialized);
+        }
+        if let Some(config) = ttl_config {
+            if config.threshold > config.extend_to {
+                return Err(QuickexError::InvalidInput);
+            }
+            set_ttl_config(&env, &config);
+        }
+        set_admin(&env, &admin);
+        set_initialized(&env);
+        bump_admin_ttl(&env);
+        Ok(())
+    }
+
+    /// Get the TTL settings applied to escrow entries.
+    ///
+    /// Returns the values passed to `initialize`, or `DEFAULT_TTL_CONFIG` if none were given.
+    pub fn get_ttl_config(env: Env) -> TtlConfig {
+        storage::get_ttl_config(&env)
+    }
+
+    /// Pause or unpause the contract (**Admin only**).
+    ///
+    /// When paused, certain operations may be blocked. Caller must equal the stored admin.
+    /// Emits `ContractPaused` with the new state and reason.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Caller address (must equal admin)
+    /// * `new_state` - `true` to pause, `false` to unpause
+    /// * `reason` - Why the contract is paused (e.g. "incident response"); ignored and
+    ///   cleared on unpause
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin, or admin not set
+    pub fn set_paused(
+        env: Env,
+        caller: Address,
+        new_state: bool,
+        reason: Option<String>,
+    ) -> Result<(), QuickexError> {
+        admin::set_paused(&env, caller, new_state, reason)
+    }
+
+    /// Get the ledger timestamp at which the contract was paused, or `None` if it is not paused.
+    pub fn get_paused_since(env: Env) -> Option<u64> {
+        get_paused_since(&env)
+    }
+
+    /// Get the reason given for the current pause, if any.
+    pub fn get_pause_reason(env: Env) -> Option<String> {
+        get_pause_reason(&env)
+    }
+
+    /// Permanently shut the contract down (**Admin only**).
+    ///
+    /// Unlike [`set_paused`](QuickexContract::set_paused) this is one-way and meant for
+    /// terminal states such as a migration: every deposit entrypoint (including `top_up`)
+    /// fails with `ContractShutdown` from then on, while withdrawals, refunds and cancels
+    /// keep working so users can exit. It cannot be undone. Emits `Shutdown`.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin, or admin not set
+    /// * `ContractShutdown` - The contract is already shut down
+    pub fn shutdown(env: Env, caller: Address) -> Result<(), QuickexError> {
+        admin::shutdown(&env, caller)
+    }
+
+    /// Whether the contract has been permanently shut down.
+    pub fn is_shutdown(env: Env) -> bool {
+        is_shutdown(&env)
+    }
+
+    /// Transfer admin rights to a new address immediately (**Admin only**).
+    ///
+    /// Caller must equal the current admin and authorize. Prefer the two-step
+    /// [`propose_admin`](QuickexContract::propose_admin) /
+    /// [`accept_admin`](QuickexContract::accept_admin) handoff, which cannot hand control to
+    /// a mistyped address. Any pending proposal is withdrawn, so a previously proposed admin
+    /// can no longer accept. Emits `AdminChanged`.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Caller address (must equal current admin; must authorize)
+    /// * `new_admin` - New admin address
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin, or admin not set
+    pub fn set_admin(env: Env, caller: Address, new_admin: Address) -> Result<(), QuickexError> {
+        admin::set_admin(&env, caller, new_admin)
+    }
+
+    /// Propose a new admin (**Admin only**), the first step of a two-step handoff.
+    ///
+    /// The current admin stays in control until `new_admin` calls
+    /// [`accept_admin`](QuickexContract::accept_admin), so a mistyped address cannot take
+    /// over. Proposing again replaces the pending admin.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Current admin (must authorize)
+    /// * `new_admin` - Proposed admin address
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin
+    pub fn propose_admin(
+        env: Env,
+        caller: Address,
+        new_admin: Address,
+    ) -> Result<(), QuickexError> {
+        admin::propose_admin(&env, caller, new_admin)
+    }
+
+    /// Accept a pending admin proposal, completing the handoff.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `new_admin` - The pending admin (must authorize)
+    ///
+    /// # Errors
+    /// * `Unauthorized` - `new_admin` is not the pending admin, or no transfer is pending
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), QuickexError> {
+        admin::accept_admin(&env, new_admin)
+    }
+
+    /// Cancel a pending admin proposal (**Admin only**). A no-op if none is pending.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin
+    pub fn cancel_admin_transfer(env: Env, caller: Address) -> Result<(), QuickexError> {
+        admin::cancel_admin_transfer(&env, caller)
+    }
+
+    /// Get the admin address awaiting acceptance, if any.
+    pub fn get_pending_admin(env: Env) -> Option<Address> {
+        get_pending_admin(&env)
+    }
+
+    /// Import pre-existing escrow entries, e.g. when migrating from another deployment (**Admin only**).
+    ///
+    /// Each entry is stored under its commitment exactly as given. With `verify_backing`, the
+    /// contract's balance of every token must cover the amounts already escrowed in it plus
+    /// the summed amounts of the imported outstanding (`Pending` or `Expired`) entries, so
+    /// the import cannot create unbacked escrows or borrow funds backing existing ones.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Caller address (must equal admin; must authorize)
+    /// * `escrows` - `(commitment, entry)` pairs to import
+    /// * `verify_backing` - Check token balances cover the imported outstanding amounts
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin, or admin not set
+    /// * `BatchTooLarge` - More than [`MAX_BATCH`] (20) escrows
+    /// * `InvalidAmount` - An entry's amount is zero or negative
+    /// * `CommitmentAlreadyExists` - An escrow for one of the commitments already exists
+    /// * `TransferShortfall` - The contract holds fewer tokens than the imported entries require
+    /// * `ArithmeticOverflow` - The imported amounts for a token overflow `i128`
+    pub fn import_escrows(
+        env: Env,
+        caller: Address,
+        escrows: Vec<(BytesN<32>, EscrowEntry)>,
+        verify_backing: bool,
+    ) -> Result<(), QuickexError> {
+        admin::require_admin(&env, &caller)?;
+        if escrows.len() > MAX_BATCH {
+            return Err(QuickexError::BatchTooLarge);
+        }
+
+        let mut seen: Map<BytesN<32>, bool> = Map::new(&env);
+        let mut required: Map<Address, i128> = Map::new(&env);
+        for (commitment, entry) in escrows.iter() {
+            if seen.contains_key(commitment.clone())
+                || !escrow::is_commitment_available(&env, &commitment)
+            {
+                return Err(QuickexError::CommitmentAlreadyExists);
+            }
+            seen.set(commitment, true);
+            if entry.amount <= 0 {
+                return Err(QuickexError::InvalidAmount);
+            }
+            if escrow::is_outstanding(entry.status) {
+                let total = required
+                    .get(entry.token.clone())
+                    .unwrap_or(0)
+                    .checked_add(entry.amount)
+                    .ok_or(QuickexError::ArithmeticOverflow)?;
+                required.set(entry.token.clone(), total);
+            }
+        }
+
+        if verify_backing {
+            for (token, total) in required.iter() {
+                let balance =
+                    token::Client::new(&env, &token).balance(&env.current_contract_address());
+                let needed = get_total_escrowed(&env, &token)
+                    .checked_add(total)
+                    .ok_or(QuickexError::ArithmeticOverflow)?;
+                if balance < needed {
+                    return Err(QuickexError::TransferShortfall);
+                }
+            }
+        }
+
+        for (commitment, entry) in escrows.iter() {
+            escrow::create(&env, &commitment, &entry)?;
+        }
+
+        Ok(())
+    }
+
+    /// Configure the protocol fee taken on withdrawals (**Admin only**).
+    ///
+    /// On each withdrawal, `amount * fee_bps / 10_000` is sent to `fee_collector` and the
+    /// remainder to the recipient. A `fee_bps` of 0 disables the fee. Emits
+    /// `FeeCollectorChanged` when this replaces a different, previously configured collector.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Caller address (must equal admin; must authorize)
+    /// * `fee_bps` - Fee in basis points (at most 1000 = 10%)
+    /// * `fee_collector` - Address receiving collected fees
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin, or admin not set
+    /// * `FeeTooHigh` - `fee_bps` exceeds the 1000 bps cap
+    /// * `InvalidInput` - `fee_collector` is this contract
+    pub fn set_fee(
+        env: Env,
+        caller: Address,
+        fee_bps: u32,
+        fee_collector: Address,
+    ) -> Result<(), QuickexError> {
+        admin::require_admin(&env, &caller)?;
+        fees::validate_fee_bps(fee_bps)?;
+        fees::validate_fee_collector(&env, &fee_collector)?;
+
+        let old_collector = get_fee_collector(&env);
+        set_fee_bps(&env, fee_bps);
+        set_fee_collector(&env, &fee_collector);
+        if let Some(old_collector) = old_collector {
+            if old_collector != fee_collector {
+                events::publish_fee_collector_changed(&env, old_collector, fee_collector);
+            }
+        }
+        Ok(())
+    }
+
+    /// Replace the fee collector without touching the fee rate (**Admin only**).
+    ///
+    /// Later withdrawal fees go to `new_collector`. Emits `FeeCollectorChanged` with the
+    /// old and new addresses. The first collector is set with `set_fee`.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Caller address (must equal admin; must authorize)
+    /// * `new_collector` - Address receiving collected fees from now on
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin, or admin not set
+    /// * `FeeCollectorNotSet` - No collector has been configured yet
+    /// * `InvalidInput` - `new_collector` is this contract
+    pub fn set_fee_collector(
+        env: Env,
+        caller: Address,
+        new_collector: Address,
+    ) -> Result<(), QuickexError> {
+        admin::require_admin(&env, &caller)?;
+
+        let old_collector = get_fee_collector(&env).ok_or(QuickexError::FeeCollectorNotSet)?;
+        fees::validate_fee_collector(&env, &new_collector)?;
+
+        set_fee_collector(&env, &new_collector);
+        events::publish_fee_collector_changed(&env, old_collector, new_collector);
+        Ok(())
+    }
+
+    /// Get the protocol withdrawal fee in basis points (0 if not configured).
+    pub fn get_fee_bps(env: Env) -> u32 {
+        get_fee_bps(&env)
+    }
+
+    /// Set the protocol fee charged at deposit time (**Admin only**).
+    ///
+    /// On each deposit, `amount * fee_bps / 10_000` is forwarded to the fee collector and
+    /// the escrow records the remainder. Commitments must be computed over that net amount,
+    /// since it is what the recipient withdraws; `deposit` does this itself. Independent of
+    /// the withdrawal fee set with `set_fee`; a `fee_bps` of 0 disables it.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Caller address (must equal admin; must authorize)
+    /// * `fee_bps` - Fee in basis points (at most 1000 = 10%)
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin, or admin not set
+    /// * `FeeTooHigh` - `fee_bps` exceeds the 1000 bps cap
+    /// * `FeeCollectorNotSet` - `fee_bps` is non-zero and no collector has been configured
+    pub fn set_deposit_fee(env: Env, caller: Address, fee_bps: u32) -> Result<(), QuickexError> {
+        admin::require_admin(&env, &caller)?;
+        fees::validate_fee_bps(fee_bps)?;
+        if fee_bps > 0 && get_fee_collector(&env).is_none() {
+            return Err(QuickexError::FeeCollectorNotSet);
+        }
+
+        set_deposit_fee_bps(&env, fee_bps);
+        Ok(())
+    }
+
+    /// Get the protocol deposit fee in basis points (0 if not configured).
+    pub fn get_deposit_fee_bps(env: Env) -> u32 {
+        get_deposit_fee_bps(&env)
+    }
+
+    /// Get the amount an escrow will hold, and its commitment must cover, for a deposit of
+    /// `amount` after the deposit fee.
+    pub fn get_net_deposit_amount(env: Env, amount: i128) -> i128 {
+        escrow::net_deposit_amount(&env, amount)
+    }
+
+    /// Get the fee collector address.
+    ///
+    /// Returns `None` if no fee has been configured.
+    pub fn get_fee_collector(env: Env) -> Option<Address> {
+        get_fee_collector(&env)
+    }
+
+    /// Add a token to the deposit allowlist (**Admin only**).
+    ///
+    /// While the allowlist is empty every token may be deposited. Once at least one token
+    /// is listed, deposits of unlisted tokens are rejected with `TokenNotAllowed`.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Caller address (must equal admin; must authorize)
+    /// * `token` - Token contract address to allow
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin, or admin not set
+    pub fn add_allowed_token(
+        env: Env,
+        caller: Address,
+        token: Address,
+    ) -> Result<(), QuickexError> {
+        admin::require_admin(&env, &caller)?;
+        add_allowed_token(&env, &token);
+        Ok(())
+    }
+
+    /// Remove a token from the deposit allowlist (**Admin only**).
+    ///
+    /// Existing escrows in the token are unaffected and remain withdrawable. Removing the
+    /// last listed token re-opens deposits to every token.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Caller address (must equal admin; must authorize)
+    /// * `token` - Token contract address to remove
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin, or admin not set
+    pub fn remove_allowed_token(
+        env: Env,
+        caller: Address,
+        token: Address,
+    ) -> Result<(), QuickexError> {
+        admin::require_admin(&env, &caller)?;
+        remove_allowed_token(&env, &token);
+        Ok(())
+    }
+
+    /// Check whether deposits of `token` are currently accepted.
+    pub fn is_token_allowed(env: Env, token: Address) -> bool {
+        escrow::is_token_allowed(&env, &token)
+    }
+
+    /// Set the minimum and maximum deposit amount for a token (**Admin only**).
+    ///
+    /// Deposits of `token` outside `[min, max]` are rejected. Tokens without configured
+    /// bounds accept any positive amount.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Caller address (must equal admin; must authorize)
+    /// * `token` - Token contract address
+    /// * `min` - Smallest accepted deposit (must be positive)
+    /// * `max` - Largest accepted deposit (must be at least `min`)
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin, or admin not set
+    /// * `InvalidAmount` - `min` is not positive or exceeds `max`
+    pub fn set_amount_bounds(
+        env: Env,
+        caller: Address,
+        token: Address,
+        min: i128,
+        max: i128,
+    ) -> Result<(), QuickexError> {
+        admin::require_admin(&env, &caller)?;
+
+        if min <= 0 || min > max {
+            return Err(QuickexError::InvalidAmount);
+        }
+
+        set_amount_bounds(&env, &token, &AmountBounds { min, max });
+        Ok(())
+    }
+
+    /// Get the deposit amount bounds for a token.
+    ///
+    /// Returns `{ min: 1, max: i128::MAX }` for tokens without configured bounds.
+    pub fn get_amount_bounds(env: Env, token: Address) -> AmountBounds {
+        get_amount_bounds(&env, &token)
+    }
+
+    /// Set the maximum number of assets accepted by `deposit_basket` (**Admin only**).
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Caller address (must equal admin; must authorize)
+    /// * `max_size` - New maximum basket size (must be positive)
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin, or admin not set
+    /// * `InvalidInput` - `max_size` is zero
+    /// * `BatchTooLarge` - `max_size` is above [`MAX_BATCH`] (20)
+    pub fn set_max_basket_size(
+        env: Env,
+        caller: Address,
+        max_size: u32,
+    ) -> Result<(), QuickexError> {
+        admin::require_admin(&env, &caller)?;
+
+        if max_size == 0 {
+            return Err(QuickexError::InvalidInput);
+        }
+        if max_size > MAX_BATCH {
+            return Err(QuickexError::BatchTooLarge);
+        }
+
+        set_max_basket_size(&env, max_size);
+        Ok(())
+    }
+
+    /// Limit how many `Pending` escrows a single owner may hold (**Admin only**).
+    ///
+    /// Deposits that would exceed the limit fail with `TooManyPendingEscrows`; the owner
+    /// can deposit again once one of their escrows is withdrawn, refunded, cancelled or
+    /// swept. Escrows already held above a newly lowered limit are left alone.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Caller address (must equal admin; must authorize)
+    /// * `max_pending` - Maximum pending escrows per owner; `0` removes the limit
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin, or admin not set
+    pub fn set_max_pending_per_owner(
+        env: Env,
+        caller: Address,
+        max_pending: u32,
+    ) -> Result<(), QuickexError> {
+        admin::require_admin(&env, &caller)?;
+
+        set_max_pending_per_owner(&env, max_pending);
+        Ok(())
+    }
+
+    /// Select the hash function used for new commitments (**Admin only**).
+    ///
+    /// Applies to every commitment the contract computes: amount, bucketed, recipient-list
+    /// and signer commitments, `prepare_deposit`, and the escrows deposited from now on.
+    /// Each escrow records the algorithm current at its deposit, and withdrawals open the
+    /// commitment with that recorded algorithm, so pending escrows stay withdrawable
+    /// after a change.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Caller address (must equal admin; must authorize)
+    /// * `algorithm` - `Sha256` (the default) or `Keccak256`
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin, or admin not set
+    pub fn set_hash_algorithm(
+        env: Env,
+        caller: Address,
+        algorithm: HashAlgorithm,
+    ) -> Result<(), QuickexError> {
+        admin::require_admin(&env, &caller)?;
+
+        set_hash_algorithm(&env, algorithm);
+        Ok(())
+    }
+
+    /// Choose which optional events are published (**Admin only**).
+    ///
+    /// Deposits, withdrawals, refunds, cancels, top-ups, transfers and admin changes are
+    /// always published. `Normal` adds privacy changes and `EscrowExpired`; `Verbose` (the
+    /// default) also adds `AuthFailed` and `CommitmentStatusChanged`. Lower levels save
+    /// fees for deployments that do not index the extra events. Suppressed events are not
+    /// counted by [`get_event_count`](QuickexContract::get_event_count).
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin, or admin not set
+    pub fn set_event_verbosity(
+        env: Env,
+        caller: Address,
+        verbosity: EventVerbosity,
+    ) -> Result<(), QuickexError> {
+        admin::require_admin(&env, &caller)?;
+
+        set_event_verbosity(&env, verbosity);
+        Ok(())
+    }
+
+    /// Get which optional events are published (`Verbose` by default).
+    pub fn get_event_verbosity(env: Env) -> EventVerbosity {
+        get_event_verbosity(&env)
+    }
+
+    /// Mark expired escrows as `Expired` in bulk (**Admin only**).
+    ///
+    /// For each commitment whose escrow is `Pending` and past its expiry, the status
+    /// becomes `Expired` and a status-change event is emitted. Missing, non-pending and
+    /// unexpired commitments are skipped rather than failing the call. Swept escrows can
+    /// still be reclaimed with `refund_expired`.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Admin address (must authorize)
+    /// * `commitments` - Commitments to check
+    ///
+    /// # Returns
+    /// * `u32` - Number of escrows swept
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin
+    /// * `BatchTooLarge` - More than [`MAX_BATCH`] (20) commitments
+    pub fn sweep_expired(
+        env: Env,
+        caller: Address,
+        commitments: Vec<BytesN<32>>,
+    ) -> Result<u32, QuickexError> {
+        admin::require_admin(&env, &caller)?;
+        if commitments.len() > MAX_BATCH {
+            return Err(QuickexError::BatchTooLarge);
+        }
+
+        Ok(escrow::sweep_expired(&env, &commitments))
+    }
+
+    /// Block withdrawals to `address` (**Admin only**).
+    ///
+    /// Any withdrawal paying a blocked recipient fails with `RecipientBlocked`. The list is
+    /// empty by default and blocks nobody. Refunds to depositors are not affected.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin
+    pub fn block_address(env: Env, caller: Address, address: Address) -> Result<(), QuickexError> {
+        admin::require_admin(&env, &caller)?;
+
+        block_address(&env, &address);
+        Ok(())
+    }
+
+    /// Remove `address` from the withdrawal deny-list (**Admin only**).
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin
+    pub fn unblock_address(
+        env: Env,
+        caller: Address,
+        address: Address,
+    ) -> Result<(), QuickexError> {
+        admin::require_admin(&env, &caller)?;
+
+        unblock_address(&env, &address);
+        Ok(())
+    }
+
+    /// Check whether withdrawals to `address` are blocked.
+    pub fn is_blocked(env: Env, address: Address) -> bool {
+        is_blocked(&env, &address)
+    }
+
+    /// Set the swap-on-withdraw rate from `token` to `target_token` (**Admin only**).
+    ///
+    /// Escrows deposited with `deposit_with_swap` pay out
+    /// `amount * numerator / denominator` (rounded down) of `target_token`. Existing escrows
+    /// keep the rate they were deposited at.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin
+    /// * `InvalidInput` - `numerator` or `denominator` is not positive
+    pub fn set_swap_rate(
+        env: Env,
+        caller: Address,
+        token: Address,
+        target_token: Address,
+        numerator: i128,
+        denominator: i128,
+    ) -> Result<(), QuickexError> {
+        admin::require_admin(&env, &caller)?;
+
+        if numerator <= 0 || denominator <= 0 {
+            return Err(QuickexError::InvalidInput);
+        }
+        let rate = SwapRate {
+            numerator,
+            denominator,
+        };
+        set_swap_rate(&env, &token, &target_token, &rate);
+        Ok(())
+    }
+
+    /// Get the swap-on-withdraw rate from `token` to `target_token`, if configured.
+    pub fn get_swap_rate(env: Env, token: Address, target_token: Address) -> Option<SwapRate> {
+        get_swap_rate(&env, &token, &target_token)
+    }
+
+    /// Set the native XLM Stellar Asset Contract address used by `deposit_native` (**Admin only**).
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin
+    pub fn set_native_token(env: Env, caller: Address, token: Address) -> Result<(), QuickexError> {
+        admin::require_admin(&env, &caller)?;
+
+        set_native_token(&env, &token);
+        Ok(())
+    }
+
+    /// Get the native XLM Stellar Asset Contract address, if configured.
+    pub fn get_native_token(env: Env) -> Option<Address> {
+        get_native_token(&env)
+    }
+
+    /// Set the reclaim window in seconds (**Admin only**).
+    ///
+    /// After an escrow expires, only its depositor may reclaim it for this long; after that
+    /// the admin may trigger the refund too. `0` lets the admin reclaim right at expiry.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin
+    pub fn set_reclaim_window(
+        env: Env,
+        caller: Address,
+        window_secs: u64,
+    ) -> Result<(), QuickexError> {
+        admin::require_admin(&env, &caller)?;
+
+        set_reclaim_window(&env, window_secs);
+        Ok(())
+    }
+
+    /// Set the lifetime of escrows deposited without an explicit expiry (**Admin only**).
+    ///
+    /// Applied when an escrow is created; existing escrows keep their deadline. `0` makes
+    /// such escrows never expire. Deposits with their own expiry are not affected.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin
+    pub fn set_default_expiry(env: Env, caller: Address, seconds: u64) -> Result<(), QuickexError> {
+        admin::require_admin(&env, &caller)?;
+
+        set_default_expiry(&env, seconds);
+        Ok(())
+    }
+
+    /// Get the lifetime in seconds of escrows deposited without an explicit expiry
+    /// (default 30 days).
+    pub fn get_default_expiry(env: Env) -> u64 {
+        get_default_expiry(&env)
+    }
+
+    /// Set how many ledgers a recipient must wait between withdrawals (**Admin only**).
+    ///
+    /// A safety net if a commitment secret leaks: a recipient that was just paid cannot be
+    /// paid again until `ledgers` ledgers have closed, whichever withdrawal entrypoint is
+    /// used; early attempts fail with `WithdrawCooldownActive`. `0` (the default) disables
+    /// the cooldown. Withdrawals are only recorded while a cooldown is configured.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin
+    pub fn set_withdraw_cooldown(
+        env: Env,
+        caller: Address,
+        ledgers: u32,
+    ) -> Result<(), QuickexError> {
+        admin::require_admin(&env, &caller)?;
+
+        set_withdraw_cooldown(&env, ledgers);
+        Ok(())
+    }
+
+    /// Get the withdrawal cooldown in ledgers (`0` = disabled).
+    pub fn get_withdraw_cooldown(env: Env) -> u32 {
+        get_withdraw_cooldown(&env)
+    }
+
+    /// Set the privacy flag for accounts that never chose one (**Admin only**).
+    ///
+    /// Lets a deployment be private by default: [`get_privacy`](QuickexContract::get_privacy)
+    /// returns `enabled` for any account without its own setting. An explicit
+    /// [`set_privacy`](QuickexContract::set_privacy) always wins, including `false`.
+    /// Defaults to `false`.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin
+    pub fn set_default_privacy(
+        env: Env,
+        caller: Address,
+        enabled: bool,
+    ) -> Result<(), QuickexError> {
+        admin::require_admin(&env, &caller)?;
+
+        set_default_privacy(&env, enabled);
+        Ok(())
+    }
+
+    /// Get the privacy flag reported for accounts without their own setting.
+    pub fn get_default_privacy(env: Env) -> bool {
+        get_default_privacy(&env)
+    }
+
+    /// Get the reclaim window in seconds (default 7 days).
+    pub fn get_reclaim_window(env: Env) -> u64 {
+        get_reclaim_window(&env)
+    }
+
+    /// Get the maximum number of assets accepted by `deposit_basket`.
+    pub fn get_max_basket_size(env: Env) -> u32 {
+        get_max_basket_size(&env)
+    }
+
+    /// Get the maximum number of pending escrows per owner (`0` = unlimited).
+    pub fn get_max_pending_per_owner(env: Env) -> u32 {
+        get_max_pending_per_owner(&env)
+    }
+
+    /// Get the hash function used for new commitments (`Sha256` unless changed).
+    pub fn get_hash_algorithm(env: Env) -> HashAlgorithm {
+        get_hash_algorithm(&env)
+    }
+
+    /// Get the root of the commitment Merkle tree.
+    ///
+    /// Every deposit appends its commitment as the next leaf of a depth-16 tree (up to
+    /// 65,536 leaves). Once the tree is full, later commitments are not added and the root
+    /// stops changing; deposits themselves are unaffected. Before the first deposit this is
+    /// the root of the empty tree.
+    pub fn get_merkle_root(env: Env) -> BytesN<32> {
+        merkle::root(&env)
+    }
+
+    /// Get the authentication path for the leaf at `leaf_index`.
+    ///
+    /// Returns the 16 sibling hashes from the leaf level up, or `None` if no leaf has been
+    /// inserted at that index. Nodes are hashed as `SHA256(left || right)`.
+    pub fn get_merkle_path(env: Env, leaf_index: u32) -> Option<Vec<BytesN<32>>> {
+        merkle::path(&env, leaf_index)
+    }
+
+    /// Get the Merkle leaf index a deposited commitment was inserted at (`None` if it was
+    /// deposited after the tree filled up).
+    pub fn get_merkle_leaf_index(env: Env, commitment: BytesN<32>) -> Option<u32> {
+        get_merkle_leaf_index(&env, &commitment)
+    }
+
+    /// Check a Merkle inclusion proof against the current root.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `leaf` - Commitment claimed to be in the tree
+    /// * `leaf_index` - Position of the leaf
+    /// * `path` - Sibling hashes from the leaf level up, as returned by `get_merkle_path`
+    pub fn verify_merkle_path(
+        env: Env,
+        leaf: BytesN<32>,
+        leaf_index: u32,
+        path: Vec<BytesN<32>>,
+    ) -> bool {
+        merkle::verify(&env, &leaf, leaf_index, &path, &merkle::root(&env))
+    }
+
+    /// Get aggregated contract metrics.
+    ///
+    /// Counters are maintained as escrows change state, so this is a constant-cost read:
+    /// * `total_deposits` - escrows created `Pending` with funds pulled from a depositor,
+    ///   by any deposit entrypoint or a top-up (one per basket item or split share);
+    ///   imported and transferred escrows are not counted
+    /// * `total_withdrawals` - escrows moved to `Spent` by paying a recipient, whichever
+    ///   withdrawal entrypoint was used
+    /// * `total_refunds` - escrows whose funds went back to the depositor, either
+    ///   `Cancelled` or moved to `Spent` by an expiry refund (including
+    ///   `withdraw_all_expired`)
+    /// * `pending_count` - escrows currently `Pending`, including imported ones
+    pub fn get_contract_stats(env: Env) -> ContractStats {
+        ContractStats {
+            total_deposits: get_counter(&env, &DataKey::TotalDeposits),
+            total_withdrawals: get_counter(&env, &DataKey::TotalWithdrawals),
+            total_refunds: get_counter(&env, &DataKey::TotalRefunds),
+            pending_count: get_counter(&env, &DataKey::PendingCount),
+        }
+    }
+
+    /// Get the number of escrows currently in each status.
+    ///
+    /// Counters move on every status change, so this is a constant-cost read. `expired`
+    /// only counts escrows marked by `sweep_expired`; pending escrows past their expiry
+    /// are still counted as `pending` until swept.
+    pub fn get_status_counts(env: Env) -> StatusCounts {
+        let count = |status| get_counter(&env, &status_count_key(status));
+        StatusCounts {
+            pending: count(EscrowStatus::Pending),
+            spent: count(EscrowStatus::Spent),
+            expired: count(EscrowStatus::Expired),
+            cancelled: count(EscrowStatus::Cancelled),
+        }
+    }
+
+    /// Get the total amount of `token` held for outstanding (pending or expired) escrows.
+    pub fn get_total_escrowed(env: Env, token: Address) -> i128 {
+        get_total_escrowed(&env, &token)
+    }
+
+    /// Compare the contract's actual `token` balance with the amount it tracks as escrowed
+    /// (read-only operator diagnostic).
+    ///
+    /// Returns `balance - get_total_escrowed(token)`. Zero means the books balance; a
+    /// positive value is surplus (tokens sent to the contract directly, or swap reserves),
+    /// and a negative value means escrows are under-backed.
+    pub fn reconcile(env: Env, token: Address) -> i128 {
+        let balance = token::Client::new(&env, &token).balance(&env.current_contract_address());
+        balance - get_total_escrowed(&env, &token)
+    }
+
+    /// Get the number of events this contract has published under `topic` (read-only).
+    ///
+    /// `topic` is the event's name topic, e.g. `Deposit` or `CommitmentStatusChanged`.
+    /// Lets indexers detect missed events by comparing against what they have seen.
+    pub fn get_event_count(env: Env, topic: Symbol) -> u64 {
+        get_counter(&env, &DataKey::EventCount(topic))
+    }
+
+    /// Check if the contract is currently paused.
+    ///
+    /// Returns `true` if paused, `false` otherwise.
+    pub fn is_paused(env: Env) -> bool {
+        is_paused(&env)
+    }
+
+    /// Get the current admin address.
+    ///
+    /// Returns `None` if the contract has not been initialized.
+    pub fn get_admin(env: Env) -> Option<Address> {
+        get_admin(&env)
+    }
+
+    /// Grant a role to an account (**Admin only**).
+    ///
+    /// An account holds at most one role; granting replaces the previous one. At most 20
+    /// accounts may hold a role at once. Roles are labels for access-control audits only:
+    /// no entrypoint accepts a role holder in place of the admin.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `caller` - Caller address (must equal admin; must authorize)
+    /// * `account` - Account receiving the role
+    /// * `role` - `Operator` or `Pauser`
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin, or admin not set
+    /// * `InvalidInput` - `role` is `Admin`; use `set_admin` or `propose_admin` instead
+    /// * `TooManyRoleMembers` - 20 accounts already hold a role
+    pub fn grant_role(
+        env: Env,
+        caller: Address,
+        account: Address,
+        role: Role,
+    ) -> Result<(), QuickexError> {
+        admin::grant_role(&env, caller, account, role)
+    }
+
+    /// Revoke the role held by an account, if any (**Admin only**).
+    ///
+    /// # Errors
+    /// * `Unauthorized` - Caller is not the admin, or admin not set
+    pub fn revoke_role(env: Env, caller: Address, account: Address) -> Result<(), QuickexError> {
+        admin::revoke_role(&env, caller, account)
+    }
+
+    /// Get the role held by an account (read-only).
+    ///
+    /// Returns `Admin` for the current admin, the granted role for role holders, and
+    /// `None` for everyone else.
+    pub fn get_role(env: Env, account: Address) -> Option<Role> {
+        admin::get_role(&env, &account)
+    }
+
+    /// List every privileged account (read-only).
+    ///
+    /// The admin comes first (if set), followed by role holders in grant order; look up
+    /// each account's role with `get_role`. Bounded by the 20-member role limit.
+    pub fn get_all_admins(env: Env) -> Vec<Address> {
+        admin::get_all_admins(&env)
+    }
+
     /// Get the status of an escrow by its commitment hash (read-only).
     ///
-    /// Returns `Pending`, `Spent`, or `Expired` if an escrow exists; `None` otherwise.
+    /// Returns `Pending`, `Spent`, `Expired`, or `Cancelled` if an escrow exists; `None` otherwise.
     ///
     /// # Arguments
     /// * `env` - The contract environment
@@ -391,47 +2512,270 @@ impl QuickexContract {
         entry.map(|e| e.status)
     }
 
+    /// Get the statuses of several escrows in one call (read-only).
+    ///
+    /// Returns one entry per commitment, in order, as
+    /// [`get_commitment_state`](QuickexContract::get_commitment_state) would.
+    ///
+    /// # Errors
+    /// * `BatchTooLarge` - More than [`MAX_BATCH`] (20) commitments
+    pub fn get_commitment_states(
+        env: Env,
+        commitments: Vec<BytesN<32>>,
+    ) -> Result<Vec<Option<EscrowStatus>>, QuickexError> {
+        if commitments.len() > MAX_BATCH {
+            return Err(QuickexError::BatchTooLarge);
+        }
+
+        let mut states = Vec::new(&env);
+        for commitment in commitments.iter() {
+            states.push_back(get_escrow(&env, &commitment.into()).map(|e| e.status));
+        }
+        Ok(states)
+    }
+
+    /// Get the ledger timestamp at which an escrow was created (read-only).
+    ///
+    /// A lightweight alternative to `get_escrow_details` for keepers checking an
+    /// escrow's age. Returns `None` if no escrow exists for `commitment`.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `commitment` - 32-byte commitment hash used as the escrow key
+    pub fn get_deposit_timestamp(env: Env, commitment: BytesN<32>) -> Option<u64> {
+        let commitment_bytes: Bytes = commitment.into();
+        get_escrow(&env, &commitment_bytes).map(|e| e.created_at)
+    }
+
+    /// List the caller's pending escrows with their full details (owner-only).
+    ///
+    /// A recovery tool for depositors who lost a salt and can no longer recompute their
+    /// commitments. Returns `(commitment, entry)` for every `Pending` escrow `owner`
+    /// deposited, in creation order, unredacted. `owner` must authorize.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `owner` - Depositor whose escrows to list (must authorize)
+    pub fn get_my_escrows(env: Env, owner: Address) -> Vec<(BytesN<32>, EscrowEntry)> {
+        owner.require_auth();
+
+        let mut escrows = Vec::new(&env);
+        for commitment in get_owner_escrows(&env, &owner).iter() {
+            if let Some(entry) = get_escrow(&env, &commitment.clone().into()) {
+                escrows.push_back((commitment, entry));
+            }
+        }
+        escrows
+    }
+
+    /// Recover a lost commitment hash from the values it was created with (owner-only).
+    ///
+    /// For users who remember the amount and salt but lost the hash: recomputes the
+    /// commitment from `owner`, `token`, `amount`, and `salt` and returns it if an escrow
+    /// exists under it, in any status. `owner` must authorize, so the call cannot be used to
+    /// probe other accounts' commitments. Returns `None` if no escrow exists or the salt is
+    /// invalid.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `owner` - Address the commitment was created for (must authorize)
+    /// * `token` - Token the deposit was made in
+    /// * `amount` - Amount the commitment was created for
+    /// * `salt` - Salt used when creating the commitment
+    pub fn recover_commitment(
+        env: Env,
+        owner: Address,
+        token: Address,
+        amount: i128,
+        salt: Bytes,
+    ) -> Option<BytesN<32>> {
+        owner.require_auth();
+
+        let preimage =
+            commitment::build_commitment_preimage(&env, owner, token, amount, salt).ok()?;
+        escrow::locate(&env, |algorithm| {
+            commitment::hash_with(&env, algorithm, &preimage)
+        })
+        .map(|(commitment, _)| commitment)
+    }
+
     /// Verify withdrawal parameters without submitting a transaction (read-only).
     ///
-    /// Recomputes the commitment from `amount`, `salt`, and `owner`, then checks that an
-    /// escrow exists with status `Pending` and matching amount. Use before calling `withdraw`.
+    /// Recomputes the commitment from `token`, `amount`, `salt`, and `owner`, then checks that an
+    /// escrow exists with status `Pending`, matching amount, and has not expired. Use before
+    /// calling `withdraw`.
     ///
     /// # Arguments
     /// * `env` - The contract environment
+    /// * `token` - Token the escrow was deposited in
     /// * `amount` - Amount to verify (non-negative)
     /// * `salt` - Salt used when creating the deposit
     /// * `owner` - Owner of the escrow
-    pub fn verify_proof_view(env: Env, amount: i128, salt: Bytes, owner: Address) -> bool {
-        let commitment_result =
-            commitment::create_amount_commitment(&env, owner.clone(), amount, salt);
-
-        let commitment = match commitment_result {
-            Ok(c) => c,
-            Err(_) => return false,
-        };
+    pub fn verify_proof_view(
+        env: Env,
+        token: Address,
+        amount: i128,
+        salt: Bytes,
+        owner: Address,
+    ) -> bool {
+        let preimage =
+            match commitment::build_commitment_preimage(&env, owner, token.clone(), amount, salt) {
+                Ok(p) => p,
+                Err(_) => return false,
+            };
 
-        // Check if commitment exists in storage
-        let commitment_bytes: Bytes = commitment.into();
-        let entry: Option<EscrowEntry> = get_escrow(&env, &commitment_bytes);
+        // Check if commitment exists in storage, under the algorithm it was deposited with
+        let entry = escrow::locate(&env, |algorithm| {
+            commitment::hash_with(&env, algorithm, &preimage)
+        })
+        .map(|(_, entry)| entry);
 
-        // Verify the entry exists, is pending, and amount matches
+        // Verify the entry exists, is pending, has not expired, and token and amount match
         match entry {
-            Some(e) => e.status == EscrowStatus::Pending && e.amount == amount,
+            Some(e) => {
+                e.status == EscrowStatus::Pending
+                    && e.token == token
+                    && e.amount == amount
+                    && !types::is_expired(&e, env.ledger().timestamp())
+            }
             None => false,
         }
     }
 
-    /// Get full escrow details for a commitment hash (read-only).
+    /// Verify withdrawal parameters against a commitment the caller already holds (read-only).
+    ///
+    /// Like [`verify_proof_view`](QuickexContract::verify_proof_view), but also requires the
+    /// recomputed commitment to equal `commitment`, so a client can independently confirm
+    /// that its stored commitment opens to these values and is still withdrawable.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `commitment` - Commitment the caller holds
+    /// * `token` - Token the escrow was deposited in
+    /// * `amount` - Amount to verify (non-negative)
+    /// * `salt` - Salt used when creating the deposit
+    /// * `owner` - Owner of the escrow
+    pub fn verify_proof_view_for(
+        env: Env,
+        commitment: BytesN<32>,
+        token: Address,
+        amount: i128,
+        salt: Bytes,
+        owner: Address,
+    ) -> bool {
+        match escrow::find_withdrawable(&env, &owner, &token, amount, salt) {
+            Ok((recomputed, _)) => recomputed == commitment,
+            Err(_) => false,
+        }
+    }
+
+    /// Check whether `withdraw` would succeed, without spending anything (read-only).
+    ///
+    /// Recomputes the commitment from `to`, `token`, `amount`, and `salt` and runs the same checks
+    /// as `withdraw`, plus a pause check. Performs no transfer or state change, so wallets
+    /// can pre-validate a withdrawal before broadcasting it.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `to` - Recipient the escrow was committed to
+    /// * `token` - Token the escrow was deposited in
+    /// * `amount` - Amount to withdraw
+    /// * `salt` - Salt used when creating the deposit
+    ///
+    /// # Returns
+    /// `Ok(true)` if the withdrawal would succeed; otherwise the error it would fail with.
+    ///
+    /// # Errors
+    /// * `ContractPaused` - The contract is paused
+    /// * `InvalidAmount` - Amount is zero or negative
+    /// * `CommitmentNotFound` - No escrow exists for the computed commitment
+    /// * `AlreadySpent` - Escrow has already been withdrawn or marked spent
+    /// * `EscrowCancelled` - Escrow was cancelled by the depositor
+    /// * `InvalidCommitment` - Escrow token or amount does not match the request
+    /// * `EscrowExpired` - Escrow has passed its expiry and can only be refunded
+    /// * `RecipientBlocked` - `to` is on the admin deny-list
+    /// * `WithdrawCooldownActive` - `to` withdrew within the withdrawal cooldown
+    pub fn can_withdraw(
+        env: Env,
+        to: Address,
+        token: Address,
+        amount: i128,
+        salt: Bytes,
+    ) -> Result<bool, QuickexError> {
+        admin::require_not_paused(&env)?;
+        if amount <= 0 {
+            return Err(QuickexError::InvalidAmount);
+        }
+        if is_blocked(&env, &to) {
+            return Err(QuickexError::RecipientBlocked);
+        }
+        escrow::check_withdraw_cooldown(&env, &to)?;
+        escrow::find_withdrawable(&env, &to, &token, amount, salt)?;
+        Ok(true)
+    }
+
+    /// Get escrow details for a commitment hash (read-only).
     ///
-    /// Returns the full `EscrowEntry` (token, amount, owner, status, created_at) if it exists.
+    /// Returns the `EscrowEntry` (token, amount, owner, status, created_at, expires_at)
+    /// if it exists. If the owner has privacy enabled, `amount` is replaced with
+    /// [`REDACTED_AMOUNT`]; use `get_escrow_details_with_proof` to read it.
     ///
     /// # Arguments
     /// * `env` - The contract environment
     /// * `commitment` - 32-byte commitment hash
-    pub fn get_escrow_details(env: Env, commitment: BytesN<32>) -> Option<EscrowEntry> {
+    /// * `only_active` - If `true`, return `None` unless the escrow is `Pending`, so
+    ///   spent, refunded, cancelled and expired records can be hidden without a status check
+    pub fn get_escrow_details(
+        env: Env,
+        commitment: BytesN<32>,
+        only_active: bool,
+    ) -> Option<EscrowEntry> {
         let commitment_bytes: Bytes = commitment.into();
-        get_escrow(&env, &commitment_bytes)
+        let mut entry = get_escrow(&env, &commitment_bytes)?;
+        if only_active && entry.status != EscrowStatus::Pending {
+            return None;
+        }
+        if privacy::get_privacy(&env, entry.owner.clone()) {
+            entry.amount = REDACTED_AMOUNT;
+        }
+        Some(entry)
+    }
+
+    /// Get unredacted escrow details by proving knowledge of the commitment's opening.
+    ///
+    /// If hashing `recipient || token || amount || salt` with the configured hash algorithm
+    /// (see [`HashAlgorithm`]) matches `commitment` for the stored token and amount, the full
+    /// entry is returned even when the owner has privacy enabled.
+    /// Otherwise this behaves like `get_escrow_details`.
+    ///
+    /// # Arguments
+    /// * `env` - The contract environment
+    /// * `commitment` - 32-byte commitment hash
+    /// * `recipient` - Address the commitment was created for
+    /// * `salt` - Salt used when creating the commitment
+    pub fn get_escrow_details_with_proof(
+        env: Env,
+        commitment: BytesN<32>,
+        recipient: Address,
+        salt: Bytes,
+    ) -> Option<EscrowEntry> {
+        let entry = get_escrow(&env, &commitment.clone().into())?;
+        let opens = commitment::build_commitment_preimage(
+            &env,
+            recipient,
+            entry.token.clone(),
+            entry.amount,
+            salt,
+        )
+        .is_ok_and(|preimage| {
+            commitment::hash_with(&env, entry.hash_algorithm, &preimage) == commitment
+        });
+        if opens {
+            return Some(entry);
+        }
+        Self::get_escrow_details(env, commitment, false)
     }
+
     /// Upgrade the contract to a new WASM implementation (**Admin only**).
     ///
     /// Caller must equal admin and authorize. The new WASM must be pre-uploaded to the network.
@@ -452,14 +2796,7 @@ impl QuickexContract {
         caller: Address,
         new_wasm_hash: BytesN<32>,
     ) -> Result<(), QuickexError> {
-        // Verify caller is admin
-        let admin = get_admin(&env).ok_or(QuickexError::Unauthorized)?;
-        if caller != admin {
-            return Err(QuickexError::Unauthorized);
-        }
-
-        // Require cryptographic authorization from the caller
-        caller.require_auth();
+        admin::require_admin(&env, &caller)?;
 
         // Update the contract WASM to the new implementation
         env.deployer()
@@ -467,11 +2804,13 @@ impl QuickexContract {
 
         // Emit upgrade event for audit trail
         let timestamp = env.ledger().timestamp();
-        events::publish_contract_upgraded(&env, new_wasm_hash, &admin, timestamp);
+        events::publish_contract_upgraded(&env, new_wasm_hash, &caller, timestamp);
 
         Ok(())
     }
 }
 
+mod commitment_vectors_test;
 mod storage_test;
 mod test;
+mod time_test;
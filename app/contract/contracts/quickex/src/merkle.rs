@@ -0,0 +1,130 @@
+//! Fixed-depth incremental Merkle tree over deposit commitments.
+//!
+//! Every deposit appends its commitment as the next leaf. Nodes are hashed as
+//! `SHA256(left || right)` and empty subtrees use precomputed zero hashes, where the
+//! zero leaf is 32 zero bytes. All written nodes are kept so that the authentication
+//! path of any leaf can be read back.
+//!
+//! Leaves are never freed, so the tree fills after [`MERKLE_CAPACITY`] deposits. From
+//! then on commitments are simply not added: the escrow path never depends on the tree,
+//! and later deposits work as usual without an inclusion proof.
+//!
+//! Each insert costs `MERKLE_DEPTH + 4` persistent writes (the leaf, one node per level,
+//! the root, the leaf index and the next index) and up to `MERKLE_DEPTH` reads.
+
+use soroban_sdk::{Bytes, BytesN, Env, Vec};
+
+use crate::storage::{
+    get_merkle_next_index, get_merkle_node, get_merkle_root, set_merkle_leaf_index,
+    set_merkle_next_index, set_merkle_node, set_merkle_root,
+};
+
+/// Depth of the commitment tree
+pub const MERKLE_DEPTH: u32 = 16;
+
+/// Number of leaves the tree can hold (`2^MERKLE_DEPTH`)
+pub const MERKLE_CAPACITY: u32 = 1 << MERKLE_DEPTH;
+
+fn hash_pair(env: &Env, left: &BytesN<32>, right: &BytesN<32>) -> BytesN<32> {
+    let mut data = Bytes::new(env);
+    data.append(&left.clone().into());
+    data.append(&right.clone().into());
+    env.crypto().sha256(&data).into()
+}
+
+/// Root of an empty subtree at each level, from the leaves (`0`) up to the root
+fn zero_hashes(env: &Env) -> Vec<BytesN<32>> {
+    let mut zeros = Vec::new(env);
+    let mut current = BytesN::from_array(env, &[0u8; 32]);
+    zeros.push_back(current.clone());
+    for _ in 0..MERKLE_DEPTH {
+        current = hash_pair(env, &current, &current);
+        zeros.push_back(current.clone());
+    }
+    zeros
+}
+
+fn node_or_zero(env: &Env, zeros: &Vec<BytesN<32>>, level: u32, index: u32) -> BytesN<32> {
+    get_merkle_node(env, level, index).unwrap_or_else(|| zeros.get(level).unwrap())
+}
+
+/// Current root of the commitment tree (the empty-tree root before any insert)
+pub fn root(env: &Env) -> BytesN<32> {
+    get_merkle_root(env).unwrap_or_else(|| zero_hashes(env).get(MERKLE_DEPTH).unwrap())
+}
+
+/// Append `leaf` to the tree and return its leaf index.
+///
+/// Returns `None` without writing anything once the tree is full.
+pub fn insert(env: &Env, leaf: &BytesN<32>) -> Option<u32> {
+    let leaf_index = get_merkle_next_index(env);
+    if leaf_index >= MERKLE_CAPACITY {
+        return None;
+    }
+
+    let zeros = zero_hashes(env);
+    let mut index = leaf_index;
+    let mut node = leaf.clone();
+    set_merkle_node(env, 0, index, &node);
+
+    for level in 0..MERKLE_DEPTH {
+        node = if index & 1 == 0 {
+            let right = node_or_zero(env, &zeros, level, index + 1);
+            hash_pair(env, &node, &right)
+        } else {
+            let left = node_or_zero(env, &zeros, level, index - 1);
+            hash_pair(env, &left, &node)
+        };
+        index /= 2;
+        set_merkle_node(env, level + 1, index, &node);
+    }
+
+    set_merkle_root(env, &node);
+    set_merkle_leaf_index(env, leaf, leaf_index);
+    set_merkle_next_index(env, leaf_index + 1);
+
+    Some(leaf_index)
+}
+
+/// Sibling hashes from the leaf level up for the leaf at `leaf_index`.
+///
+/// Returns `None` if no leaf has been inserted at that index.
+pub fn path(env: &Env, leaf_index: u32) -> Option<Vec<BytesN<32>>> {
+    if leaf_index >= get_merkle_next_index(env) {
+        return None;
+    }
+
+    let zeros = zero_hashes(env);
+    let mut siblings = Vec::new(env);
+    let mut index = leaf_index;
+    for level in 0..MERKLE_DEPTH {
+        siblings.push_back(node_or_zero(env, &zeros, level, index ^ 1));
+        index /= 2;
+    }
+    Some(siblings)
+}
+
+/// Check that `path` proves `leaf` sits at `leaf_index` under `root`
+pub fn verify(
+    env: &Env,
+    leaf: &BytesN<32>,
+    leaf_index: u32,
+    path: &Vec<BytesN<32>>,
+    root: &BytesN<32>,
+) -> bool {
+    if path.len() != MERKLE_DEPTH || leaf_index >= MERKLE_CAPACITY {
+        return false;
+    }
+
+    let mut index = leaf_index;
+    let mut node = leaf.clone();
+    for sibling in path.iter() {
+        node = if index & 1 == 0 {
+            hash_pair(env, &node, &sibling)
+        } else {
+            hash_pair(env, &sibling, &node)
+        };
+        index /= 2;
+    }
+    node == *root
+}
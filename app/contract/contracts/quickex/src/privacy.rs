@@ -1,10 +1,35 @@
 use crate::errors::QuickexError;
 use crate::events::publish_privacy_toggled;
+use crate::storage::get_default_privacy;
 use soroban_sdk::{Address, Env, Symbol};
 
+/// Highest numeric privacy level.
+///
+/// * `0` - Public: no privacy; the account's activity is shown normally
+/// * `1` - Basic: the account is hidden from public listings and search
+/// * `2` - Enhanced: as 1, and amounts are hidden in the account's activity
+/// * `3` - Maximum: as 2, and counterparties are hidden as well
+pub const MAX_PRIVACY_LEVEL: u32 = 3;
+
+/// Reject numeric privacy levels outside `0..=3`
+pub fn validate_privacy_level(level: u32) -> Result<(), QuickexError> {
+    if level > MAX_PRIVACY_LEVEL {
+        return Err(QuickexError::InvalidPrivacyLevel);
+    }
+    Ok(())
+}
+
 pub fn set_privacy(env: &Env, owner: Address, enabled: bool) -> Result<(), QuickexError> {
     owner.require_auth();
 
+    store_privacy(env, owner, enabled);
+
+    Ok(())
+}
+
+/// Record the privacy flag for `owner` and announce it. Callers are responsible for
+/// authorization.
+pub fn store_privacy(env: &Env, owner: Address, enabled: bool) {
     let key = Symbol::new(env, "privacy_enabled");
     env.storage()
         .persistent()
@@ -12,14 +37,14 @@ pub fn set_privacy(env: &Env, owner: Address, enabled: bool) -> Result<(), Quick
 
     let timestamp = env.ledger().timestamp();
     publish_privacy_toggled(env, owner, enabled, timestamp);
-
-    Ok(())
 }
 
+/// Privacy flag for `owner`, falling back to the admin default when the account has never
+/// set one
 pub fn get_privacy(env: &Env, owner: Address) -> bool {
     let key = Symbol::new(env, "privacy_enabled");
     env.storage()
         .persistent()
         .get(&(key, owner))
-        .unwrap_or(false)
+        .unwrap_or_else(|| get_default_privacy(env))
 }
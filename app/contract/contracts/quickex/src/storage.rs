@@ -1,6 +1,12 @@
-use soroban_sdk::{contracttype, Address, Bytes, Env, Vec};
+use soroban_sdk::{contracttype, Address, Bytes, BytesN, Env, String, Symbol, Vec};
 
-use crate::types::EscrowEntry;
+use crate::errors::QuickexError;
+use crate::privacy::validate_privacy_level;
+use crate::types::{
+    status_to_code, AmountBounds, EscrowEntry, EscrowStatus, EventVerbosity, HashAlgorithm, Role,
+    SwapRate, TtlConfig, DEFAULT_AMOUNT_BOUNDS, DEFAULT_EXPIRY_SECS, DEFAULT_RECLAIM_WINDOW_SECS,
+    DEFAULT_TTL_CONFIG,
+};
 
 /// Storage keys for the contract
 #[contracttype]
@@ -12,12 +18,70 @@ pub enum DataKey {
     Paused,
     PrivacyLevel(Address),
     PrivacyHistory(Address),
+    FeeBps,
+    FeeCollector,
+    Memo(Bytes),
+    AmountBounds(Address),
+    AllowedToken(Address),
+    AllowedTokenCount,
+    MaxBasketSize,
+    TotalDeposits,
+    TotalWithdrawals,
+    TotalRefunds,
+    PendingCount,
+    MerkleRoot,
+    MerkleNextIndex,
+    MerkleNode(u32, u32),
+    MerkleLeafIndex(BytesN<32>),
+    ReclaimWindow,
+    PendingAdmin,
+    NativeToken,
+    TtlConfig,
+    PauseReason,
+    SwapRate(Address, Address),
+    EventCount(Symbol),
+    TotalEscrowed(Address),
+    Blocked(Address),
+    PausedSince,
+    /// Keyed by `sha256(salt)`; raw salts are never persisted
+    UsedSalt((Address, BytesN<32>)),
+    MaxPendingPerOwner,
+    OwnerPendingCount(Address),
+    /// Keyed by `status_to_code(status)` rather than the full `EscrowStatus`
+    StatusCount(u32),
+    Role(Address),
+    RoleMembers,
+    HashAlgorithm,
+    Initialized,
+    DefaultExpiry,
+    ViewKey(Bytes),
+    WithdrawCooldown,
+    LastWithdraw(Address),
+    OwnerEscrows(Address),
+    DepositFeeBps,
+    DefaultPrivacy,
+    Shutdown,
+    EventVerbosity,
 }
 
-/// Put an escrow entry into storage
+/// Maximum number of accounts that may hold a granted role at once
+pub const MAX_ROLE_MEMBERS: u32 = 20;
+
+/// Default maximum number of assets in a basket deposit
+pub const DEFAULT_MAX_BASKET_SIZE: u32 = 10;
+
+/// Maximum length in bytes of an escrow memo
+pub const MAX_MEMO_LEN: u32 = 256;
+
+/// Put an escrow entry into storage and bump its TTL per the configured [`TtlConfig`]
 pub fn put_escrow(env: &Env, commitment: &Bytes, entry: &EscrowEntry) {
     let key = DataKey::Escrow(commitment.clone());
     env.storage().persistent().set(&key, entry);
+
+    let ttl = get_ttl_config(env);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, ttl.threshold, ttl.extend_to);
 }
 
 /// Get an escrow entry from storage
@@ -27,14 +91,12 @@ pub fn get_escrow(env: &Env, commitment: &Bytes) -> Option<EscrowEntry> {
 }
 
 /// Check if an escrow entry exists in storage
-#[allow(dead_code)]
 pub fn has_escrow(env: &Env, commitment: &Bytes) -> bool {
     let key = DataKey::Escrow(commitment.clone());
     env.storage().persistent().has(&key)
 }
 
-/// Get the next escrow counter value
-#[allow(dead_code)]
+/// Get the current escrow counter value
 pub fn get_escrow_counter(env: &Env) -> u64 {
     let key = DataKey::EscrowCounter;
     env.storage().persistent().get(&key).unwrap_or(0)
@@ -61,6 +123,125 @@ pub fn get_admin(env: &Env) -> Option<Address> {
     env.storage().persistent().get(&key)
 }
 
+/// Bump the admin entry's TTL per the configured [`TtlConfig`] so it is not archived
+/// while the contract is in use
+pub fn bump_admin_ttl(env: &Env) {
+    let key = DataKey::Admin;
+    if env.storage().persistent().has(&key) {
+        let ttl = get_ttl_config(env);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, ttl.threshold, ttl.extend_to);
+    }
+}
+
+/// Record that `initialize` has run.
+///
+/// Kept in instance storage, which shares the contract instance's TTL: it cannot be
+/// archived while the contract itself is still callable, unlike the persistent admin entry.
+pub fn set_initialized(env: &Env) {
+    env.storage().instance().set(&DataKey::Initialized, &true);
+}
+
+/// Check whether `initialize` has run
+pub fn is_initialized(env: &Env) -> bool {
+    env.storage().instance().has(&DataKey::Initialized)
+}
+
+/// Set the proposed admin awaiting acceptance
+pub fn set_pending_admin(env: &Env, admin: &Address) {
+    let key = DataKey::PendingAdmin;
+    env.storage().persistent().set(&key, admin);
+}
+
+/// Get the proposed admin awaiting acceptance
+pub fn get_pending_admin(env: &Env) -> Option<Address> {
+    let key = DataKey::PendingAdmin;
+    env.storage().persistent().get(&key)
+}
+
+/// Clear the proposed admin
+pub fn remove_pending_admin(env: &Env) {
+    let key = DataKey::PendingAdmin;
+    env.storage().persistent().remove(&key);
+}
+
+/// Get the commitments of `owner`'s pending escrows, in creation order
+pub fn get_owner_escrows(env: &Env, owner: &Address) -> Vec<BytesN<32>> {
+    let key = DataKey::OwnerEscrows(owner.clone());
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env))
+}
+
+/// Add a pending escrow to `owner`'s index
+pub fn add_owner_escrow(env: &Env, owner: &Address, commitment: &BytesN<32>) {
+    let mut commitments = get_owner_escrows(env, owner);
+    commitments.push_back(commitment.clone());
+    let key = DataKey::OwnerEscrows(owner.clone());
+    env.storage().persistent().set(&key, &commitments);
+}
+
+/// Remove an escrow that is no longer pending from `owner`'s index
+pub fn remove_owner_escrow(env: &Env, owner: &Address, commitment: &BytesN<32>) {
+    let mut commitments = get_owner_escrows(env, owner);
+    if let Some(index) = commitments.first_index_of(commitment) {
+        commitments.remove(index);
+        let key = DataKey::OwnerEscrows(owner.clone());
+        if commitments.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &commitments);
+        }
+    }
+}
+
+/// Get the role granted to `account`, if any
+pub fn get_role(env: &Env, account: &Address) -> Option<Role> {
+    let key = DataKey::Role(account.clone());
+    env.storage().persistent().get(&key)
+}
+
+/// Grant `role` to `account`, adding it to the role member index
+pub fn set_role(env: &Env, account: &Address, role: Role) -> Result<(), QuickexError> {
+    let mut members = get_role_members(env);
+    if !members.contains(account) {
+        if members.len() >= MAX_ROLE_MEMBERS {
+            return Err(QuickexError::TooManyRoleMembers);
+        }
+        members.push_back(account.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::RoleMembers, &members);
+    }
+    let key = DataKey::Role(account.clone());
+    env.storage().persistent().set(&key, &role);
+    Ok(())
+}
+
+/// Revoke any role held by `account`, removing it from the role member index
+pub fn remove_role(env: &Env, account: &Address) {
+    let mut members = get_role_members(env);
+    if let Some(index) = members.first_index_of(account) {
+        members.remove(index);
+        env.storage()
+            .persistent()
+            .set(&DataKey::RoleMembers, &members);
+    }
+    let key = DataKey::Role(account.clone());
+    env.storage().persistent().remove(&key);
+}
+
+/// Get every account holding a granted role, in grant order
+pub fn get_role_members(env: &Env) -> Vec<Address> {
+    let key = DataKey::RoleMembers;
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env))
+}
+
 /// Set paused state
 pub fn set_paused(env: &Env, paused: bool) {
     let key = DataKey::Paused;
@@ -73,10 +254,76 @@ pub fn is_paused(env: &Env) -> bool {
     env.storage().persistent().get(&key).unwrap_or(false)
 }
 
-/// Set privacy level for an account
-pub fn set_privacy_level(env: &Env, account: &Address, level: u32) {
+/// Set which optional events are published
+pub fn set_event_verbosity(env: &Env, verbosity: EventVerbosity) {
+    let key = DataKey::EventVerbosity;
+    env.storage().persistent().set(&key, &verbosity);
+}
+
+/// Get which optional events are published (defaults to `Verbose`)
+pub fn get_event_verbosity(env: &Env) -> EventVerbosity {
+    let key = DataKey::EventVerbosity;
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(EventVerbosity::Verbose)
+}
+
+/// Permanently mark the contract as shut down
+pub fn set_shutdown(env: &Env) {
+    let key = DataKey::Shutdown;
+    env.storage().persistent().set(&key, &true);
+}
+
+/// Whether the contract has been shut down
+pub fn is_shutdown(env: &Env) -> bool {
+    let key = DataKey::Shutdown;
+    env.storage().persistent().get(&key).unwrap_or(false)
+}
+
+/// Set the ledger timestamp at which the contract was paused
+pub fn set_paused_since(env: &Env, timestamp: u64) {
+    let key = DataKey::PausedSince;
+    env.storage().persistent().set(&key, &timestamp);
+}
+
+/// Get the ledger timestamp at which the contract was paused
+pub fn get_paused_since(env: &Env) -> Option<u64> {
+    let key = DataKey::PausedSince;
+    env.storage().persistent().get(&key)
+}
+
+/// Clear the paused-since timestamp
+pub fn remove_paused_since(env: &Env) {
+    let key = DataKey::PausedSince;
+    env.storage().persistent().remove(&key);
+}
+
+/// Set the reason given for the current pause
+pub fn set_pause_reason(env: &Env, reason: &String) {
+    let key = DataKey::PauseReason;
+    env.storage().persistent().set(&key, reason);
+}
+
+/// Get the reason given for the current pause
+pub fn get_pause_reason(env: &Env) -> Option<String> {
+    let key = DataKey::PauseReason;
+    env.storage().persistent().get(&key)
+}
+
+/// Clear the pause reason
+pub fn remove_pause_reason(env: &Env) {
+    let key = DataKey::PauseReason;
+    env.storage().persistent().remove(&key);
+}
+
+/// Set privacy level for an account; levels above 3 are rejected
+pub fn set_privacy_level(env: &Env, account: &Address, level: u32) -> Result<(), QuickexError> {
+    validate_privacy_level(level)?;
+
     let key = DataKey::PrivacyLevel(account.clone());
     env.storage().persistent().set(&key, &level);
+    Ok(())
 }
 
 /// Get privacy level for an account
@@ -105,3 +352,407 @@ pub fn get_privacy_history(env: &Env, account: &Address) -> Vec<u32> {
         .get(&key)
         .unwrap_or(Vec::new(env))
 }
+
+/// Set the withdrawal fee in basis points
+pub fn set_fee_bps(env: &Env, fee_bps: u32) {
+    let key = DataKey::FeeBps;
+    env.storage().persistent().set(&key, &fee_bps);
+}
+
+/// Get the withdrawal fee in basis points (defaults to 0)
+pub fn get_fee_bps(env: &Env) -> u32 {
+    let key = DataKey::FeeBps;
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+/// Set the deposit fee in basis points
+pub fn set_deposit_fee_bps(env: &Env, fee_bps: u32) {
+    let key = DataKey::DepositFeeBps;
+    env.storage().persistent().set(&key, &fee_bps);
+}
+
+/// Get the deposit fee in basis points (defaults to 0)
+pub fn get_deposit_fee_bps(env: &Env) -> u32 {
+    let key = DataKey::DepositFeeBps;
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+/// Set the fee collector address
+pub fn set_fee_collector(env: &Env, collector: &Address) {
+    let key = DataKey::FeeCollector;
+    env.storage().persistent().set(&key, collector);
+}
+
+/// Get the fee collector address
+pub fn get_fee_collector(env: &Env) -> Option<Address> {
+    let key = DataKey::FeeCollector;
+    env.storage().persistent().get(&key)
+}
+
+/// Set the memo attached to an escrow
+pub fn set_memo(env: &Env, commitment: &Bytes, memo: &Bytes) {
+    let key = DataKey::Memo(commitment.clone());
+    env.storage().persistent().set(&key, memo);
+}
+
+/// Get the memo attached to an escrow
+pub fn get_memo(env: &Env, commitment: &Bytes) -> Option<Bytes> {
+    let key = DataKey::Memo(commitment.clone());
+    env.storage().persistent().get(&key)
+}
+
+/// Set the viewing-key hash registered for an escrow
+pub fn set_view_key_hash(env: &Env, commitment: &Bytes, view_key_hash: &BytesN<32>) {
+    let key = DataKey::ViewKey(commitment.clone());
+    env.storage().persistent().set(&key, view_key_hash);
+}
+
+/// Get the viewing-key hash registered for an escrow
+pub fn get_view_key_hash(env: &Env, commitment: &Bytes) -> Option<BytesN<32>> {
+    let key = DataKey::ViewKey(commitment.clone());
+    env.storage().persistent().get(&key)
+}
+
+/// Set the deposit amount bounds for a token
+pub fn set_amount_bounds(env: &Env, token: &Address, bounds: &AmountBounds) {
+    let key = DataKey::AmountBounds(token.clone());
+    env.storage().persistent().set(&key, bounds);
+}
+
+/// Get the deposit amount bounds for a token (permissive when unconfigured)
+pub fn get_amount_bounds(env: &Env, token: &Address) -> AmountBounds {
+    let key = DataKey::AmountBounds(token.clone());
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(DEFAULT_AMOUNT_BOUNDS)
+}
+
+/// Add a token to the deposit allowlist
+pub fn add_allowed_token(env: &Env, token: &Address) {
+    let key = DataKey::AllowedToken(token.clone());
+    if env.storage().persistent().has(&key) {
+        return;
+    }
+    env.storage().persistent().set(&key, &true);
+    let count = get_allowed_token_count(env);
+    env.storage()
+        .persistent()
+        .set(&DataKey::AllowedTokenCount, &(count + 1));
+}
+
+/// Remove a token from the deposit allowlist
+pub fn remove_allowed_token(env: &Env, token: &Address) {
+    let key = DataKey::AllowedToken(token.clone());
+    if !env.storage().persistent().has(&key) {
+        return;
+    }
+    env.storage().persistent().remove(&key);
+    let count = get_allowed_token_count(env);
+    env.storage()
+        .persistent()
+        .set(&DataKey::AllowedTokenCount, &(count - 1));
+}
+
+/// Check if a token is on the deposit allowlist
+pub fn is_allowed_token(env: &Env, token: &Address) -> bool {
+    let key = DataKey::AllowedToken(token.clone());
+    env.storage().persistent().has(&key)
+}
+
+/// Get the number of allowlisted tokens
+pub fn get_allowed_token_count(env: &Env) -> u32 {
+    let key = DataKey::AllowedTokenCount;
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+/// Set the maximum basket size
+pub fn set_max_basket_size(env: &Env, max_size: u32) {
+    let key = DataKey::MaxBasketSize;
+    env.storage().persistent().set(&key, &max_size);
+}
+
+/// Get the maximum basket size
+pub fn get_max_basket_size(env: &Env) -> u32 {
+    let key = DataKey::MaxBasketSize;
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(DEFAULT_MAX_BASKET_SIZE)
+}
+
+/// Set how many pending escrows one owner may hold; `0` removes the limit
+pub fn set_max_pending_per_owner(env: &Env, max_pending: u32) {
+    let key = DataKey::MaxPendingPerOwner;
+    env.storage().persistent().set(&key, &max_pending);
+}
+
+/// Get how many pending escrows one owner may hold; `0` means unlimited
+pub fn get_max_pending_per_owner(env: &Env) -> u32 {
+    let key = DataKey::MaxPendingPerOwner;
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+/// Set the hash algorithm used for amount commitments
+pub fn set_hash_algorithm(env: &Env, algorithm: HashAlgorithm) {
+    let key = DataKey::HashAlgorithm;
+    env.storage().persistent().set(&key, &algorithm);
+}
+
+/// Get the hash algorithm used for amount commitments; `Sha256` if never set
+pub fn get_hash_algorithm(env: &Env) -> HashAlgorithm {
+    let key = DataKey::HashAlgorithm;
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(HashAlgorithm::Sha256)
+}
+
+/// Get the value of a statistics counter such as `DataKey::TotalDeposits`
+pub fn get_counter(env: &Env, key: &DataKey) -> u64 {
+    env.storage().persistent().get(key).unwrap_or(0)
+}
+
+/// Counter holding the number of escrows currently in `status`.
+///
+/// `Pending` reuses `DataKey::PendingCount`, which predates the other counters; the rest
+/// are keyed by the compact [`status_to_code`] code.
+pub fn status_count_key(status: EscrowStatus) -> DataKey {
+    match status {
+        EscrowStatus::Pending => DataKey::PendingCount,
+        _ => DataKey::StatusCount(status_to_code(status)),
+    }
+}
+
+/// Increment a statistics counter by one
+pub fn increment_counter(env: &Env, key: &DataKey) {
+    let count = get_counter(env, key);
+    env.storage().persistent().set(key, &(count + 1));
+}
+
+/// Decrement a statistics counter by one, stopping at zero
+pub fn decrement_counter(env: &Env, key: &DataKey) {
+    let count = get_counter(env, key);
+    env.storage()
+        .persistent()
+        .set(key, &count.saturating_sub(1));
+}
+
+/// Get the commitment Merkle root, if any leaf has been inserted
+pub fn get_merkle_root(env: &Env) -> Option<BytesN<32>> {
+    let key = DataKey::MerkleRoot;
+    env.storage().persistent().get(&key)
+}
+
+/// Set the commitment Merkle root
+pub fn set_merkle_root(env: &Env, root: &BytesN<32>) {
+    let key = DataKey::MerkleRoot;
+    env.storage().persistent().set(&key, root);
+}
+
+/// Get the index the next Merkle leaf will be inserted at
+pub fn get_merkle_next_index(env: &Env) -> u32 {
+    let key = DataKey::MerkleNextIndex;
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+/// Set the index the next Merkle leaf will be inserted at
+pub fn set_merkle_next_index(env: &Env, index: u32) {
+    let key = DataKey::MerkleNextIndex;
+    env.storage().persistent().set(&key, &index);
+}
+
+/// Get the Merkle node at `level` (0 = leaves) and `index`, if it has been written
+pub fn get_merkle_node(env: &Env, level: u32, index: u32) -> Option<BytesN<32>> {
+    let key = DataKey::MerkleNode(level, index);
+    env.storage().persistent().get(&key)
+}
+
+/// Set the Merkle node at `level` (0 = leaves) and `index`
+pub fn set_merkle_node(env: &Env, level: u32, index: u32, node: &BytesN<32>) {
+    let key = DataKey::MerkleNode(level, index);
+    env.storage().persistent().set(&key, node);
+}
+
+/// Get the leaf index a commitment was inserted at
+pub fn get_merkle_leaf_index(env: &Env, commitment: &BytesN<32>) -> Option<u32> {
+    let key = DataKey::MerkleLeafIndex(commitment.clone());
+    env.storage().persistent().get(&key)
+}
+
+/// Record the leaf index a commitment was inserted at
+pub fn set_merkle_leaf_index(env: &Env, commitment: &BytesN<32>, index: u32) {
+    let key = DataKey::MerkleLeafIndex(commitment.clone());
+    env.storage().persistent().set(&key, &index);
+}
+
+/// Set the lifetime in seconds of escrows deposited without an explicit expiry
+pub fn set_default_expiry(env: &Env, secs: u64) {
+    let key = DataKey::DefaultExpiry;
+    env.storage().persistent().set(&key, &secs);
+}
+
+/// Get the lifetime in seconds of escrows deposited without an explicit expiry
+pub fn get_default_expiry(env: &Env) -> u64 {
+    let key = DataKey::DefaultExpiry;
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(DEFAULT_EXPIRY_SECS)
+}
+
+/// Set the number of ledgers a recipient must wait between withdrawals; `0` disables it
+pub fn set_withdraw_cooldown(env: &Env, ledgers: u32) {
+    let key = DataKey::WithdrawCooldown;
+    env.storage().persistent().set(&key, &ledgers);
+}
+
+/// Get the number of ledgers a recipient must wait between withdrawals; `0` if disabled
+pub fn get_withdraw_cooldown(env: &Env) -> u32 {
+    let key = DataKey::WithdrawCooldown;
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+/// Set the privacy flag reported for accounts that never set their own
+pub fn set_default_privacy(env: &Env, enabled: bool) {
+    let key = DataKey::DefaultPrivacy;
+    env.storage().persistent().set(&key, &enabled);
+}
+
+/// Get the privacy flag reported for accounts that never set their own (defaults to `false`)
+pub fn get_default_privacy(env: &Env) -> bool {
+    let key = DataKey::DefaultPrivacy;
+    env.storage().persistent().get(&key).unwrap_or(false)
+}
+
+/// Record the ledger sequence of `recipient`'s latest withdrawal
+pub fn set_last_withdraw(env: &Env, recipient: &Address, sequence: u32) {
+    let key = DataKey::LastWithdraw(recipient.clone());
+    env.storage().persistent().set(&key, &sequence);
+}
+
+/// Get the ledger sequence of `recipient`'s latest recorded withdrawal
+pub fn get_last_withdraw(env: &Env, recipient: &Address) -> Option<u32> {
+    let key = DataKey::LastWithdraw(recipient.clone());
+    env.storage().persistent().get(&key)
+}
+
+/// Set the reclaim window in seconds
+pub fn set_reclaim_window(env: &Env, window: u64) {
+    let key = DataKey::ReclaimWindow;
+    env.storage().persistent().set(&key, &window);
+}
+
+/// Get the reclaim window in seconds
+pub fn get_reclaim_window(env: &Env) -> u64 {
+    let key = DataKey::ReclaimWindow;
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(DEFAULT_RECLAIM_WINDOW_SECS)
+}
+
+/// Set the native XLM Stellar Asset Contract address
+pub fn set_native_token(env: &Env, token: &Address) {
+    let key = DataKey::NativeToken;
+    env.storage().persistent().set(&key, token);
+}
+
+/// Get the native XLM Stellar Asset Contract address
+pub fn get_native_token(env: &Env) -> Option<Address> {
+    let key = DataKey::NativeToken;
+    env.storage().persistent().get(&key)
+}
+
+/// Set the TTL settings for persistent entries
+pub fn set_ttl_config(env: &Env, config: &TtlConfig) {
+    let key = DataKey::TtlConfig;
+    env.storage().persistent().set(&key, config);
+}
+
+/// Get the TTL settings for persistent entries (defaults to [`DEFAULT_TTL_CONFIG`])
+pub fn get_ttl_config(env: &Env) -> TtlConfig {
+    let key = DataKey::TtlConfig;
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(DEFAULT_TTL_CONFIG)
+}
+
+/// Set the swap-on-withdraw rate from `token` to `target_token`
+pub fn set_swap_rate(env: &Env, token: &Address, target_token: &Address, rate: &SwapRate) {
+    let key = DataKey::SwapRate(token.clone(), target_token.clone());
+    env.storage().persistent().set(&key, rate);
+}
+
+/// Get the swap-on-withdraw rate from `token` to `target_token`
+pub fn get_swap_rate(env: &Env, token: &Address, target_token: &Address) -> Option<SwapRate> {
+    let key = DataKey::SwapRate(token.clone(), target_token.clone());
+    env.storage().persistent().get(&key)
+}
+
+/// Get the total amount of `token` held for outstanding (pending or expired) escrows
+pub fn get_total_escrowed(env: &Env, token: &Address) -> i128 {
+    let key = DataKey::TotalEscrowed(token.clone());
+    env.storage().persistent().get(&key).unwrap_or(0)
+}
+
+/// Add `amount` to the total escrowed for `token`; `ArithmeticOverflow` if it would overflow
+pub fn add_total_escrowed(env: &Env, token: &Address, amount: i128) -> Result<(), QuickexError> {
+    let total = get_total_escrowed(env, token)
+        .checked_add(amount)
+        .ok_or(QuickexError::ArithmeticOverflow)?;
+    let key = DataKey::TotalEscrowed(token.clone());
+    env.storage().persistent().set(&key, &total);
+    Ok(())
+}
+
+/// Subtract `amount` from the total escrowed for `token`, stopping at zero.
+///
+/// Entries written before the total was tracked are not included in it, so paying them
+/// out must not fail; the total is floored at zero instead.
+pub fn sub_total_escrowed(env: &Env, token: &Address, amount: i128) {
+    let total = get_total_escrowed(env, token)
+        .checked_sub(amount)
+        .map_or(0, |total| total.max(0));
+    let key = DataKey::TotalEscrowed(token.clone());
+    env.storage().persistent().set(&key, &total);
+}
+
+/// Add an address to the withdrawal deny-list
+pub fn block_address(env: &Env, address: &Address) {
+    let key = DataKey::Blocked(address.clone());
+    env.storage().persistent().set(&key, &true);
+}
+
+/// Remove an address from the withdrawal deny-list
+pub fn unblock_address(env: &Env, address: &Address) {
+    let key = DataKey::Blocked(address.clone());
+    env.storage().persistent().remove(&key);
+}
+
+/// Check if an address is on the withdrawal deny-list
+pub fn is_blocked(env: &Env, address: &Address) -> bool {
+    let key = DataKey::Blocked(address.clone());
+    env.storage().persistent().has(&key)
+}
+
+/// Key recording that `owner` has deposited with `salt`.
+///
+/// A stored salt would let anyone open the commitments it was used in, so only its
+/// SHA-256 hash is kept.
+fn used_salt_key(env: &Env, owner: &Address, salt: &Bytes) -> DataKey {
+    DataKey::UsedSalt((owner.clone(), env.crypto().sha256(salt).into()))
+}
+
+/// Record that `owner` has deposited with `salt`
+pub fn mark_salt_used(env: &Env, owner: &Address, salt: &Bytes) {
+    let key = used_salt_key(env, owner, salt);
+    env.storage().persistent().set(&key, &true);
+}
+
+/// Whether `owner` has already deposited with `salt`
+pub fn is_salt_used(env: &Env, owner: &Address, salt: &Bytes) -> bool {
+    let key = used_salt_key(env, owner, salt);
+    env.storage().persistent().has(&key)
+}
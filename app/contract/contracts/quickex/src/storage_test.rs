@@ -2,8 +2,9 @@
 use soroban_sdk::{testutils::Address as _, Address, Bytes, Env};
 
 use crate::{
+    errors::QuickexError,
     storage::*,
-    types::{EscrowEntry, EscrowStatus},
+    types::{EscrowEntry, EscrowStatus, HashAlgorithm, Payout, NO_EXPIRY},
 };
 
 #[test]
@@ -24,6 +25,9 @@ fn test_escrow_storage() {
             owner: owner.clone(),
             status: EscrowStatus::Pending,
             created_at,
+            expires_at: NO_EXPIRY,
+            payout: Payout::Deposited,
+            hash_algorithm: HashAlgorithm::Sha256,
         };
 
         // Test put_escrow
@@ -64,6 +68,9 @@ fn test_escrow_status_update() {
             owner: owner.clone(),
             status: EscrowStatus::Pending,
             created_at,
+            expires_at: NO_EXPIRY,
+            payout: Payout::Deposited,
+            hash_algorithm: HashAlgorithm::Sha256,
         };
 
         put_escrow(&env, &commitment, &entry);
@@ -146,15 +153,25 @@ fn test_privacy_storage() {
     let contract_id = env.register(crate::QuickexContract, ());
     env.as_contract(&contract_id, || {
         let account = Address::generate(&env);
-        let privacy_level = 5u32;
+        let privacy_level = 1u32;
 
         // Test setting privacy level
-        set_privacy_level(&env, &account, privacy_level);
+        set_privacy_level(&env, &account, privacy_level).unwrap();
         assert_eq!(get_privacy_level(&env, &account).unwrap(), privacy_level);
 
         // Test updating privacy level
-        let new_privacy_level = 10u32;
-        set_privacy_level(&env, &account, new_privacy_level);
+        let new_privacy_level = 3u32;
+        set_privacy_level(&env, &account, new_privacy_level).unwrap();
+        assert_eq!(
+            get_privacy_level(&env, &account).unwrap(),
+            new_privacy_level
+        );
+
+        // Levels above 3 are rejected and leave the stored level untouched
+        assert_eq!(
+            set_privacy_level(&env, &account, 4u32),
+            Err(QuickexError::InvalidPrivacyLevel)
+        );
         assert_eq!(
             get_privacy_level(&env, &account).unwrap(),
             new_privacy_level
@@ -177,3 +194,27 @@ fn test_privacy_storage() {
         assert_eq!(get_privacy_history(&env, &non_existent_account).len(), 0);
     });
 }
+
+#[test]
+fn test_total_escrowed_storage() {
+    let env = Env::default();
+    let contract_id = env.register(crate::QuickexContract, ());
+    env.as_contract(&contract_id, || {
+        let token = Address::generate(&env);
+        assert_eq!(get_total_escrowed(&env, &token), 0);
+
+        add_total_escrowed(&env, &token, i128::MAX - 1).unwrap();
+        assert_eq!(
+            add_total_escrowed(&env, &token, 2),
+            Err(QuickexError::ArithmeticOverflow)
+        );
+        assert_eq!(get_total_escrowed(&env, &token), i128::MAX - 1);
+
+        sub_total_escrowed(&env, &token, i128::MAX - 100);
+        assert_eq!(get_total_escrowed(&env, &token), 99);
+
+        // Never goes below zero
+        sub_total_escrowed(&env, &token, 1000);
+        assert_eq!(get_total_escrowed(&env, &token), 0);
+    });
+}
@@ -1,8 +1,16 @@
 #![cfg(test)]
 use crate::{
-    storage::put_escrow, EscrowEntry, EscrowStatus, QuickexContract, QuickexContractClient,
+    events::{CommitmentStatusChangedEvent, DepositCancelledEvent},
+    storage::{put_escrow, DataKey},
+    types::{BasketItem, HashAlgorithm, Payout, TtlConfig, DEFAULT_TTL_CONFIG, NO_EXPIRY},
+    EscrowEntry, EscrowStatus, QuickexContract, QuickexContractClient,
+};
+use soroban_sdk::{
+    testutils::{storage::Persistent as _, Address as _, Events as _},
+    token,
+    xdr::ToXdr,
+    Address, Bytes, BytesN, Env, Event,
 };
-use soroban_sdk::{testutils::Address as _, token, xdr::ToXdr, Address, Bytes, BytesN, Env};
 
 fn setup<'a>() -> (Env, QuickexContractClient<'a>) {
     let env = Env::default();
@@ -27,6 +35,9 @@ fn setup_escrow(
         owner: depositor,
         status: EscrowStatus::Pending,
         created_at: env.ledger().timestamp(),
+        expires_at: NO_EXPIRY,
+        payout: Payout::Deposited,
+        hash_algorithm: HashAlgorithm::Sha256,
     };
 
     env.as_contract(contract_id, || {
@@ -41,19 +52,40 @@ fn create_test_token(env: &Env) -> Address {
         .address()
 }
 
+#[test]
+fn test_error_messages_are_distinct() {
+    use crate::errors::QuickexError;
+
+    let env = Env::default();
+    let mut messages: soroban_sdk::Vec<soroban_sdk::String> = soroban_sdk::Vec::new(&env);
+    let mut code = 1;
+    while let Ok(error) = QuickexError::try_from(soroban_sdk::Error::from_contract_error(code)) {
+        let message = error.to_string(&env);
+        assert!(!message.is_empty());
+        assert!(
+            !messages.contains(&message),
+            "duplicate message for #{code}"
+        );
+        messages.push_back(message);
+        code += 1;
+    }
+    assert_eq!(code - 1, QuickexError::ContractShutdown as u32);
+}
+
 #[test]
 fn test_successful_withdrawal() {
     let (env, client) = setup();
     let token = create_test_token(&env);
     let to = Address::generate(&env);
     let amount: i128 = 1000;
-    let salt = Bytes::from_slice(&env, b"test_salt_123");
+    let salt = Bytes::from_slice(&env, b"test_salt_123456");
 
     let mut data = Bytes::new(&env);
 
     let address_bytes: Bytes = to.clone().to_xdr(&env);
 
     data.append(&address_bytes);
+    data.append(&token.clone().to_xdr(&env));
     data.append(&Bytes::from_slice(&env, &amount.to_be_bytes()));
     data.append(&salt);
 
@@ -76,11 +108,12 @@ fn test_double_withdrawal_fails() {
     let token = create_test_token(&env);
     let to = Address::generate(&env);
     let amount: i128 = 1000;
-    let salt = Bytes::from_slice(&env, b"test_salt_456");
+    let salt = Bytes::from_slice(&env, b"test_salt_456789");
 
     let mut data = Bytes::new(&env);
     let address_bytes: Bytes = to.clone().to_xdr(&env);
     data.append(&address_bytes);
+    data.append(&token.clone().to_xdr(&env));
     data.append(&Bytes::from_slice(&env, &amount.to_be_bytes()));
     data.append(&salt);
     let commitment: BytesN<32> = env.crypto().sha256(&data).into();
@@ -94,7 +127,7 @@ fn test_double_withdrawal_fails() {
 
     let first_result = client.try_withdraw(&token, &amount, &commitment, &to, &salt);
     assert!(first_result.is_ok());
-    assert_eq!(first_result.unwrap(), Ok(true));
+    assert_eq!(first_result.unwrap().unwrap().status, EscrowStatus::Spent);
     let _ = client.withdraw(&token, &amount, &commitment, &to, &salt);
 }
 
@@ -105,12 +138,13 @@ fn test_invalid_salt_fails() {
     let token = create_test_token(&env);
     let to = Address::generate(&env);
     let amount: i128 = 1000;
-    let correct_salt = Bytes::from_slice(&env, b"correct_salt");
-    let wrong_salt = Bytes::from_slice(&env, b"wrong_salt");
+    let correct_salt = Bytes::from_slice(&env, b"correct_salt_abcd");
+    let wrong_salt = Bytes::from_slice(&env, b"wrong_salt_abcdef");
 
     let mut data = Bytes::new(&env);
     let address_bytes: Bytes = to.clone().to_xdr(&env);
     data.append(&address_bytes);
+    data.append(&token.clone().to_xdr(&env));
     data.append(&Bytes::from_slice(&env, &amount.to_be_bytes()));
     data.append(&correct_salt);
     let commitment: BytesN<32> = env.crypto().sha256(&data).into();
@@ -129,11 +163,12 @@ fn test_invalid_amount_fails() {
     let to = Address::generate(&env);
     let correct_amount: i128 = 1000;
     let wrong_amount: i128 = 500;
-    let salt = Bytes::from_slice(&env, b"test_salt_789");
+    let salt = Bytes::from_slice(&env, b"test_salt_789012");
 
     let mut data = Bytes::new(&env);
     let address_bytes: Bytes = to.clone().to_xdr(&env);
     data.append(&address_bytes);
+    data.append(&token.clone().to_xdr(&env));
     data.append(&Bytes::from_slice(&env, &correct_amount.to_be_bytes()));
     data.append(&salt);
     let commitment: BytesN<32> = env.crypto().sha256(&data).into();
@@ -158,11 +193,12 @@ fn test_zero_amount_fails() {
     let token = create_test_token(&env);
     let to = Address::generate(&env);
     let amount: i128 = 0;
-    let salt = Bytes::from_slice(&env, b"test_salt");
+    let salt = Bytes::from_slice(&env, b"test_salt_000001");
 
     let mut data = Bytes::new(&env);
     let address_bytes: Bytes = to.clone().to_xdr(&env);
     data.append(&address_bytes);
+    data.append(&token.clone().to_xdr(&env));
     data.append(&Bytes::from_slice(&env, &amount.to_be_bytes()));
     data.append(&salt);
     let commitment: BytesN<32> = env.crypto().sha256(&data).into();
@@ -179,11 +215,12 @@ fn test_negative_amount_fails() {
     let token = create_test_token(&env);
     let to = Address::generate(&env);
     let amount: i128 = -100;
-    let salt = Bytes::from_slice(&env, b"test_salt");
+    let salt = Bytes::from_slice(&env, b"test_salt_000001");
 
     let mut data = Bytes::new(&env);
     let address_bytes: Bytes = to.clone().to_xdr(&env);
     data.append(&address_bytes);
+    data.append(&token.clone().to_xdr(&env));
     data.append(&Bytes::from_slice(&env, &amount.to_be_bytes()));
     data.append(&salt);
     let commitment: BytesN<32> = env.crypto().sha256(&data).into();
@@ -200,11 +237,12 @@ fn test_nonexistent_commitment_fails() {
     let token = create_test_token(&env);
     let to = Address::generate(&env);
     let amount: i128 = 1000;
-    let salt = Bytes::from_slice(&env, b"nonexistent");
+    let salt = Bytes::from_slice(&env, b"nonexistent_salt");
 
     let mut data = Bytes::new(&env);
     let address_bytes: Bytes = to.clone().to_xdr(&env);
     data.append(&address_bytes);
+    data.append(&token.clone().to_xdr(&env));
     data.append(&Bytes::from_slice(&env, &amount.to_be_bytes()));
     data.append(&salt);
     let commitment: BytesN<32> = env.crypto().sha256(&data).into();
@@ -230,34 +268,336 @@ fn test_set_and_get_privacy() {
     assert!(!client.get_privacy(&account));
 }
 
+#[test]
+fn test_default_privacy_applies_to_unset_accounts() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let account = Address::generate(&env);
+    client.initialize(&admin, &None);
+
+    let result = client.try_set_default_privacy(&account, &true);
+    assert_eq!(result, Err(Ok(QuickexError::Unauthorized)));
+    assert!(!client.get_default_privacy());
+
+    client.set_default_privacy(&admin, &true);
+    assert!(client.get_default_privacy());
+    assert!(client.get_privacy(&account));
+
+    // An explicit choice overrides the default
+    client.set_privacy(&account, &false);
+    assert!(!client.get_privacy(&account));
+    assert!(client.get_privacy(&Address::generate(&env)));
+}
+
+#[test]
+fn test_weak_salts_are_rejected() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let owner = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &1000);
+
+    let empty = Bytes::new(&env);
+    let short = Bytes::from_slice(&env, b"abcd");
+    let strong = Bytes::from_array(&env, &[42u8; 32]);
+
+    for salt in [&empty, &short] {
+        let result = client.try_create_amount_commitment(&owner, &token, &1000, salt);
+        assert_eq!(result, Err(Ok(QuickexError::WeakSalt)));
+        let result = client.try_deposit(&token, &1000, &owner, salt);
+        assert_eq!(result, Err(Ok(QuickexError::WeakSalt)));
+    }
+
+    let commitment = client.create_amount_commitment(&owner, &token, &1000, &strong);
+    assert_eq!(client.deposit(&token, &1000, &owner, &strong), commitment);
+}
+
 #[test]
 fn test_commitment_cycle() {
     let (env, client) = setup();
+    let token = Address::generate(&env);
     let owner = Address::generate(&env);
     let amount = 1_000_000i128;
     let mut salt = Bytes::new(&env);
-    salt.append(&Bytes::from_slice(&env, b"random_salt"));
+    salt.append(&Bytes::from_slice(&env, b"random_salt_value"));
 
     // Create commitment
-    let commitment = client.create_amount_commitment(&owner, &amount, &salt);
+    let commitment = client.create_amount_commitment(&owner, &token, &amount, &salt);
 
     // Verify correct commitment
-    let is_valid = client.verify_amount_commitment(&commitment, &owner, &amount, &salt);
+    let is_valid = client.verify_amount_commitment(&commitment, &owner, &token, &amount, &salt);
     assert!(is_valid);
 
     // Verify incorrect amount
     let is_valid_bad_amount =
-        client.verify_amount_commitment(&commitment, &owner, &2_000_000i128, &salt);
+        client.verify_amount_commitment(&commitment, &owner, &token, &2_000_000i128, &salt);
     assert!(!is_valid_bad_amount);
 
     // Verify incorrect salt
     let mut bad_salt = Bytes::new(&env);
-    bad_salt.append(&Bytes::from_slice(&env, b"wrong_salt"));
+    bad_salt.append(&Bytes::from_slice(&env, b"wrong_salt_abcdef"));
     let is_valid_bad_salt =
-        client.verify_amount_commitment(&commitment, &owner, &amount, &bad_salt);
+        client.verify_amount_commitment(&commitment, &owner, &token, &amount, &bad_salt);
     assert!(!is_valid_bad_salt);
 }
 
+#[test]
+fn test_commitment_is_bound_to_token() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let token_a = create_test_token(&env);
+    let token_b = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    let to = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"token_bound_salt");
+    token::StellarAssetClient::new(&env, &token_b).mint(&depositor, &1000);
+
+    let commitment = client.create_amount_commitment(&to, &token_a, &1000, &salt);
+    assert!(client.verify_amount_commitment(&commitment, &to, &token_a, &1000, &salt));
+    assert!(!client.verify_amount_commitment(&commitment, &to, &token_b, &1000, &salt));
+    assert_ne!(
+        commitment,
+        client.create_amount_commitment(&to, &token_b, &1000, &salt)
+    );
+
+    // A commitment meant for token A, funded with token B, cannot be withdrawn as either
+    client.deposit_with_commitment(&depositor, &token_b, &1000, &commitment);
+    let result = client.try_withdraw(&token_b, &1000, &commitment, &to, &salt);
+    assert_eq!(result, Err(Ok(QuickexError::CommitmentNotFound)));
+    let result = client.try_withdraw(&token_a, &1000, &commitment, &to, &salt);
+    assert_eq!(result, Err(Ok(QuickexError::InvalidCommitment)));
+}
+
+#[test]
+fn test_build_commitment_preimage_matches_commitment() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let token = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let amount = 1_000_000i128;
+    let salt = Bytes::from_slice(&env, b"preimage_salt_01");
+
+    let preimage = client.build_commitment_preimage(&owner, &token, &amount, &salt);
+    let hash: BytesN<32> = env.crypto().sha256(&preimage).into();
+    assert_eq!(
+        hash,
+        client.create_amount_commitment(&owner, &token, &amount, &salt)
+    );
+
+    // owner XDR || token XDR || 16-byte big-endian amount || salt
+    let mut expected = owner.clone().to_xdr(&env);
+    expected.append(&token.clone().to_xdr(&env));
+    expected.append(&Bytes::from_slice(&env, &amount.to_be_bytes()));
+    expected.append(&salt);
+    assert_eq!(preimage, expected);
+
+    let result = client.try_build_commitment_preimage(&owner, &token, &-1, &salt);
+    assert_eq!(result, Err(Ok(QuickexError::InvalidAmount)));
+}
+
+#[test]
+fn test_prepare_deposit_returns_matching_hash_and_preimage() {
+    let (env, client) = setup();
+    let token = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let amount = 42_000i128;
+    let salt = Bytes::from_slice(&env, b"prepare_salt_001");
+
+    let (commitment, preimage) = client.prepare_deposit(&owner, &token, &amount, &salt);
+    assert!(client.verify_amount_commitment(&commitment, &owner, &token, &amount, &salt));
+    assert_eq!(
+        preimage,
+        client.build_commitment_preimage(&owner, &token, &amount, &salt)
+    );
+    let hash: BytesN<32> = env.crypto().sha256(&preimage).into();
+    assert_eq!(hash, commitment);
+}
+
+#[test]
+fn test_hash_algorithm_selects_commitment_hash() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &None);
+    let token = create_test_token(&env);
+    let owner = Address::generate(&env);
+    let amount = 1000i128;
+    let salt = Bytes::from_slice(&env, b"hash_algo_salt01");
+    let preimage = client.build_commitment_preimage(&owner, &token, &amount, &salt);
+
+    assert_eq!(client.get_hash_algorithm(), HashAlgorithm::Sha256);
+    let sha = client.create_amount_commitment(&owner, &token, &amount, &salt);
+    let expected: BytesN<32> = env.crypto().sha256(&preimage).into();
+    assert_eq!(sha, expected);
+    assert!(client.verify_amount_commitment(&sha, &owner, &token, &amount, &salt));
+
+    let result = client.try_set_hash_algorithm(&owner, &HashAlgorithm::Keccak256);
+    assert_eq!(result, Err(Ok(QuickexError::Unauthorized)));
+
+    client.set_hash_algorithm(&admin, &HashAlgorithm::Keccak256);
+    assert_eq!(client.get_hash_algorithm(), HashAlgorithm::Keccak256);
+    let keccak = client.create_amount_commitment(&owner, &token, &amount, &salt);
+    let expected: BytesN<32> = env.crypto().keccak256(&preimage).into();
+    assert_eq!(keccak, expected);
+    assert_eq!(
+        client.prepare_deposit(&owner, &token, &amount, &salt).0,
+        keccak
+    );
+    assert!(client.verify_amount_commitment(&keccak, &owner, &token, &amount, &salt));
+
+    // Commitments do not cross-validate between algorithms
+    assert_ne!(sha, keccak);
+    assert!(!client.verify_amount_commitment(&sha, &owner, &token, &amount, &salt));
+    client.set_hash_algorithm(&admin, &HashAlgorithm::Sha256);
+    assert!(!client.verify_amount_commitment(&keccak, &owner, &token, &amount, &salt));
+}
+
+#[test]
+fn test_withdraw_under_keccak_commitments() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &None);
+    client.set_hash_algorithm(&admin, &HashAlgorithm::Keccak256);
+
+    let token = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &1000);
+
+    let salt = Bytes::from_slice(&env, b"keccak_withdraw1");
+    let commitment = client.create_amount_commitment(&recipient, &token, &1000, &salt);
+    client.deposit_with_commitment(&depositor, &token, &1000, &commitment);
+    client.withdraw(&token, &1000, &commitment, &recipient, &salt);
+
+    assert_eq!(token::Client::new(&env, &token).balance(&recipient), 1000);
+}
+
+#[test]
+fn test_hash_algorithm_change_keeps_pending_escrows_withdrawable() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &None);
+    let token = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &2000);
+
+    let sha_salt = Bytes::from_slice(&env, b"algo_change_sha1");
+    let sha = client.create_amount_commitment(&recipient, &token, &1000, &sha_salt);
+    client.deposit_with_commitment(&depositor, &token, &1000, &sha);
+
+    client.set_hash_algorithm(&admin, &HashAlgorithm::Keccak256);
+    let keccak_salt = Bytes::from_slice(&env, b"algo_change_kec1");
+    let keccak = client.create_amount_commitment(&recipient, &token, &1000, &keccak_salt);
+    client.deposit_with_commitment(&depositor, &token, &1000, &keccak);
+    let recorded = |commitment: &BytesN<32>| {
+        client
+            .get_escrow_details(commitment, &false)
+            .unwrap()
+            .hash_algorithm
+    };
+    assert_eq!(recorded(&sha), HashAlgorithm::Sha256);
+    assert_eq!(recorded(&keccak), HashAlgorithm::Keccak256);
+
+    // The SHA-256 escrow still opens under the algorithm it was deposited with
+    assert!(client.can_withdraw(&recipient, &token, &1000, &sha_salt));
+    assert!(client.verify_proof_view(&token, &1000, &sha_salt, &recipient));
+    assert_eq!(
+        client.verify_and_state(&sha, &recipient, &token, &1000, &sha_salt),
+        Some(EscrowStatus::Pending)
+    );
+    client.withdraw(&token, &1000, &sha, &recipient, &sha_salt);
+
+    // And switching back does not strand the Keccak-256 one
+    client.set_hash_algorithm(&admin, &HashAlgorithm::Sha256);
+    client.withdraw(&token, &1000, &keccak, &recipient, &keccak_salt);
+    assert_eq!(token::Client::new(&env, &token).balance(&recipient), 2000);
+}
+
+#[test]
+fn test_hash_algorithm_applies_to_every_commitment_type() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &None);
+    let token = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    let to = Address::generate(&env);
+    let key = ed25519_dalek::SigningKey::from_bytes(&[9u8; 32]);
+    let pubkey = BytesN::from_array(&env, &key.verifying_key().to_bytes());
+    let recipients = soroban_sdk::vec![&env, to.clone()];
+    let salt = Bytes::from_slice(&env, b"every_type_salt1");
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &2000);
+
+    let sha_bucketed = client.create_bucketed_commitment(&to, &token, &2, &salt);
+    let sha_recipients = client.create_recipients_commitment(&recipients, &token, &500, &salt);
+    let sha_signer = client.create_signer_commitment(&pubkey, &token, &500, &salt);
+
+    client.set_hash_algorithm(&admin, &HashAlgorithm::Keccak256);
+    let bucketed = client.create_bucketed_commitment(&to, &token, &2, &salt);
+    let recipients_commitment =
+        client.create_recipients_commitment(&recipients, &token, &500, &salt);
+    let signer = client.create_signer_commitment(&pubkey, &token, &500, &salt);
+    assert_ne!(bucketed, sha_bucketed);
+    assert_ne!(recipients_commitment, sha_recipients);
+    assert_ne!(signer, sha_signer);
+    assert!(client.verify_bucketed_commitment(&bucketed, &to, &token, &2, &500, &salt));
+    assert!(!client.verify_bucketed_commitment(&sha_bucketed, &to, &token, &2, &500, &salt));
+
+    client.deposit_with_commitment(&depositor, &token, &500, &bucketed);
+    client.deposit_with_commitment(&depositor, &token, &500, &recipients_commitment);
+    client.deposit_with_commitment(&depositor, &token, &500, &signer);
+
+    // Each escrow is withdrawn under the Keccak-256 algorithm it was deposited with
+    client.set_hash_algorithm(&admin, &HashAlgorithm::Sha256);
+    assert_eq!(client.withdraw_bucketed(&to, &token, &2, &salt), 500);
+    client.withdraw_to_allowed(&to, &recipients, &token, &500, &salt);
+    let signature = sign_withdrawal(&env, &client, &key, &signer, &to, 500);
+    client.withdraw_signed(&to, &token, &500, &salt, &pubkey, &signature);
+    assert_eq!(token::Client::new(&env, &token).balance(&to), 1500);
+}
+
+#[test]
+fn test_verify_and_state() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let to = Address::generate(&env);
+    let amount: i128 = 1000;
+    let salt = Bytes::from_slice(&env, b"verify_and_state");
+    let bad_salt = Bytes::from_slice(&env, b"verify_and_wrong");
+
+    let commitment = client.create_amount_commitment(&to, &token, &amount, &salt);
+    assert_eq!(
+        client.verify_and_state(&commitment, &to, &token, &amount, &salt),
+        None
+    );
+
+    setup_withdrawable_escrow(&env, &client, &token, &to, amount, &salt);
+    assert_eq!(
+        client.verify_and_state(&commitment, &to, &token, &amount, &salt),
+        Some(EscrowStatus::Pending)
+    );
+    assert_eq!(
+        client.verify_and_state(&commitment, &to, &token, &amount, &bad_salt),
+        None
+    );
+
+    client.withdraw(&token, &amount, &commitment, &to, &salt);
+    assert_eq!(
+        client.verify_and_state(&commitment, &to, &token, &amount, &salt),
+        Some(EscrowStatus::Spent)
+    );
+    assert_eq!(
+        client.verify_and_state(&commitment, &to, &token, &amount, &bad_salt),
+        None
+    );
+}
+
 #[test]
 fn test_create_escrow() {
     let (env, client) = setup();
@@ -274,6 +614,100 @@ fn test_health_check() {
     assert!(client.health_check());
 }
 
+#[test]
+fn test_get_health() {
+    use crate::types::HealthStatus;
+
+    let (env, client) = setup();
+    assert_eq!(
+        client.get_health(),
+        HealthStatus {
+            is_paused: false,
+            admin_set: false,
+            escrow_counter: 0,
+            version: crate::VERSION,
+        }
+    );
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &None);
+    client.create_escrow(&admin, &admin, &1);
+    client.create_escrow(&admin, &admin, &1);
+
+    assert_eq!(
+        client.get_health(),
+        HealthStatus {
+            is_paused: false,
+            admin_set: true,
+            escrow_counter: 2,
+            version: crate::VERSION,
+        }
+    );
+
+    client.set_paused(&admin, &true, &None);
+    assert!(client.get_health().is_paused);
+}
+
+#[test]
+fn test_get_config_reflects_admin_settings() {
+    use crate::types::{ContractConfig, EventVerbosity, DEFAULT_AMOUNT_BOUNDS};
+
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let collector = Address::generate(&env);
+    let config = TtlConfig {
+        threshold: 10_000,
+        extend_to: 50_000,
+    };
+
+    let defaults = client.get_config();
+    assert_eq!(defaults.admin, None);
+    assert_eq!(defaults.fee_bps, 0);
+    assert_eq!(defaults.withdraw_cooldown, 0);
+
+    client.initialize(&admin, &Some(config));
+    client.set_fee(&admin, &150, &collector);
+    client.set_deposit_fee(&admin, &50);
+    client.set_default_expiry(&admin, &3_600);
+    client.set_reclaim_window(&admin, &600);
+    client.set_withdraw_cooldown(&admin, &12);
+    client.set_default_privacy(&admin, &true);
+    client.set_max_basket_size(&admin, &4);
+    client.set_max_pending_per_owner(&admin, &8);
+    client.set_hash_algorithm(&admin, &HashAlgorithm::Keccak256);
+    client.set_event_verbosity(&admin, &EventVerbosity::Normal);
+    client.set_paused(&admin, &true, &None);
+
+    assert_eq!(
+        client.get_config(),
+        ContractConfig {
+            admin: Some(admin),
+            paused: true,
+            shutdown: false,
+            fee_bps: 150,
+            deposit_fee_bps: 50,
+            fee_collector: Some(collector),
+            default_expiry: 3_600,
+            reclaim_window: 600,
+            withdraw_cooldown: 12,
+            default_privacy: true,
+            default_amount_bounds: DEFAULT_AMOUNT_BOUNDS,
+            max_basket_size: 4,
+            max_pending_per_owner: 8,
+            hash_algorithm: HashAlgorithm::Keccak256,
+            event_verbosity: EventVerbosity::Normal,
+            ttl_config: config,
+        }
+    );
+}
+
+#[test]
+fn test_get_version() {
+    let (_, client) = setup();
+    assert_eq!(client.get_version(), crate::VERSION);
+    assert_eq!(client.get_version(), 1);
+}
+
 #[test]
 fn test_deposit() {
     let env = Env::default();
@@ -306,7 +740,7 @@ fn test_initialize_admin() {
     let admin = Address::generate(&env);
 
     // Initialize admin
-    client.initialize(&admin);
+    client.initialize(&admin, &None);
 
     // Verify admin is set
     assert_eq!(client.get_admin(), Some(admin.clone()));
@@ -323,130 +757,618 @@ fn test_initialize_twice_fails() {
     let admin2 = Address::generate(&env);
 
     // Initialize admin
-    client.initialize(&admin1);
+    client.initialize(&admin1, &None);
 
     // Try to initialize again - should fail
-    client.initialize(&admin2);
+    client.initialize(&admin2, &None);
 }
 
 #[test]
-fn test_set_paused_by_admin() {
+fn test_initialize_with_custom_ttl_config() {
     let (env, client) = setup();
     let admin = Address::generate(&env);
+    let config = TtlConfig {
+        threshold: 10_000,
+        extend_to: 50_000,
+    };
 
-    // Initialize admin
-    client.initialize(&admin);
+    client.initialize(&admin, &Some(config));
+    assert_eq!(client.get_ttl_config(), config);
 
-    // Admin pauses the contract
-    client.set_paused(&admin, &true);
-    assert!(client.is_paused());
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    let owner = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &100);
+    let commitment = BytesN::from_array(&env, &[7; 32]);
+    client.deposit_with_commitment(&owner, &token, &100, &commitment);
 
-    // Admin unpauses the contract
-    client.set_paused(&admin, &false);
-    assert!(!client.is_paused());
+    let ttl = env.as_contract(&client.address, || {
+        let key = DataKey::Escrow(commitment.clone().into());
+        env.storage().persistent().get_ttl(&key)
+    });
+    assert_eq!(ttl, config.extend_to);
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #2)")]
-fn test_set_paused_by_non_admin_fails() {
+fn test_initialize_defaults_ttl_config() {
+    let (env, client) = setup();
+    client.initialize(&Address::generate(&env), &None);
+    assert_eq!(client.get_ttl_config(), DEFAULT_TTL_CONFIG);
+}
+
+#[test]
+fn test_reinitialize_blocked_after_admin_entry_archived() {
+    use crate::errors::QuickexError;
+
     let (env, client) = setup();
     let admin = Address::generate(&env);
-    let non_admin = Address::generate(&env);
+    client.initialize(&admin, &None);
 
-    // Initialize admin
-    client.initialize(&admin);
+    // Simulate the persistent admin entry being archived and no longer readable
+    env.as_contract(&client.address, || {
+        env.storage().persistent().remove(&DataKey::Admin);
+    });
 
-    // Non-admin tries to pause - should fail
-    client.set_paused(&non_admin, &true);
+    let attacker = Address::generate(&env);
+    let result = client.try_initialize(&attacker, &None);
+    assert_eq!(result, Err(Ok(QuickexError::AlreadyInitialized)));
+    assert_eq!(
+        client.try_set_paused(&attacker, &true, &None),
+        Err(Ok(QuickexError::Unauthorized))
+    );
 }
 
 #[test]
-fn test_set_admin() {
+fn test_admin_operations_bump_admin_ttl() {
+    use soroban_sdk::testutils::Ledger as _;
+
     let (env, client) = setup();
     let admin = Address::generate(&env);
-    let new_admin = Address::generate(&env);
+    let config = TtlConfig {
+        threshold: 10_000,
+        extend_to: 50_000,
+    };
+    client.initialize(&admin, &Some(config));
 
-    // Initialize admin
-    client.initialize(&admin);
+    let admin_ttl = || {
+        env.as_contract(&client.address, || {
+            env.storage().persistent().get_ttl(&DataKey::Admin)
+        })
+    };
+    assert_eq!(admin_ttl(), config.extend_to);
 
-    // Transfer admin rights
-    client.set_admin(&admin, &new_admin);
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + 45_000);
+    assert_eq!(admin_ttl(), config.extend_to - 45_000);
 
-    // Verify new admin is set
-    assert_eq!(client.get_admin(), Some(new_admin.clone()));
+    client.set_paused(&admin, &true, &None);
+    assert_eq!(admin_ttl(), config.extend_to);
+}
 
-    // Verify new admin can pause
-    client.set_paused(&new_admin, &true);
-    assert!(client.is_paused());
+#[test]
+#[should_panic(expected = "Error(Contract, #20)")]
+fn test_initialize_rejects_threshold_above_extend_to() {
+    let (env, client) = setup();
+    let config = TtlConfig {
+        threshold: 2_000,
+        extend_to: 1_000,
+    };
+    client.initialize(&Address::generate(&env), &Some(config));
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #2)")]
-fn test_set_admin_by_non_admin_fails() {
+fn test_set_paused_by_admin() {
     let (env, client) = setup();
     let admin = Address::generate(&env);
-    let non_admin = Address::generate(&env);
-    let new_admin = Address::generate(&env);
 
     // Initialize admin
-    client.initialize(&admin);
+    client.initialize(&admin, &None);
 
-    // Non-admin tries to transfer admin rights - should fail
-    client.set_admin(&non_admin, &new_admin);
+    // Admin pauses the contract
+    client.set_paused(&admin, &true, &None);
+    assert!(client.is_paused());
+
+    // Admin unpauses the contract
+    client.set_paused(&admin, &false, &None);
+    assert!(!client.is_paused());
 }
 
 #[test]
-#[should_panic(expected = "Error(Contract, #2)")]
-fn test_old_admin_cannot_pause_after_transfer() {
+fn test_pause_reason_set_and_cleared() {
+    use crate::events::ContractPausedEvent;
+    use soroban_sdk::String;
+
     let (env, client) = setup();
     let admin = Address::generate(&env);
-    let new_admin = Address::generate(&env);
+    client.initialize(&admin, &None);
+    assert_eq!(client.get_pause_reason(), None);
+
+    let reason = String::from_str(&env, "incident response");
+    client.set_paused(&admin, &true, &Some(reason.clone()));
+    let paused = ContractPausedEvent {
+        paused: true,
+        reason: Some(reason.clone()),
+        timestamp: env.ledger().timestamp(),
+    };
+    assert!(has_event(&env, &client.address, &paused));
+    assert_eq!(client.get_pause_reason(), Some(reason.clone()));
+
+    // Unpausing clears the reason even if one is passed
+    client.set_paused(&admin, &false, &Some(reason));
+    let unpaused = ContractPausedEvent {
+        paused: false,
+        reason: None,
+        timestamp: env.ledger().timestamp(),
+    };
+    assert!(has_event(&env, &client.address, &unpaused));
+    assert_eq!(client.get_pause_reason(), None);
+}
 
-    // Initialize admin
-    client.initialize(&admin);
+#[test]
+fn test_paused_since_recorded_and_cleared() {
+    use soroban_sdk::testutils::Ledger as _;
 
-    // Transfer admin rights
-    client.set_admin(&admin, &new_admin);
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &None);
+    assert_eq!(client.get_paused_since(), None);
 
-    // Old admin tries to pause - should fail
-    client.set_paused(&admin, &true);
+    env.ledger().set_timestamp(5_000);
+    client.set_paused(&admin, &true, &None);
+    assert_eq!(client.get_paused_since(), Some(5_000));
+
+    // Pausing again keeps the original start time
+    env.ledger().set_timestamp(6_000);
+    client.set_paused(&admin, &true, &None);
+    assert_eq!(client.get_paused_since(), Some(5_000));
+
+    client.set_paused(&admin, &false, &None);
+    assert_eq!(client.get_paused_since(), None);
 }
 
 #[test]
-fn test_get_commitment_state_pending() {
+fn test_pause_blocks_every_mutating_entrypoint() {
+    use crate::errors::QuickexError;
+
     let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
     let token = create_test_token(&env);
-    let owner = Address::generate(&env);
-    let amount: i128 = 1000;
-    let salt = Bytes::from_slice(&env, b"test_salt");
+    token::StellarAssetClient::new(&env, &token).mint(&user, &10_000);
+    client.initialize(&admin, &None);
 
-    let mut data = Bytes::new(&env);
-    let address_bytes: Bytes = owner.clone().to_xdr(&env);
-    data.append(&address_bytes);
-    data.append(&Bytes::from_slice(&env, &amount.to_be_bytes()));
-    data.append(&salt);
-    let commitment: BytesN<32> = env.crypto().sha256(&data).into();
+    let salt = Bytes::from_slice(&env, b"paused_policy_01");
+    let commitment = client.create_amount_commitment(&user, &token, &1000, &salt);
+    client.deposit_with_commitment(&user, &token, &1000, &commitment);
+    client.set_paused(&admin, &true, &None);
 
-    setup_escrow(&env, &client.address, &token, amount, commitment.clone());
+    let other = BytesN::from_array(&env, &[88; 32]);
+    let commitments = soroban_sdk::vec![&env, commitment.clone()];
+    let items = soroban_sdk::vec![
+        &env,
+        BasketItem {
+            token: token.clone(),
+            amount: 1000,
+            commitment: other.clone(),
+        },
+    ];
+    let memo = Bytes::from_slice(&env, b"memo");
+    let paused = Some(Ok(QuickexError::ContractPaused));
+
+    assert_eq!(
+        client.try_deposit(&token, &1000, &user, &salt).err(),
+        paused
+    );
+    assert_eq!(
+        client
+            .try_deposit_with_commitment(&user, &token, &1000, &other)
+            .err(),
+        paused
+    );
+    assert_eq!(
+        client.try_deposit_native(&user, &1000, &other).err(),
+        paused
+    );
+    assert_eq!(
+        client
+            .try_deposit_with_memo(&user, &token, &1000, &other, &memo)
+            .err(),
+        paused
+    );
+    assert_eq!(
+        client
+            .try_deposit_with_expiry(&user, &token, &1000, &other, &10)
+            .err(),
+        paused
+    );
+    assert_eq!(
+        client
+            .try_deposit_with_swap(&user, &token, &1000, &other, &token)
+            .err(),
+        paused
+    );
+    assert_eq!(
+        client
+            .try_deposit_with_deadline(&user, &token, &1000, &other, &u64::MAX)
+            .err(),
+        paused
+    );
+    assert_eq!(client.try_deposit_basket(&user, &items).err(), paused);
+    let shares = soroban_sdk::vec![
+        &env,
+        crate::types::SplitShare {
+            commitment: other.clone(),
+            amount: 1000,
+        },
+    ];
+    assert_eq!(
+        client
+            .try_deposit_split(&user, &token, &1000, &shares)
+            .err(),
+        paused
+    );
+    assert_eq!(
+        client
+            .try_withdraw(&token, &1000, &commitment, &user, &salt)
+            .err(),
+        paused
+    );
+    assert_eq!(
+        client.try_withdraw_bucketed(&user, &token, &3, &salt).err(),
+        paused
+    );
+    let recipients = soroban_sdk::vec![&env, user.clone()];
+    assert_eq!(
+        client
+            .try_withdraw_to_allowed(&user, &recipients, &token, &1000, &salt)
+            .err(),
+        paused
+    );
+    let pubkey = BytesN::from_array(&env, &[7; 32]);
+    let signature = BytesN::from_array(&env, &[0; 64]);
+    assert_eq!(
+        client
+            .try_withdraw_signed(&user, &token, &1000, &salt, &pubkey, &signature)
+            .err(),
+        paused
+    );
+    assert_eq!(
+        client
+            .try_withdraw_private(&user, &admin, &token, &1000, &salt)
+            .err(),
+        paused
+    );
+    assert_eq!(
+        client
+            .try_withdraw_relayed(&admin, &user, &token, &1000, &salt, &0)
+            .err(),
+        paused
+    );
+    assert_eq!(client.try_cancel_deposit(&user, &commitment).err(), paused);
+    assert_eq!(
+        client.try_top_up(&user, &commitment, &1000, &salt).err(),
+        paused
+    );
+    assert_eq!(
+        client
+            .try_transfer_escrow(&user, &commitment, &user, &commitment)
+            .err(),
+        paused
+    );
+    assert_eq!(
+        client.try_update_memo(&user, &commitment, &memo).err(),
+        paused
+    );
+    assert_eq!(
+        client.try_set_view_key(&user, &commitment, &other).err(),
+        paused
+    );
+    assert_eq!(client.try_refund_expired(&user, &commitment).err(), paused);
+    assert_eq!(
+        client.try_withdraw_all_expired(&user, &commitments).err(),
+        paused
+    );
+    assert_eq!(client.try_enable_privacy(&user, &1).err(), paused);
+    assert_eq!(client.try_set_privacy(&user, &true).err(), paused);
+    assert_eq!(client.try_create_escrow(&user, &admin, &1000).err(), paused);
+    assert_eq!(
+        client.try_can_withdraw(&user, &token, &1000, &salt).err(),
+        paused
+    );
 
-    let state = client.get_commitment_state(&commitment);
-    assert_eq!(state, Some(EscrowStatus::Pending));
+    // Unpausing restores normal operation
+    client.set_paused(&admin, &false, &None);
+    client.withdraw(&token, &1000, &commitment, &user, &salt);
 }
 
 #[test]
-fn test_get_commitment_state_spent() {
+fn test_pause_leaves_getters_and_admin_operations_callable() {
     let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
     let token = create_test_token(&env);
-    let owner = Address::generate(&env);
-    let amount: i128 = 1000;
-    let salt = Bytes::from_slice(&env, b"test_salt_spent");
+    token::StellarAssetClient::new(&env, &token).mint(&user, &1000);
+    client.initialize(&admin, &None);
 
-    let mut data = Bytes::new(&env);
-    let address_bytes: Bytes = owner.clone().to_xdr(&env);
-    data.append(&address_bytes);
-    data.append(&Bytes::from_slice(&env, &amount.to_be_bytes()));
-    data.append(&salt);
-    let commitment: BytesN<32> = env.crypto().sha256(&data).into();
+    let salt = Bytes::from_slice(&env, b"paused_getters01");
+    let commitment = client.create_amount_commitment(&user, &token, &1000, &salt);
+    client.deposit_with_commitment(&user, &token, &1000, &commitment);
+    let reason = soroban_sdk::String::from_str(&env, "maintenance");
+    client.set_paused(&admin, &true, &Some(reason.clone()));
+
+    assert!(client.is_paused());
+    assert_eq!(client.get_pause_reason(), Some(reason));
+    assert!(client.get_paused_since().is_some());
+    assert_eq!(client.get_admin(), Some(admin.clone()));
+    assert_eq!(
+        client.get_commitment_state(&commitment),
+        Some(EscrowStatus::Pending)
+    );
+    assert_eq!(
+        client
+            .get_escrow_details(&commitment, &false)
+            .unwrap()
+            .amount,
+        1000
+    );
+    assert!(client.verify_amount_commitment(&commitment, &user, &token, &1000, &salt));
+    assert!(client.verify_proof_view(&token, &1000, &salt, &user));
+    assert_eq!(
+        client.prepare_deposit(&user, &token, &1000, &salt).0,
+        commitment
+    );
+    assert!(!client.get_privacy(&user));
+    assert_eq!(client.privacy_status(&user), None);
+    assert_eq!(client.get_contract_stats().pending_count, 1);
+    assert_eq!(client.get_status_counts().pending, 1);
+    assert_eq!(client.get_total_escrowed(&token), 1000);
+    assert_eq!(client.get_fee_bps(), 0);
+    assert!(client.health_check());
+
+    // Admin operations are not pause-blocked, so incidents can be handled while paused
+    client.block_address(&admin, &user);
+    client.unblock_address(&admin, &user);
+    client.set_paused(&admin, &false, &None);
+    assert!(!client.is_paused());
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_set_paused_by_non_admin_fails() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let non_admin = Address::generate(&env);
+
+    // Initialize admin
+    client.initialize(&admin, &None);
+
+    // Non-admin tries to pause - should fail
+    client.set_paused(&non_admin, &true, &None);
+}
+
+#[test]
+fn test_shutdown_blocks_deposits_but_not_exits() {
+    use crate::errors::QuickexError;
+    use crate::events::ShutdownEvent;
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let to = Address::generate(&env);
+    client.initialize(&admin, &None);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &3000);
+    let salt = Bytes::from_slice(&env, b"shutdown_salt_01");
+    let commitment = client.create_amount_commitment(&to, &token, &1000, &salt);
+    client.deposit_with_commitment(&depositor, &token, &1000, &commitment);
+    let refundable = BytesN::from_array(&env, &[61; 32]);
+    client.deposit_with_commitment(&depositor, &token, &1000, &refundable);
+
+    assert_eq!(
+        client.try_shutdown(&depositor),
+        Err(Ok(QuickexError::Unauthorized))
+    );
+    client.shutdown(&admin);
+    assert!(has_event(
+        &env,
+        &client.address,
+        &ShutdownEvent {
+            admin: admin.clone(),
+            timestamp: env.ledger().timestamp(),
+        }
+    ));
+    assert!(client.is_shutdown());
+    assert!(client.get_config().shutdown);
+
+    let shutdown = Some(Ok(QuickexError::ContractShutdown));
+    let late = BytesN::from_array(&env, &[62; 32]);
+    assert_eq!(
+        client
+            .try_deposit_with_commitment(&depositor, &token, &1000, &late)
+            .err(),
+        shutdown
+    );
+    let late_salt = Bytes::from_slice(&env, b"shutdown_salt_02");
+    assert_eq!(
+        client.try_deposit(&token, &1000, &to, &late_salt).err(),
+        shutdown
+    );
+    assert_eq!(
+        client
+            .try_top_up(&depositor, &refundable, &100, &late_salt)
+            .err(),
+        shutdown
+    );
+
+    // Pausing and unpausing does not lift the shutdown, and it cannot be repeated
+    client.set_paused(&admin, &true, &None);
+    client.set_paused(&admin, &false, &None);
+    assert_eq!(
+        client
+            .try_deposit_with_commitment(&depositor, &token, &1000, &late)
+            .err(),
+        shutdown
+    );
+    assert_eq!(
+        client.try_shutdown(&admin),
+        Err(Ok(QuickexError::ContractShutdown))
+    );
+
+    // Users can still exit
+    client.withdraw(&token, &1000, &commitment, &to, &salt);
+    client.cancel_deposit(&depositor, &refundable);
+    let balance = token::Client::new(&env, &token);
+    assert_eq!(balance.balance(&to), 1000);
+    assert_eq!(balance.balance(&depositor), 2000);
+}
+
+#[test]
+fn test_set_admin() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let new_admin = Address::generate(&env);
+
+    // Initialize admin
+    client.initialize(&admin, &None);
+
+    // Transfer admin rights
+    client.set_admin(&admin, &new_admin);
+
+    // Verify new admin is set
+    assert_eq!(client.get_admin(), Some(new_admin.clone()));
+
+    // Verify new admin can pause
+    client.set_paused(&new_admin, &true, &None);
+    assert!(client.is_paused());
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_set_admin_by_non_admin_fails() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let non_admin = Address::generate(&env);
+    let new_admin = Address::generate(&env);
+
+    // Initialize admin
+    client.initialize(&admin, &None);
+
+    // Non-admin tries to transfer admin rights - should fail
+    client.set_admin(&non_admin, &new_admin);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_old_admin_cannot_pause_after_transfer() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let new_admin = Address::generate(&env);
+
+    // Initialize admin
+    client.initialize(&admin, &None);
+
+    // Transfer admin rights
+    client.set_admin(&admin, &new_admin);
+
+    // Old admin tries to pause - should fail
+    client.set_paused(&admin, &true, &None);
+}
+
+#[test]
+fn test_roles_are_enumerable() {
+    use crate::errors::QuickexError;
+    use crate::types::Role;
+
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let operator = Address::generate(&env);
+    let pauser = Address::generate(&env);
+    client.initialize(&admin, &None);
+
+    client.grant_role(&admin, &operator, &Role::Operator);
+    client.grant_role(&admin, &pauser, &Role::Pauser);
+    assert_eq!(client.get_role(&admin), Some(Role::Admin));
+    assert_eq!(client.get_role(&operator), Some(Role::Operator));
+    assert_eq!(client.get_role(&pauser), Some(Role::Pauser));
+    assert_eq!(client.get_role(&Address::generate(&env)), None);
+    assert_eq!(
+        client.get_all_admins(),
+        soroban_sdk::vec![&env, admin.clone(), operator.clone(), pauser.clone()]
+    );
+
+    // Re-granting replaces the role without duplicating the account
+    client.grant_role(&admin, &operator, &Role::Pauser);
+    assert_eq!(client.get_role(&operator), Some(Role::Pauser));
+    assert_eq!(client.get_all_admins().len(), 3);
+
+    client.revoke_role(&admin, &operator);
+    assert_eq!(client.get_role(&operator), None);
+    assert_eq!(
+        client.get_all_admins(),
+        soroban_sdk::vec![&env, admin.clone(), pauser.clone()]
+    );
+
+    let result = client.try_grant_role(&admin, &operator, &Role::Admin);
+    assert_eq!(result, Err(Ok(QuickexError::InvalidInput)));
+    let result = client.try_grant_role(&pauser, &operator, &Role::Operator);
+    assert_eq!(result, Err(Ok(QuickexError::Unauthorized)));
+}
+
+#[test]
+fn test_role_members_are_bounded() {
+    use crate::errors::QuickexError;
+    use crate::types::Role;
+
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &None);
+
+    for _ in 0..20 {
+        client.grant_role(&admin, &Address::generate(&env), &Role::Operator);
+    }
+    let result = client.try_grant_role(&admin, &Address::generate(&env), &Role::Operator);
+    assert_eq!(result, Err(Ok(QuickexError::TooManyRoleMembers)));
+    assert_eq!(client.get_all_admins().len(), 21);
+}
+
+#[test]
+fn test_get_commitment_state_pending() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let owner = Address::generate(&env);
+    let amount: i128 = 1000;
+    let salt = Bytes::from_slice(&env, b"test_salt_000001");
+
+    let mut data = Bytes::new(&env);
+    let address_bytes: Bytes = owner.clone().to_xdr(&env);
+    data.append(&address_bytes);
+    data.append(&token.clone().to_xdr(&env));
+    data.append(&Bytes::from_slice(&env, &amount.to_be_bytes()));
+    data.append(&salt);
+    let commitment: BytesN<32> = env.crypto().sha256(&data).into();
+
+    setup_escrow(&env, &client.address, &token, amount, commitment.clone());
+
+    let state = client.get_commitment_state(&commitment);
+    assert_eq!(state, Some(EscrowStatus::Pending));
+}
+
+#[test]
+fn test_get_commitment_state_spent() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let owner = Address::generate(&env);
+    let amount: i128 = 1000;
+    let salt = Bytes::from_slice(&env, b"test_salt_spent1");
+
+    let mut data = Bytes::new(&env);
+    let address_bytes: Bytes = owner.clone().to_xdr(&env);
+    data.append(&address_bytes);
+    data.append(&token.clone().to_xdr(&env));
+    data.append(&Bytes::from_slice(&env, &amount.to_be_bytes()));
+    data.append(&salt);
+    let commitment: BytesN<32> = env.crypto().sha256(&data).into();
 
     // Create entry with Spent status
     let entry = EscrowEntry {
@@ -455,6 +1377,9 @@ fn test_get_commitment_state_spent() {
         owner: owner.clone(),
         status: EscrowStatus::Spent,
         created_at: env.ledger().timestamp(),
+        expires_at: NO_EXPIRY,
+        payout: Payout::Deposited,
+        hash_algorithm: HashAlgorithm::Sha256,
     };
 
     env.as_contract(&client.address, || {
@@ -466,9 +1391,55 @@ fn test_get_commitment_state_spent() {
     assert_eq!(state, Some(EscrowStatus::Spent));
 }
 
+#[test]
+fn test_get_commitment_states_in_order() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let to = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"state_batch_salt");
+    let spent = setup_withdrawable_escrow(&env, &client, &token, &to, 1000, &salt);
+    client.withdraw(&token, &1000, &spent, &to, &salt);
+    let pending = BytesN::from_array(&env, &[50; 32]);
+    setup_escrow(&env, &client.address, &token, 500, pending.clone());
+    let missing = BytesN::from_array(&env, &[51; 32]);
+
+    let states = client.get_commitment_states(&soroban_sdk::vec![
+        &env,
+        pending.clone(),
+        missing.clone(),
+        spent.clone(),
+        pending.clone(),
+    ]);
+    assert_eq!(
+        states,
+        soroban_sdk::vec![
+            &env,
+            Some(EscrowStatus::Pending),
+            None,
+            Some(EscrowStatus::Spent),
+            Some(EscrowStatus::Pending),
+        ]
+    );
+    assert!(client
+        .get_commitment_states(&soroban_sdk::Vec::new(&env))
+        .is_empty());
+
+    let mut too_many = soroban_sdk::Vec::new(&env);
+    for _ in 0..=crate::MAX_BATCH {
+        too_many.push_back(missing.clone());
+    }
+    assert_eq!(
+        client.try_get_commitment_states(&too_many),
+        Err(Ok(QuickexError::BatchTooLarge))
+    );
+}
+
 #[test]
 fn test_get_commitment_state_not_found() {
     let (env, client) = setup();
+    let token = Address::generate(&env);
     let owner = Address::generate(&env);
     let amount: i128 = 1000;
     let salt = Bytes::from_slice(&env, b"nonexistent_salt");
@@ -476,6 +1447,7 @@ fn test_get_commitment_state_not_found() {
     let mut data = Bytes::new(&env);
     let address_bytes: Bytes = owner.clone().to_xdr(&env);
     data.append(&address_bytes);
+    data.append(&token.clone().to_xdr(&env));
     data.append(&Bytes::from_slice(&env, &amount.to_be_bytes()));
     data.append(&salt);
     let commitment: BytesN<32> = env.crypto().sha256(&data).into();
@@ -495,13 +1467,14 @@ fn test_verify_proof_view_valid() {
     let mut data = Bytes::new(&env);
     let address_bytes: Bytes = owner.clone().to_xdr(&env);
     data.append(&address_bytes);
+    data.append(&token.clone().to_xdr(&env));
     data.append(&Bytes::from_slice(&env, &amount.to_be_bytes()));
     data.append(&salt);
     let commitment: BytesN<32> = env.crypto().sha256(&data).into();
 
     setup_escrow(&env, &client.address, &token, amount, commitment.clone());
 
-    let is_valid = client.verify_proof_view(&amount, &salt, &owner);
+    let is_valid = client.verify_proof_view(&token, &amount, &salt, &owner);
     assert!(is_valid);
 }
 
@@ -517,6 +1490,7 @@ fn test_verify_proof_view_wrong_amount() {
     let mut data = Bytes::new(&env);
     let address_bytes: Bytes = owner.clone().to_xdr(&env);
     data.append(&address_bytes);
+    data.append(&token.clone().to_xdr(&env));
     data.append(&Bytes::from_slice(&env, &correct_amount.to_be_bytes()));
     data.append(&salt);
     let commitment: BytesN<32> = env.crypto().sha256(&data).into();
@@ -529,7 +1503,7 @@ fn test_verify_proof_view_wrong_amount() {
         commitment.clone(),
     );
 
-    let is_valid = client.verify_proof_view(&wrong_amount, &salt, &owner);
+    let is_valid = client.verify_proof_view(&token, &wrong_amount, &salt, &owner);
     assert!(!is_valid);
 }
 
@@ -539,19 +1513,20 @@ fn test_verify_proof_view_wrong_salt() {
     let token = create_test_token(&env);
     let owner = Address::generate(&env);
     let amount: i128 = 1000;
-    let correct_salt = Bytes::from_slice(&env, b"correct_salt");
-    let wrong_salt = Bytes::from_slice(&env, b"wrong_salt");
+    let correct_salt = Bytes::from_slice(&env, b"correct_salt_abcd");
+    let wrong_salt = Bytes::from_slice(&env, b"wrong_salt_abcdef");
 
     let mut data = Bytes::new(&env);
     let address_bytes: Bytes = owner.clone().to_xdr(&env);
     data.append(&address_bytes);
+    data.append(&token.clone().to_xdr(&env));
     data.append(&Bytes::from_slice(&env, &amount.to_be_bytes()));
     data.append(&correct_salt);
     let commitment: BytesN<32> = env.crypto().sha256(&data).into();
 
     setup_escrow(&env, &client.address, &token, amount, commitment.clone());
 
-    let is_valid = client.verify_proof_view(&amount, &wrong_salt, &owner);
+    let is_valid = client.verify_proof_view(&token, &amount, &wrong_salt, &owner);
     assert!(!is_valid);
 }
 
@@ -562,18 +1537,19 @@ fn test_verify_proof_view_wrong_owner() {
     let correct_owner = Address::generate(&env);
     let wrong_owner = Address::generate(&env);
     let amount: i128 = 1000;
-    let salt = Bytes::from_slice(&env, b"owner_test_salt");
+    let salt = Bytes::from_slice(&env, b"owner_test_salt1");
 
     let mut data = Bytes::new(&env);
     let address_bytes: Bytes = correct_owner.clone().to_xdr(&env);
     data.append(&address_bytes);
+    data.append(&token.clone().to_xdr(&env));
     data.append(&Bytes::from_slice(&env, &amount.to_be_bytes()));
     data.append(&salt);
     let commitment: BytesN<32> = env.crypto().sha256(&data).into();
 
     setup_escrow(&env, &client.address, &token, amount, commitment.clone());
 
-    let is_valid = client.verify_proof_view(&amount, &salt, &wrong_owner);
+    let is_valid = client.verify_proof_view(&token, &amount, &salt, &wrong_owner);
     assert!(!is_valid);
 }
 
@@ -588,6 +1564,7 @@ fn test_verify_proof_view_spent_commitment() {
     let mut data = Bytes::new(&env);
     let address_bytes: Bytes = owner.clone().to_xdr(&env);
     data.append(&address_bytes);
+    data.append(&token.clone().to_xdr(&env));
     data.append(&Bytes::from_slice(&env, &amount.to_be_bytes()));
     data.append(&salt);
     let commitment: BytesN<32> = env.crypto().sha256(&data).into();
@@ -599,6 +1576,9 @@ fn test_verify_proof_view_spent_commitment() {
         owner: owner.clone(),
         status: EscrowStatus::Spent,
         created_at: env.ledger().timestamp(),
+        expires_at: NO_EXPIRY,
+        payout: Payout::Deposited,
+        hash_algorithm: HashAlgorithm::Sha256,
     };
 
     let escrow_key = soroban_sdk::Symbol::new(&env, "escrow");
@@ -608,21 +1588,53 @@ fn test_verify_proof_view_spent_commitment() {
             .set(&(escrow_key, commitment.clone()), &entry);
     });
 
-    let is_valid = client.verify_proof_view(&amount, &salt, &owner);
+    let is_valid = client.verify_proof_view(&token, &amount, &salt, &owner);
     assert!(!is_valid);
 }
 
 #[test]
 fn test_verify_proof_view_nonexistent_commitment() {
     let (env, client) = setup();
+    let token = Address::generate(&env);
     let owner = Address::generate(&env);
     let amount: i128 = 1000;
     let salt = Bytes::from_slice(&env, b"nonexistent_proof_salt");
 
-    let is_valid = client.verify_proof_view(&amount, &salt, &owner);
+    let is_valid = client.verify_proof_view(&token, &amount, &salt, &owner);
     assert!(!is_valid);
 }
 
+#[test]
+fn test_verify_proof_view_for_matching_commitment() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let owner = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"proof_for_salt01");
+    let commitment = setup_withdrawable_escrow(&env, &client, &token, &owner, 1000, &salt);
+
+    assert!(client.verify_proof_view_for(&commitment, &token, &1000, &salt, &owner));
+
+    client.withdraw(&token, &1000, &commitment, &owner, &salt);
+    assert!(!client.verify_proof_view_for(&commitment, &token, &1000, &salt, &owner));
+}
+
+#[test]
+fn test_verify_proof_view_for_mismatching_commitment() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let owner = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"proof_for_salt02");
+    let other_salt = Bytes::from_slice(&env, b"proof_for_salt03");
+    let commitment = setup_withdrawable_escrow(&env, &client, &token, &owner, 1000, &salt);
+    let other = setup_withdrawable_escrow(&env, &client, &token, &owner, 1000, &other_salt);
+
+    // Both openings are valid on their own, but each belongs to a different commitment
+    assert!(client.verify_proof_view(&token, &1000, &other_salt, &owner));
+    assert!(!client.verify_proof_view_for(&commitment, &token, &1000, &other_salt, &owner));
+    assert!(!client.verify_proof_view_for(&other, &token, &1000, &salt, &owner));
+    assert!(!client.verify_proof_view_for(&commitment, &token, &999, &salt, &owner));
+}
+
 #[test]
 fn test_get_escrow_details_found() {
     let (env, client) = setup();
@@ -634,13 +1646,14 @@ fn test_get_escrow_details_found() {
     let mut data = Bytes::new(&env);
     let address_bytes: Bytes = owner.clone().to_xdr(&env);
     data.append(&address_bytes);
+    data.append(&token.clone().to_xdr(&env));
     data.append(&Bytes::from_slice(&env, &amount.to_be_bytes()));
     data.append(&salt);
     let commitment: BytesN<32> = env.crypto().sha256(&data).into();
 
     setup_escrow(&env, &client.address, &token, amount, commitment.clone());
 
-    let details = client.get_escrow_details(&commitment);
+    let details = client.get_escrow_details(&commitment, &false);
     assert!(details.is_some());
 
     let entry = details.unwrap();
@@ -652,18 +1665,20 @@ fn test_get_escrow_details_found() {
 #[test]
 fn test_get_escrow_details_not_found() {
     let (env, client) = setup();
+    let token = Address::generate(&env);
     let owner = Address::generate(&env);
     let amount: i128 = 1000;
-    let salt = Bytes::from_slice(&env, b"not_found_salt");
+    let salt = Bytes::from_slice(&env, b"not_found_salt01");
 
     let mut data = Bytes::new(&env);
     let address_bytes: Bytes = owner.clone().to_xdr(&env);
     data.append(&address_bytes);
+    data.append(&token.clone().to_xdr(&env));
     data.append(&Bytes::from_slice(&env, &amount.to_be_bytes()));
     data.append(&salt);
     let commitment: BytesN<32> = env.crypto().sha256(&data).into();
 
-    let details = client.get_escrow_details(&commitment);
+    let details = client.get_escrow_details(&commitment, &false);
     assert!(details.is_none());
 }
 
@@ -678,6 +1693,7 @@ fn test_get_escrow_details_spent_status() {
     let mut data = Bytes::new(&env);
     let address_bytes: Bytes = owner.clone().to_xdr(&env);
     data.append(&address_bytes);
+    data.append(&token.clone().to_xdr(&env));
     data.append(&Bytes::from_slice(&env, &amount.to_be_bytes()));
     data.append(&salt);
     let commitment: BytesN<32> = env.crypto().sha256(&data).into();
@@ -689,6 +1705,9 @@ fn test_get_escrow_details_spent_status() {
         owner: owner.clone(),
         status: EscrowStatus::Spent,
         created_at: env.ledger().timestamp(),
+        expires_at: NO_EXPIRY,
+        payout: Payout::Deposited,
+        hash_algorithm: HashAlgorithm::Sha256,
     };
 
     env.as_contract(&client.address, || {
@@ -696,7 +1715,7 @@ fn test_get_escrow_details_spent_status() {
         put_escrow(&env, &storage_commitment, &entry);
     });
 
-    let details = client.get_escrow_details(&commitment);
+    let details = client.get_escrow_details(&commitment, &false);
     assert!(details.is_some());
 
     let retrieved_entry = details.unwrap();
@@ -704,6 +1723,70 @@ fn test_get_escrow_details_spent_status() {
     assert_eq!(retrieved_entry.amount, amount);
     assert_eq!(retrieved_entry.token, token);
 }
+
+#[test]
+fn test_get_escrow_details_only_active_hides_spent() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let to = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"only_active_salt");
+    let commitment = setup_withdrawable_escrow(&env, &client, &token, &to, 1000, &salt);
+
+    let pending = client.get_escrow_details(&commitment, &true).unwrap();
+    assert_eq!(pending.status, EscrowStatus::Pending);
+
+    client.withdraw(&token, &1000, &commitment, &to, &salt);
+    let spent = client.get_escrow_details(&commitment, &false).unwrap();
+    assert_eq!(spent.status, EscrowStatus::Spent);
+    assert!(client.get_escrow_details(&commitment, &true).is_none());
+}
+
+#[test]
+fn test_get_escrow_details_redacts_amount_for_private_owner() {
+    use crate::types::REDACTED_AMOUNT;
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    let to = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &1000);
+
+    let salt = Bytes::from_slice(&env, b"redacted_salt_01");
+    let commitment = client.create_amount_commitment(&to, &token, &1000, &salt);
+    client.deposit_with_commitment(&depositor, &token, &1000, &commitment);
+    assert_eq!(
+        client
+            .get_escrow_details(&commitment, &false)
+            .unwrap()
+            .amount,
+        1000
+    );
+
+    client.set_privacy(&depositor, &true);
+    let redacted = client.get_escrow_details(&commitment, &false).unwrap();
+    assert_eq!(redacted.amount, REDACTED_AMOUNT);
+    assert_eq!(redacted.token, token);
+
+    // A valid opening reveals the amount; a wrong salt does not
+    let full = client
+        .get_escrow_details_with_proof(&commitment, &to, &salt)
+        .unwrap();
+    assert_eq!(full.amount, 1000);
+    let wrong_salt = Bytes::from_slice(&env, b"not_the_salt_001");
+    let guessed = client
+        .get_escrow_details_with_proof(&commitment, &to, &wrong_salt)
+        .unwrap();
+    assert_eq!(guessed.amount, REDACTED_AMOUNT);
+
+    client.set_privacy(&depositor, &false);
+    assert_eq!(
+        client
+            .get_escrow_details(&commitment, &false)
+            .unwrap()
+            .amount,
+        1000
+    );
+}
 // ============================================================================
 // Upgrade Tests
 // ============================================================================
@@ -716,7 +1799,7 @@ fn test_upgrade_by_admin() {
     let admin = Address::generate(&env);
 
     // Initialize admin
-    client.initialize(&admin);
+    client.initialize(&admin, &None);
 
     // Create a dummy WASM hash for testing
     let new_wasm_hash = BytesN::from_array(&env, &[0u8; 32]);
@@ -746,6 +1829,37 @@ fn test_upgrade_by_admin() {
     }
 }
 
+#[test]
+fn test_upgrade_replaces_contract_wasm() {
+    use crate::events::ContractUpgradedEvent;
+    use soroban_sdk::{IntoVal, Symbol};
+
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &None);
+
+    // A minimal contract exposing `add(u64, u64) -> u64`
+    let new_wasm_hash = env
+        .deployer()
+        .upload_contract_wasm(include_bytes!("../test_fixtures/upgrade_target.wasm").as_slice());
+
+    client.upgrade(&admin, &new_wasm_hash);
+
+    let expected = ContractUpgradedEvent {
+        new_wasm_hash,
+        admin,
+        timestamp: env.ledger().timestamp(),
+    };
+    assert!(has_event(&env, &client.address, &expected));
+
+    let sum: u64 = env.invoke_contract(
+        &client.address,
+        &Symbol::new(&env, "add"),
+        soroban_sdk::vec![&env, 1u64.into_val(&env), 2u64.into_val(&env)],
+    );
+    assert_eq!(sum, 3);
+}
+
 #[test]
 #[should_panic(expected = "Error(Contract, #2)")]
 fn test_upgrade_by_non_admin_fails() {
@@ -754,7 +1868,7 @@ fn test_upgrade_by_non_admin_fails() {
     let non_admin = Address::generate(&env);
 
     // Initialize admin
-    client.initialize(&admin);
+    client.initialize(&admin, &None);
 
     // Create a dummy WASM hash
     let new_wasm_hash = BytesN::from_array(&env, &[0u8; 32]);
@@ -775,3 +1889,4520 @@ fn test_upgrade_without_admin_initialized_fails() {
     // Try to upgrade without admin set - should fail with Unauthorized
     client.upgrade(&caller, &new_wasm_hash);
 }
+
+// ============================================================================
+// Protocol Fee Tests
+// ============================================================================
+
+fn setup_withdrawable_escrow(
+    env: &Env,
+    client: &QuickexContractClient,
+    token: &Address,
+    to: &Address,
+    amount: i128,
+    salt: &Bytes,
+) -> BytesN<32> {
+    let commitment = client.create_amount_commitment(to, token, &amount, salt);
+    setup_escrow(env, &client.address, token, amount, commitment.clone());
+    token::StellarAssetClient::new(env, token).mint(&client.address, &amount);
+    commitment
+}
+
+#[test]
+fn test_withdraw_with_fee_splits_amount() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let collector = Address::generate(&env);
+    let token = create_test_token(&env);
+    let to = Address::generate(&env);
+    let amount: i128 = 10_000;
+    let salt = Bytes::from_slice(&env, b"fee_split_salt01");
+
+    client.initialize(&admin, &None);
+    client.set_fee(&admin, &250, &collector);
+    assert_eq!(client.get_fee_bps(), 250);
+    assert_eq!(client.get_fee_collector(), Some(collector.clone()));
+
+    let commitment = setup_withdrawable_escrow(&env, &client, &token, &to, amount, &salt);
+    client.withdraw(&token, &amount, &commitment, &to, &salt);
+
+    let token_client = token::Client::new(&env, &token);
+    assert_eq!(token_client.balance(&collector), 250);
+    assert_eq!(token_client.balance(&to), 9_750);
+    assert_eq!(token_client.balance(&client.address), 0);
+    assert_eq!(
+        client.get_commitment_state(&commitment),
+        Some(EscrowStatus::Spent)
+    );
+}
+
+#[test]
+fn test_deposit_fee_escrows_net_amount() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let collector = Address::generate(&env);
+    let token = create_test_token(&env);
+    let owner = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"deposit_fee_salt");
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &10_000);
+
+    client.initialize(&admin, &None);
+    client.set_fee(&admin, &0, &collector);
+    client.set_deposit_fee(&admin, &200);
+    assert_eq!(client.get_deposit_fee_bps(), 200);
+    assert_eq!(client.get_net_deposit_amount(&10_000), 9_800);
+
+    let commitment = client.deposit(&token, &10_000, &owner, &salt);
+    assert_eq!(
+        commitment,
+        client.create_amount_commitment(&owner, &token, &9_800, &salt)
+    );
+    assert_eq!(
+        client
+            .get_escrow_details(&commitment, &false)
+            .unwrap()
+            .amount,
+        9_800
+    );
+    assert_eq!(client.get_total_escrowed(&token), 9_800);
+
+    // A retried deposit is not charged again
+    client.deposit(&token, &10_000, &owner, &salt);
+
+    let token_client = token::Client::new(&env, &token);
+    assert_eq!(token_client.balance(&collector), 200);
+    assert_eq!(token_client.balance(&client.address), 9_800);
+    assert_eq!(token_client.balance(&owner), 0);
+
+    client.withdraw(&token, &9_800, &commitment, &owner, &salt);
+    assert_eq!(token_client.balance(&owner), 9_800);
+    assert_eq!(token_client.balance(&client.address), 0);
+}
+
+#[test]
+fn test_deposit_fee_with_precomputed_commitment() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let collector = Address::generate(&env);
+    let token = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    let to = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"deposit_fee_slt2");
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &5_000);
+
+    client.initialize(&admin, &None);
+    client.set_fee(&admin, &100, &collector);
+    client.set_deposit_fee(&admin, &1_000);
+
+    let net = client.get_net_deposit_amount(&5_000);
+    assert_eq!(net, 4_500);
+    let commitment = client.create_amount_commitment(&to, &token, &net, &salt);
+    client.deposit_with_commitment(&depositor, &token, &5_000, &commitment);
+
+    // The deposit fee and the withdrawal fee are charged independently
+    let receipt = client.withdraw(&token, &net, &commitment, &to, &salt);
+    assert_eq!(receipt.fee, 45);
+    let token_client = token::Client::new(&env, &token);
+    assert_eq!(token_client.balance(&collector), 500 + 45);
+    assert_eq!(token_client.balance(&to), 4_455);
+}
+
+#[test]
+fn test_set_deposit_fee_rejects_invalid_cases() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &None);
+
+    assert_eq!(client.get_deposit_fee_bps(), 0);
+    assert_eq!(
+        client.try_set_deposit_fee(&admin, &100),
+        Err(Ok(QuickexError::FeeCollectorNotSet))
+    );
+    client.set_fee(&admin, &0, &Address::generate(&env));
+    assert_eq!(
+        client.try_set_deposit_fee(&admin, &1_001),
+        Err(Ok(QuickexError::FeeTooHigh))
+    );
+    assert_eq!(
+        client.try_set_deposit_fee(&Address::generate(&env), &100),
+        Err(Ok(QuickexError::Unauthorized))
+    );
+    assert_eq!(client.get_deposit_fee_bps(), 0);
+}
+
+#[test]
+fn test_withdraw_with_fee_rounds_down() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let collector = Address::generate(&env);
+    let token = create_test_token(&env);
+    let to = Address::generate(&env);
+    let amount: i128 = 999;
+    let salt = Bytes::from_slice(&env, b"fee_round_salt01");
+
+    client.initialize(&admin, &None);
+    client.set_fee(&admin, &100, &collector);
+
+    let commitment = setup_withdrawable_escrow(&env, &client, &token, &to, amount, &salt);
+    client.withdraw(&token, &amount, &commitment, &to, &salt);
+
+    // 999 * 100 / 10_000 = 9.99 -> 9
+    let token_client = token::Client::new(&env, &token);
+    assert_eq!(token_client.balance(&collector), 9);
+    assert_eq!(token_client.balance(&to), 990);
+}
+
+#[test]
+fn test_withdraw_cooldown_per_recipient() {
+    use crate::errors::QuickexError;
+    use soroban_sdk::testutils::Ledger as _;
+
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &None);
+    let token = create_test_token(&env);
+    let to = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    assert_eq!(client.get_withdraw_cooldown(), 0);
+    client.set_withdraw_cooldown(&admin, &100);
+    assert_eq!(client.get_withdraw_cooldown(), 100);
+
+    let salts = [
+        Bytes::from_slice(&env, b"cooldown_salt_01"),
+        Bytes::from_slice(&env, b"cooldown_salt_02"),
+        Bytes::from_slice(&env, b"cooldown_salt_03"),
+    ];
+    let first = setup_withdrawable_escrow(&env, &client, &token, &to, 1000, &salts[0]);
+    let second = setup_withdrawable_escrow(&env, &client, &token, &to, 1000, &salts[1]);
+    let unrelated = setup_withdrawable_escrow(&env, &client, &token, &other, 1000, &salts[2]);
+
+    client.withdraw(&token, &1000, &first, &to, &salts[0]);
+    let result = client.try_withdraw(&token, &1000, &second, &to, &salts[1]);
+    assert_eq!(result.err(), Some(Ok(QuickexError::WithdrawCooldownActive)));
+    assert_eq!(
+        client.try_can_withdraw(&to, &token, &1000, &salts[1]),
+        Err(Ok(QuickexError::WithdrawCooldownActive))
+    );
+
+    // The cooldown is per recipient
+    client.withdraw(&token, &1000, &unrelated, &other, &salts[2]);
+
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + 99);
+    let result = client.try_withdraw(&token, &1000, &second, &to, &salts[1]);
+    assert_eq!(result.err(), Some(Ok(QuickexError::WithdrawCooldownActive)));
+
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + 1);
+    client.withdraw(&token, &1000, &second, &to, &salts[1]);
+    assert_eq!(token::Client::new(&env, &token).balance(&to), 2000);
+}
+
+#[test]
+fn test_withdraw_receipt_without_fee() {
+    use crate::types::WithdrawReceipt;
+    use soroban_sdk::testutils::Ledger as _;
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let to = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"receipt_salt_001");
+    let commitment = setup_withdrawable_escrow(&env, &client, &token, &to, 1000, &salt);
+
+    env.ledger().set_timestamp(7_000);
+    let receipt = client.withdraw(&token, &1000, &commitment, &to, &salt);
+    assert_eq!(
+        receipt,
+        WithdrawReceipt {
+            amount_out: 1000,
+            fee: 0,
+            status: EscrowStatus::Spent,
+            timestamp: 7_000,
+        }
+    );
+}
+
+#[test]
+fn test_withdraw_receipt_with_fee() {
+    use crate::types::WithdrawReceipt;
+    use soroban_sdk::testutils::Ledger as _;
+
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let collector = Address::generate(&env);
+    let token = create_test_token(&env);
+    let to = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"receipt_fee_salt");
+
+    client.initialize(&admin, &None);
+    client.set_fee(&admin, &250, &collector);
+    let commitment = setup_withdrawable_escrow(&env, &client, &token, &to, 10_000, &salt);
+
+    env.ledger().set_timestamp(8_000);
+    let receipt = client.withdraw(&token, &10_000, &commitment, &to, &salt);
+    assert_eq!(
+        receipt,
+        WithdrawReceipt {
+            amount_out: 9_750,
+            fee: 250,
+            status: EscrowStatus::Spent,
+            timestamp: 8_000,
+        }
+    );
+    assert_eq!(
+        token::Client::new(&env, &token).balance(&to),
+        receipt.amount_out
+    );
+}
+
+#[test]
+fn test_set_fee_collector_routes_later_fees() {
+    use crate::events::FeeCollectorChangedEvent;
+
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let old_collector = Address::generate(&env);
+    let new_collector = Address::generate(&env);
+    let token = create_test_token(&env);
+    let to = Address::generate(&env);
+    client.initialize(&admin, &None);
+    client.set_fee(&admin, &100, &old_collector);
+
+    client.set_fee_collector(&admin, &new_collector);
+    let event = FeeCollectorChangedEvent {
+        old_collector: old_collector.clone(),
+        new_collector: new_collector.clone(),
+        timestamp: env.ledger().timestamp(),
+    };
+    assert!(has_event(&env, &client.address, &event));
+    assert_eq!(client.get_fee_collector(), Some(new_collector.clone()));
+    assert_eq!(client.get_fee_bps(), 100);
+
+    let salt = Bytes::from_slice(&env, b"fee_rotate_salt1");
+    let commitment = setup_withdrawable_escrow(&env, &client, &token, &to, 10_000, &salt);
+    client.withdraw(&token, &10_000, &commitment, &to, &salt);
+
+    let token_client = token::Client::new(&env, &token);
+    assert_eq!(token_client.balance(&new_collector), 100);
+    assert_eq!(token_client.balance(&old_collector), 0);
+    assert_eq!(token_client.balance(&to), 9_900);
+}
+
+#[test]
+fn test_set_fee_collector_rejects_invalid_cases() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let collector = Address::generate(&env);
+    client.initialize(&admin, &None);
+
+    // Nothing to rotate before a fee is configured
+    let result = client.try_set_fee_collector(&admin, &collector);
+    assert_eq!(result, Err(Ok(QuickexError::FeeCollectorNotSet)));
+
+    client.set_fee(&admin, &100, &collector);
+    let result = client.try_set_fee_collector(&admin, &client.address);
+    assert_eq!(result, Err(Ok(QuickexError::InvalidInput)));
+    let result = client.try_set_fee_collector(&Address::generate(&env), &admin);
+    assert_eq!(result, Err(Ok(QuickexError::Unauthorized)));
+    assert_eq!(client.get_fee_collector(), Some(collector));
+}
+
+#[test]
+fn test_set_fee_validates_and_announces_collector() {
+    use crate::errors::QuickexError;
+    use crate::events::FeeCollectorChangedEvent;
+    use soroban_sdk::{Symbol, TryFromVal};
+
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let old_collector = Address::generate(&env);
+    let new_collector = Address::generate(&env);
+    client.initialize(&admin, &None);
+    let collector_changed = || {
+        let topic = Symbol::new(&env, "FeeCollectorChanged");
+        env.events().all().iter().any(|(contract, topics, _)| {
+            contract == client.address
+                && Symbol::try_from_val(&env, &topics.get(0).unwrap()).is_ok_and(|t| t == topic)
+        })
+    };
+
+    let result = client.try_set_fee(&admin, &100, &client.address);
+    assert_eq!(result, Err(Ok(QuickexError::InvalidInput)));
+    assert_eq!(client.get_fee_collector(), None);
+
+    // The first collector is configured, not changed
+    client.set_fee(&admin, &100, &old_collector);
+    assert!(!collector_changed());
+
+    client.set_fee(&admin, &200, &new_collector);
+    let event = FeeCollectorChangedEvent {
+        old_collector: old_collector.clone(),
+        new_collector: new_collector.clone(),
+        timestamp: env.ledger().timestamp(),
+    };
+    assert!(has_event(&env, &client.address, &event));
+
+    // Updating only the rate keeps the collector and stays quiet
+    client.set_fee(&admin, &300, &new_collector);
+    assert!(!collector_changed());
+
+    let result = client.try_set_fee(&admin, &100, &client.address);
+    assert_eq!(result, Err(Ok(QuickexError::InvalidInput)));
+    assert_eq!(client.get_fee_collector(), Some(new_collector));
+    assert_eq!(client.get_fee_bps(), 300);
+}
+
+#[test]
+fn test_withdraw_with_zero_fee_pays_full_amount() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let collector = Address::generate(&env);
+    let token = create_test_token(&env);
+    let to = Address::generate(&env);
+    let amount: i128 = 1000;
+    let salt = Bytes::from_slice(&env, b"zero_fee_salt001");
+
+    client.initialize(&admin, &None);
+    client.set_fee(&admin, &0, &collector);
+
+    let commitment = setup_withdrawable_escrow(&env, &client, &token, &to, amount, &salt);
+    client.withdraw(&token, &amount, &commitment, &to, &salt);
+
+    let token_client = token::Client::new(&env, &token);
+    assert_eq!(token_client.balance(&collector), 0);
+    assert_eq!(token_client.balance(&to), amount);
+}
+
+#[test]
+fn test_set_fee_above_cap_fails() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let collector = Address::generate(&env);
+
+    client.initialize(&admin, &None);
+
+    let result = client.try_set_fee(&admin, &1_001, &collector);
+    assert_eq!(result, Err(Ok(QuickexError::FeeTooHigh)));
+
+    // The cap itself is accepted
+    client.set_fee(&admin, &1_000, &collector);
+    assert_eq!(client.get_fee_bps(), 1_000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_set_fee_by_non_admin_fails() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let non_admin = Address::generate(&env);
+    let collector = Address::generate(&env);
+
+    client.initialize(&admin, &None);
+    client.set_fee(&non_admin, &100, &collector);
+}
+
+// ============================================================================
+// Commitment Status Event Tests
+// ============================================================================
+
+fn has_event<E: Event>(env: &Env, contract_id: &Address, event: &E) -> bool {
+    let expected = (contract_id.clone(), event.topics(env), event.data(env));
+    env.events().all().contains(expected)
+}
+
+/// Like [`has_event`], but for events published by an invocation that returned an
+/// error. The host keeps those only as failed-call events, which `events().all()` hides.
+fn has_failed_call_event<E: Event>(env: &Env, contract_id: &Address, event: &E) -> bool {
+    use soroban_sdk::{xdr, TryIntoVal, Val};
+
+    let mut failed: soroban_sdk::Vec<(Address, soroban_sdk::Vec<Val>, Val)> =
+        soroban_sdk::Vec::new(env);
+    for e in env.host().get_events().unwrap().0 {
+        if let (
+            true,
+            xdr::ContractEvent {
+                contract_id: Some(id),
+                body: xdr::ContractEventBody::V0(xdr::ContractEventV0 { topics, data }),
+                ..
+            },
+        ) = (e.failed_call, e.event)
+        {
+            failed.push_back((
+                xdr::ScAddress::Contract(id).try_into_val(env).unwrap(),
+                topics.try_into_val(env).unwrap(),
+                data.try_into_val(env).unwrap(),
+            ));
+        }
+    }
+    failed.contains((contract_id.clone(), event.topics(env), event.data(env)))
+}
+
+#[test]
+fn test_deposit_emits_pending_status_event() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let from = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&from, &1000);
+
+    let commitment = BytesN::from_array(&env, &[7; 32]);
+    client.deposit_with_commitment(&from, &token, &1000, &commitment);
+
+    let event = CommitmentStatusChangedEvent {
+        commitment,
+        from: None,
+        to: EscrowStatus::Pending,
+        timestamp: env.ledger().timestamp(),
+    };
+    assert!(has_event(&env, &client.address, &event));
+}
+
+#[test]
+fn test_withdraw_emits_spent_status_event() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let to = Address::generate(&env);
+    let amount: i128 = 1000;
+    let salt = Bytes::from_slice(&env, b"status_event_salt");
+
+    let commitment = setup_withdrawable_escrow(&env, &client, &token, &to, amount, &salt);
+    client.withdraw(&token, &amount, &commitment, &to, &salt);
+
+    let event = CommitmentStatusChangedEvent {
+        commitment,
+        from: Some(EscrowStatus::Pending),
+        to: EscrowStatus::Spent,
+        timestamp: env.ledger().timestamp(),
+    };
+    assert!(has_event(&env, &client.address, &event));
+}
+
+// ============================================================================
+// Cancel Deposit Tests
+// ============================================================================
+
+#[test]
+fn test_cancel_deposit_refunds_depositor() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    let token_client = token::Client::new(&env, &token);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &1000);
+
+    let commitment = BytesN::from_array(&env, &[3; 32]);
+    client.deposit_with_commitment(&depositor, &token, &1000, &commitment);
+    assert_eq!(token_client.balance(&depositor), 0);
+
+    client.cancel_deposit(&depositor, &commitment);
+
+    let event = DepositCancelledEvent {
+        depositor: depositor.clone(),
+        commitment: commitment.clone(),
+        timestamp: env.ledger().timestamp(),
+    };
+    assert!(has_event(&env, &client.address, &event));
+
+    assert_eq!(token_client.balance(&depositor), 1000);
+    assert_eq!(token_client.balance(&client.address), 0);
+    assert_eq!(
+        client.get_commitment_state(&commitment),
+        Some(EscrowStatus::Cancelled)
+    );
+}
+
+#[test]
+fn test_cancel_deposit_by_non_depositor_fails() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &1000);
+
+    let commitment = BytesN::from_array(&env, &[4; 32]);
+    client.deposit_with_commitment(&depositor, &token, &1000, &commitment);
+
+    let result = client.try_cancel_deposit(&stranger, &commitment);
+    assert_eq!(result, Err(Ok(QuickexError::Unauthorized)));
+    assert_eq!(
+        client.get_commitment_state(&commitment),
+        Some(EscrowStatus::Pending)
+    );
+}
+
+#[test]
+fn test_cancel_deposit_after_withdrawal_fails() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    let to = Address::generate(&env);
+    let amount: i128 = 1000;
+    let salt = Bytes::from_slice(&env, b"cancel_after_withdraw");
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &amount);
+
+    let commitment = client.create_amount_commitment(&to, &token, &amount, &salt);
+    client.deposit_with_commitment(&depositor, &token, &amount, &commitment);
+    client.withdraw(&token, &amount, &commitment, &to, &salt);
+
+    let result = client.try_cancel_deposit(&depositor, &commitment);
+    assert_eq!(result, Err(Ok(QuickexError::AlreadySpent)));
+}
+
+// ============================================================================
+// Escrow Import Tests
+// ============================================================================
+
+fn import_entry(env: &Env, token: &Address, amount: i128) -> EscrowEntry {
+    EscrowEntry {
+        token: token.clone(),
+        amount,
+        owner: Address::generate(env),
+        status: EscrowStatus::Pending,
+        created_at: env.ledger().timestamp(),
+        expires_at: NO_EXPIRY,
+        payout: Payout::Deposited,
+        hash_algorithm: HashAlgorithm::Sha256,
+    }
+}
+
+#[test]
+fn test_import_backed_escrows() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let token = create_test_token(&env);
+    client.initialize(&admin, &None);
+
+    token::StellarAssetClient::new(&env, &token).mint(&client.address, &1500);
+
+    let first = BytesN::from_array(&env, &[10; 32]);
+    let second = BytesN::from_array(&env, &[11; 32]);
+    let escrows = soroban_sdk::vec![
+        &env,
+        (first.clone(), import_entry(&env, &token, 1000)),
+        (second.clone(), import_entry(&env, &token, 500)),
+    ];
+
+    client.import_escrows(&admin, &escrows, &true);
+
+    assert_eq!(
+        client.get_escrow_details(&first, &false).unwrap().amount,
+        1000
+    );
+    assert_eq!(
+        client.get_escrow_details(&second, &false).unwrap().amount,
+        500
+    );
+}
+
+#[test]
+fn test_import_unbacked_escrows_fails() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let token = create_test_token(&env);
+    client.initialize(&admin, &None);
+
+    token::StellarAssetClient::new(&env, &token).mint(&client.address, &1499);
+
+    let first = BytesN::from_array(&env, &[12; 32]);
+    let second = BytesN::from_array(&env, &[13; 32]);
+    let escrows = soroban_sdk::vec![
+        &env,
+        (first.clone(), import_entry(&env, &token, 1000)),
+        (second, import_entry(&env, &token, 500)),
+    ];
+
+    let result = client.try_import_escrows(&admin, &escrows, &true);
+    assert_eq!(result, Err(Ok(QuickexError::TransferShortfall)));
+    assert!(client.get_escrow_details(&first, &false).is_none());
+
+    // Without backing verification the same set is accepted as-is
+    client.import_escrows(&admin, &escrows, &false);
+    assert!(client.get_escrow_details(&first, &false).is_some());
+}
+
+#[test]
+fn test_import_unbacked_expired_escrow_fails() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let token = create_test_token(&env);
+    client.initialize(&admin, &None);
+
+    let mut entry = import_entry(&env, &token, 1000);
+    entry.status = EscrowStatus::Expired;
+    let expired = BytesN::from_array(&env, &[15; 32]);
+    let escrows = soroban_sdk::vec![&env, (expired.clone(), entry)];
+
+    // Expired escrows can still be refunded, so they need backing too
+    let result = client.try_import_escrows(&admin, &escrows, &true);
+    assert_eq!(result, Err(Ok(QuickexError::TransferShortfall)));
+    assert!(client.get_escrow_details(&expired, &false).is_none());
+}
+
+#[test]
+fn test_import_covered_only_by_existing_escrows_fails() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let token = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    client.initialize(&admin, &None);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &1000);
+    client.deposit_with_commitment(
+        &depositor,
+        &token,
+        &1000,
+        &BytesN::from_array(&env, &[16; 32]),
+    );
+
+    // The contract's 1000 already back the existing escrow
+    let imported = BytesN::from_array(&env, &[17; 32]);
+    let escrows = soroban_sdk::vec![&env, (imported.clone(), import_entry(&env, &token, 1000))];
+    let result = client.try_import_escrows(&admin, &escrows, &true);
+    assert_eq!(result, Err(Ok(QuickexError::TransferShortfall)));
+
+    token::StellarAssetClient::new(&env, &token).mint(&client.address, &1000);
+    client.import_escrows(&admin, &escrows, &true);
+    assert_eq!(client.get_total_escrowed(&token), 2000);
+}
+
+#[test]
+fn test_import_escrows_rejects_non_positive_amount() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let token = create_test_token(&env);
+    client.initialize(&admin, &None);
+
+    for amount in [0, -5] {
+        let escrows = soroban_sdk::vec![
+            &env,
+            (
+                BytesN::from_array(&env, &[18; 32]),
+                import_entry(&env, &token, amount)
+            ),
+        ];
+        let result = client.try_import_escrows(&admin, &escrows, &false);
+        assert_eq!(result, Err(Ok(QuickexError::InvalidAmount)));
+    }
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_import_escrows_by_non_admin_fails() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let non_admin = Address::generate(&env);
+    let token = create_test_token(&env);
+    client.initialize(&admin, &None);
+
+    let escrows = soroban_sdk::vec![
+        &env,
+        (
+            BytesN::from_array(&env, &[14; 32]),
+            import_entry(&env, &token, 1000)
+        ),
+    ];
+    client.import_escrows(&non_admin, &escrows, &false);
+}
+
+#[test]
+fn test_get_commitment_state_cancelled() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let owner = Address::generate(&env);
+    let commitment = BytesN::from_array(&env, &[5; 32]);
+
+    let entry = EscrowEntry {
+        token,
+        amount: 1000,
+        owner,
+        status: EscrowStatus::Cancelled,
+        created_at: env.ledger().timestamp(),
+        expires_at: NO_EXPIRY,
+        payout: Payout::Deposited,
+        hash_algorithm: HashAlgorithm::Sha256,
+    };
+    env.as_contract(&client.address, || {
+        put_escrow(&env, &commitment.clone().into(), &entry);
+    });
+
+    assert_eq!(
+        client.get_commitment_state(&commitment),
+        Some(EscrowStatus::Cancelled)
+    );
+}
+
+#[test]
+fn test_get_deposit_timestamp() {
+    use soroban_sdk::testutils::Ledger as _;
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let owner = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &1000);
+
+    env.ledger().set_timestamp(12_345);
+    let salt = Bytes::from_slice(&env, b"timestamp_salt01");
+    let commitment = client.deposit(&token, &1000, &owner, &salt);
+    env.ledger().set_timestamp(20_000);
+
+    assert_eq!(client.get_deposit_timestamp(&commitment), Some(12_345));
+    assert_eq!(
+        client.get_deposit_timestamp(&BytesN::from_array(&env, &[9u8; 32])),
+        None
+    );
+}
+
+#[test]
+fn test_get_my_escrows_lists_pending_escrows() {
+    use soroban_sdk::{testutils::MockAuth, testutils::MockAuthInvoke, IntoVal};
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let owner = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &3000);
+
+    let salt = Bytes::from_slice(&env, b"my_escrows_salt1");
+    let withdrawn = client.create_amount_commitment(&recipient, &token, &1000, &salt);
+    client.deposit_with_commitment(&owner, &token, &1000, &withdrawn);
+    let first = BytesN::from_array(&env, &[30; 32]);
+    client.deposit_with_commitment(&owner, &token, &1000, &first);
+    let second = BytesN::from_array(&env, &[31; 32]);
+    client.deposit_with_commitment(&owner, &token, &1000, &second);
+    client.withdraw(&token, &1000, &withdrawn, &recipient, &salt);
+
+    // Only the owner's authorization is accepted
+    env.mock_auths(&[MockAuth {
+        address: &owner,
+        invoke: &MockAuthInvoke {
+            contract: &client.address,
+            fn_name: "get_my_escrows",
+            args: (owner.clone(),).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    let escrows = client.get_my_escrows(&owner);
+    assert_eq!(env.auths().len(), 1);
+    assert_eq!(escrows.len(), 2);
+    let (commitment, entry) = escrows.get(0).unwrap();
+    assert_eq!(commitment, first);
+    assert_eq!(entry.owner, owner);
+    assert_eq!(entry.amount, 1000);
+    assert_eq!(entry.status, EscrowStatus::Pending);
+    assert_eq!(escrows.get(1).unwrap().0, second);
+
+    env.mock_all_auths();
+    assert!(client.get_my_escrows(&recipient).is_empty());
+}
+
+#[test]
+fn test_recover_commitment() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let owner = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &1000);
+    let salt = Bytes::from_slice(&env, b"recover_salt_001");
+    let commitment = client.deposit(&token, &1000, &owner, &salt);
+
+    assert_eq!(
+        client.recover_commitment(&owner, &token, &1000, &salt),
+        Some(commitment)
+    );
+    assert_eq!(env.auths()[0].0, owner);
+}
+
+#[test]
+fn test_recover_commitment_without_escrow_returns_none() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let owner = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &1000);
+    let salt = Bytes::from_slice(&env, b"recover_salt_002");
+    client.deposit(&token, &1000, &owner, &salt);
+
+    assert_eq!(client.recover_commitment(&owner, &token, &999, &salt), None);
+    let other_salt = Bytes::from_slice(&env, b"recover_salt_003");
+    assert_eq!(
+        client.recover_commitment(&owner, &token, &1000, &other_salt),
+        None
+    );
+    let weak_salt = Bytes::from_slice(&env, b"weak");
+    assert_eq!(
+        client.recover_commitment(&owner, &token, &1000, &weak_salt),
+        None
+    );
+}
+
+#[test]
+fn test_withdraw_to_allowed_recipient() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let mallory = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"allowed_rcpt_slt");
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &1000);
+
+    let recipients = soroban_sdk::vec![&env, alice.clone(), bob.clone()];
+    let commitment = client.create_recipients_commitment(&recipients, &token, &1000, &salt);
+    assert_ne!(
+        commitment,
+        client.create_amount_commitment(&alice, &token, &1000, &salt)
+    );
+    client.deposit_with_commitment(&depositor, &token, &1000, &commitment);
+
+    // Knowing the salt is not enough to redirect the funds off the list
+    assert_eq!(
+        client
+            .try_withdraw_to_allowed(&mallory, &recipients, &token, &1000, &salt)
+            .err(),
+        Some(Ok(QuickexError::RecipientNotAllowed))
+    );
+    let forged = soroban_sdk::vec![&env, alice.clone(), mallory.clone()];
+    assert_eq!(
+        client
+            .try_withdraw_to_allowed(&mallory, &forged, &token, &1000, &salt)
+            .err(),
+        Some(Ok(QuickexError::CommitmentNotFound))
+    );
+
+    let receipt = client.withdraw_to_allowed(&bob, &recipients, &token, &1000, &salt);
+    assert_eq!(receipt.amount_out, 1000);
+    assert_eq!(token::Client::new(&env, &token).balance(&bob), 1000);
+    assert_eq!(
+        client.get_commitment_state(&commitment),
+        Some(EscrowStatus::Spent)
+    );
+    assert_eq!(
+        client
+            .try_withdraw_to_allowed(&alice, &recipients, &token, &1000, &salt)
+            .err(),
+        Some(Ok(QuickexError::AlreadySpent))
+    );
+}
+
+/// Deposit `amount` under a signer commitment to `key`; returns the commitment
+fn setup_signer_escrow(
+    env: &Env,
+    client: &QuickexContractClient,
+    token: &Address,
+    key: &ed25519_dalek::SigningKey,
+    amount: i128,
+    salt: &Bytes,
+) -> BytesN<32> {
+    let depositor = Address::generate(env);
+    token::StellarAssetClient::new(env, token).mint(&depositor, &amount);
+    let pubkey = BytesN::from_array(env, &key.verifying_key().to_bytes());
+    let commitment = client.create_signer_commitment(&pubkey, token, &amount, salt);
+    client.deposit_with_commitment(&depositor, token, &amount, &commitment);
+    commitment
+}
+
+/// Sign the `withdraw_signed` message for `commitment`, `to` and `amount` with `key`
+fn sign_withdrawal(
+    env: &Env,
+    client: &QuickexContractClient,
+    key: &ed25519_dalek::SigningKey,
+    commitment: &BytesN<32>,
+    to: &Address,
+    amount: i128,
+) -> BytesN<64> {
+    use ed25519_dalek::Signer;
+
+    let message = client.build_withdraw_message(commitment, to, &amount);
+    let mut bytes = [0u8; 256];
+    let len = message.len() as usize;
+    message.copy_into_slice(&mut bytes[..len]);
+    BytesN::from_array(env, &key.sign(&bytes[..len]).to_bytes())
+}
+
+#[test]
+fn test_withdraw_signed_valid_signature() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let key = ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]);
+    let pubkey = BytesN::from_array(&env, &key.verifying_key().to_bytes());
+    let to = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"signed_wdraw_slt");
+    let commitment = setup_signer_escrow(&env, &client, &token, &key, 1000, &salt);
+    assert_ne!(
+        commitment,
+        client.create_amount_commitment(&to, &token, &1000, &salt)
+    );
+
+    let signature = sign_withdrawal(&env, &client, &key, &commitment, &to, 1000);
+    let receipt = client.withdraw_signed(&to, &token, &1000, &salt, &pubkey, &signature);
+    assert!(env.auths().is_empty());
+    assert_eq!(receipt.amount_out, 1000);
+    assert_eq!(token::Client::new(&env, &token).balance(&to), 1000);
+    assert_eq!(
+        client.get_commitment_state(&commitment),
+        Some(EscrowStatus::Spent)
+    );
+}
+
+#[test]
+fn test_withdraw_signed_tampered_amount_fails() {
+    use crate::errors::QuickexError;
+    use soroban_sdk::InvokeError;
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let key = ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]);
+    let pubkey = BytesN::from_array(&env, &key.verifying_key().to_bytes());
+    let to = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"signed_tamper_sl");
+    let commitment = setup_signer_escrow(&env, &client, &token, &key, 1000, &salt);
+
+    let signature = sign_withdrawal(&env, &client, &key, &commitment, &to, 1000);
+    let result = client.try_withdraw_signed(&to, &token, &999, &salt, &pubkey, &signature);
+    assert_eq!(result.err(), Some(Ok(QuickexError::CommitmentNotFound)));
+
+    // A signature over another amount does not verify for the real escrow
+    let signature = sign_withdrawal(&env, &client, &key, &commitment, &to, 999);
+    let result = client.try_withdraw_signed(&to, &token, &1000, &salt, &pubkey, &signature);
+    assert_eq!(result.err(), Some(Err(InvokeError::Abort)));
+
+    // Nor can the signed recipient be swapped
+    let signature = sign_withdrawal(&env, &client, &key, &commitment, &to, 1000);
+    let other = Address::generate(&env);
+    let result = client.try_withdraw_signed(&other, &token, &1000, &salt, &pubkey, &signature);
+    assert_eq!(result.err(), Some(Err(InvokeError::Abort)));
+
+    assert_eq!(
+        client.get_commitment_state(&commitment),
+        Some(EscrowStatus::Pending)
+    );
+}
+
+#[test]
+fn test_withdraw_signed_wrong_key_fails() {
+    use crate::errors::QuickexError;
+    use soroban_sdk::InvokeError;
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let key = ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]);
+    let pubkey = BytesN::from_array(&env, &key.verifying_key().to_bytes());
+    let wrong_key = ed25519_dalek::SigningKey::from_bytes(&[8u8; 32]);
+    let wrong_pubkey = BytesN::from_array(&env, &wrong_key.verifying_key().to_bytes());
+    let to = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"signed_wrongkey1");
+    let commitment = setup_signer_escrow(&env, &client, &token, &key, 1000, &salt);
+
+    // Signed by the wrong key but presented with the committed pubkey
+    let signature = sign_withdrawal(&env, &client, &wrong_key, &commitment, &to, 1000);
+    let result = client.try_withdraw_signed(&to, &token, &1000, &salt, &pubkey, &signature);
+    assert_eq!(result.err(), Some(Err(InvokeError::Abort)));
+
+    // Presented with the wrong pubkey, the commitment does not open
+    let result = client.try_withdraw_signed(&to, &token, &1000, &salt, &wrong_pubkey, &signature);
+    assert_eq!(result.err(), Some(Ok(QuickexError::CommitmentNotFound)));
+
+    assert_eq!(
+        client.get_commitment_state(&commitment),
+        Some(EscrowStatus::Pending)
+    );
+    assert_eq!(token::Client::new(&env, &token).balance(&to), 0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Crypto, InvalidInput)")]
+fn test_withdraw_signed_bad_signature_traps_in_host() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let key = ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]);
+    let pubkey = BytesN::from_array(&env, &key.verifying_key().to_bytes());
+    let wrong_key = ed25519_dalek::SigningKey::from_bytes(&[8u8; 32]);
+    let to = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"signed_trap_salt");
+    let commitment = setup_signer_escrow(&env, &client, &token, &key, 1000, &salt);
+
+    let signature = sign_withdrawal(&env, &client, &wrong_key, &commitment, &to, 1000);
+    client.withdraw_signed(&to, &token, &1000, &salt, &pubkey, &signature);
+}
+
+#[test]
+fn test_top_up_moves_escrow_to_new_commitment() {
+    use crate::errors::QuickexError;
+    use crate::events::EscrowToppedUpEvent;
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let owner = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &1500);
+    let salt = Bytes::from_slice(&env, b"top_up_old_salt1");
+    let new_salt = Bytes::from_slice(&env, b"top_up_new_salt1");
+
+    let old_commitment = client.deposit(&token, &1000, &owner, &salt);
+    let new_commitment = client.top_up(&owner, &old_commitment, &500, &new_salt);
+    assert!(has_event(
+        &env,
+        &client.address,
+        &EscrowToppedUpEvent {
+            old_commitment: old_commitment.clone(),
+            new_commitment: new_commitment.clone(),
+            timestamp: env.ledger().timestamp(),
+        }
+    ));
+    assert_eq!(
+        new_commitment,
+        client.create_amount_commitment(&owner, &token, &1500, &new_salt)
+    );
+    assert_eq!(
+        client.get_commitment_state(&old_commitment),
+        Some(EscrowStatus::Spent)
+    );
+    assert_eq!(
+        client
+            .get_escrow_details(&new_commitment, &false)
+            .unwrap()
+            .amount,
+        1500
+    );
+    assert_eq!(client.get_total_escrowed(&token), 1500);
+
+    // The old commitment is nullified
+    assert_eq!(
+        client
+            .try_withdraw(&token, &1000, &old_commitment, &owner, &salt)
+            .err(),
+        Some(Ok(QuickexError::AlreadySpent))
+    );
+    assert_eq!(
+        client
+            .try_top_up(
+                &owner,
+                &old_commitment,
+                &1,
+                &Bytes::from_slice(&env, b"top_up_new_salt2")
+            )
+            .err(),
+        Some(Ok(QuickexError::AlreadySpent))
+    );
+
+    let receipt = client.withdraw(&token, &1500, &new_commitment, &owner, &new_salt);
+    assert_eq!(receipt.amount_out, 1500);
+    assert_eq!(token::Client::new(&env, &token).balance(&owner), 1500);
+    assert_eq!(token::Client::new(&env, &token).balance(&client.address), 0);
+}
+
+#[test]
+fn test_top_up_rejects_non_owner_and_reused_salt() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let owner = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &1000);
+    token::StellarAssetClient::new(&env, &token).mint(&stranger, &1000);
+    let salt = Bytes::from_slice(&env, b"top_up_reject_01");
+    let new_salt = Bytes::from_slice(&env, b"top_up_reject_02");
+
+    let commitment = client.deposit(&token, &500, &owner, &salt);
+    assert_eq!(
+        client
+            .try_top_up(&stranger, &commitment, &100, &new_salt)
+            .err(),
+        Some(Ok(QuickexError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_top_up(&owner, &commitment, &100, &salt).err(),
+        Some(Ok(QuickexError::SaltReused))
+    );
+    assert_eq!(
+        client.try_top_up(&owner, &commitment, &0, &new_salt).err(),
+        Some(Ok(QuickexError::InvalidAmount))
+    );
+    assert_eq!(
+        client.get_commitment_state(&commitment),
+        Some(EscrowStatus::Pending)
+    );
+    assert_eq!(token::Client::new(&env, &token).balance(&stranger), 1000);
+}
+
+#[test]
+fn test_is_commitment_available() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let to = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"available_salt01");
+    let commitment = client.create_amount_commitment(&to, &token, &1000, &salt);
+    let available = |commitment: &BytesN<32>| {
+        env.as_contract(&client.address, || {
+            crate::escrow::is_commitment_available(&env, commitment)
+        })
+    };
+
+    assert!(available(&commitment));
+
+    setup_withdrawable_escrow(&env, &client, &token, &to, 1000, &salt);
+    assert!(!available(&commitment));
+
+    // Spending nullifies the commitment; it stays unavailable for good
+    client.withdraw(&token, &1000, &commitment, &to, &salt);
+    assert!(!available(&commitment));
+    let depositor = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &1000);
+    assert_eq!(
+        client
+            .try_deposit_with_commitment(&depositor, &token, &1000, &commitment)
+            .err(),
+        Some(Ok(QuickexError::CommitmentAlreadyExists))
+    );
+}
+
+#[test]
+fn test_transfer_escrow_only_new_commitment_withdraws() {
+    use crate::errors::QuickexError;
+    use crate::events::EscrowTransferredEvent;
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &1000);
+    let alice_salt = Bytes::from_slice(&env, b"transfer_alice_1");
+    let bob_salt = Bytes::from_slice(&env, b"transfer_bob_001");
+
+    let commitment = client.create_amount_commitment(&alice, &token, &1000, &alice_salt);
+    client.deposit_with_commitment(&depositor, &token, &1000, &commitment);
+
+    // Bob computes his commitment and shares only the hash
+    let bob_commitment = client.create_amount_commitment(&bob, &token, &1000, &bob_salt);
+    client.transfer_escrow(&depositor, &commitment, &bob, &bob_commitment);
+    assert!(has_event(
+        &env,
+        &client.address,
+        &EscrowTransferredEvent {
+            old_commitment: commitment.clone(),
+            new_commitment: bob_commitment.clone(),
+            timestamp: env.ledger().timestamp(),
+        }
+    ));
+    assert_eq!(
+        client.get_commitment_state(&commitment),
+        Some(EscrowStatus::Spent)
+    );
+    let entry = client.get_escrow_details(&bob_commitment, &false).unwrap();
+    assert_eq!(entry.owner, bob);
+    assert_eq!(entry.amount, 1000);
+    assert_eq!(client.get_total_escrowed(&token), 1000);
+    assert!(client.get_my_escrows(&depositor).is_empty());
+    let escrows = client.get_my_escrows(&bob);
+    assert_eq!(escrows.len(), 1);
+    assert_eq!(escrows.get(0).unwrap().0, bob_commitment);
+
+    assert_eq!(
+        client
+            .try_withdraw(&token, &1000, &commitment, &alice, &alice_salt)
+            .err(),
+        Some(Ok(QuickexError::AlreadySpent))
+    );
+    client.withdraw(&token, &1000, &bob_commitment, &bob, &bob_salt);
+    assert_eq!(token::Client::new(&env, &token).balance(&bob), 1000);
+    assert_eq!(token::Client::new(&env, &token).balance(&alice), 0);
+}
+
+#[test]
+fn test_transfer_escrow_cannot_be_revoked_by_old_owner() {
+    use crate::errors::QuickexError;
+    use soroban_sdk::testutils::Ledger as _;
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &1000);
+    let commitment = BytesN::from_array(&env, &[54; 32]);
+    client.deposit_with_expiry(&depositor, &token, &1000, &commitment, &100);
+    let bob_commitment = BytesN::from_array(&env, &[55; 32]);
+    client.transfer_escrow(&depositor, &commitment, &bob, &bob_commitment);
+
+    assert_eq!(
+        client.try_cancel_deposit(&depositor, &bob_commitment),
+        Err(Ok(QuickexError::Unauthorized))
+    );
+    env.ledger().with_mut(|li| li.timestamp += 100);
+    assert_eq!(
+        client.try_refund_expired(&depositor, &bob_commitment),
+        Err(Ok(QuickexError::Unauthorized))
+    );
+
+    // The owner rights moved with the escrow
+    client.refund_expired(&bob, &bob_commitment);
+    let balance = token::Client::new(&env, &token);
+    assert_eq!(balance.balance(&bob), 1000);
+    assert_eq!(balance.balance(&depositor), 0);
+}
+
+#[test]
+fn test_transfer_escrow_rejects_non_owner_and_existing_commitment() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &2000);
+    let first = BytesN::from_array(&env, &[51; 32]);
+    let second = BytesN::from_array(&env, &[52; 32]);
+    let target = BytesN::from_array(&env, &[53; 32]);
+    client.deposit_with_commitment(&depositor, &token, &1000, &first);
+    client.deposit_with_commitment(&depositor, &token, &1000, &second);
+
+    assert_eq!(
+        client.try_transfer_escrow(&stranger, &first, &stranger, &target),
+        Err(Ok(QuickexError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_transfer_escrow(&depositor, &first, &stranger, &second),
+        Err(Ok(QuickexError::CommitmentAlreadyExists))
+    );
+    assert_eq!(
+        client.try_transfer_escrow(&depositor, &target, &stranger, &first),
+        Err(Ok(QuickexError::CommitmentNotFound))
+    );
+    assert_eq!(
+        client.get_commitment_state(&first),
+        Some(EscrowStatus::Pending)
+    );
+    assert_eq!(client.get_commitment_state(&target), None);
+}
+
+#[test]
+fn test_withdraw_cancelled_escrow_fails() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    let to = Address::generate(&env);
+    let amount: i128 = 1000;
+    let salt = Bytes::from_slice(&env, b"cancelled_withdraw");
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &amount);
+
+    let commitment = client.create_amount_commitment(&to, &token, &amount, &salt);
+    client.deposit_with_commitment(&depositor, &token, &amount, &commitment);
+    client.cancel_deposit(&depositor, &commitment);
+
+    let result = client.try_withdraw(&token, &amount, &commitment, &to, &salt);
+    assert_eq!(result, Err(Ok(QuickexError::EscrowCancelled)));
+}
+
+// ============================================================================
+// Private Withdrawal Tests
+// ============================================================================
+
+#[test]
+fn test_withdraw_private_pays_recipient_and_enables_privacy() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let owner = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let amount: i128 = 1000;
+    let salt = Bytes::from_slice(&env, b"private_withdraw_salt");
+
+    let commitment = setup_withdrawable_escrow(&env, &client, &token, &owner, amount, &salt);
+    assert!(!client.get_privacy(&recipient));
+
+    assert!(client.withdraw_private(&owner, &recipient, &token, &amount, &salt));
+
+    let token_client = token::Client::new(&env, &token);
+    assert_eq!(token_client.balance(&recipient), amount);
+    assert_eq!(token_client.balance(&owner), 0);
+    assert!(client.get_privacy(&recipient));
+    assert_eq!(
+        client.get_commitment_state(&commitment),
+        Some(EscrowStatus::Spent)
+    );
+}
+
+#[test]
+fn test_withdraw_private_with_wrong_owner_fails() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let owner = Address::generate(&env);
+    let other = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let amount: i128 = 1000;
+    let salt = Bytes::from_slice(&env, b"private_wrong_owner");
+
+    setup_withdrawable_escrow(&env, &client, &token, &owner, amount, &salt);
+
+    let result = client.try_withdraw_private(&other, &recipient, &token, &amount, &salt);
+    assert_eq!(result, Err(Ok(QuickexError::CommitmentNotFound)));
+    assert!(!client.get_privacy(&recipient));
+}
+
+// ============================================================================
+// Memo Tests
+// ============================================================================
+
+#[test]
+fn test_update_memo() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &1000);
+
+    let commitment = BytesN::from_array(&env, &[20; 32]);
+    client.deposit_with_commitment(&depositor, &token, &1000, &commitment);
+    assert_eq!(client.get_escrow_memo(&commitment), None);
+
+    let memo = Bytes::from_slice(&env, b"encrypted-note-v1");
+    client.update_memo(&depositor, &commitment, &memo);
+    assert_eq!(client.get_escrow_memo(&commitment), Some(memo));
+
+    let revised = Bytes::from_slice(&env, b"encrypted-note-v2");
+    client.update_memo(&depositor, &commitment, &revised);
+    assert_eq!(client.get_escrow_memo(&commitment), Some(revised));
+}
+
+#[test]
+fn test_update_memo_on_spent_escrow_fails() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &1000);
+
+    let commitment = BytesN::from_array(&env, &[21; 32]);
+    client.deposit_with_commitment(&depositor, &token, &1000, &commitment);
+    client.cancel_deposit(&depositor, &commitment);
+
+    let memo = Bytes::from_slice(&env, b"too-late");
+    let result = client.try_update_memo(&depositor, &commitment, &memo);
+    assert_eq!(result, Err(Ok(QuickexError::AlreadySpent)));
+    assert_eq!(client.get_escrow_memo(&commitment), None);
+}
+
+#[test]
+fn test_update_memo_too_long_fails() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &1000);
+
+    let commitment = BytesN::from_array(&env, &[22; 32]);
+    client.deposit_with_commitment(&depositor, &token, &1000, &commitment);
+
+    let memo = Bytes::from_array(&env, &[0u8; 257]);
+    let result = client.try_update_memo(&depositor, &commitment, &memo);
+    assert_eq!(result, Err(Ok(QuickexError::MemoTooLong)));
+}
+
+#[test]
+fn test_deposit_with_memo_round_trips() {
+    use crate::events::DepositToggledEvent;
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &1000);
+
+    let commitment = BytesN::from_array(&env, &[23; 32]);
+    let memo = Bytes::from_array(&env, &[7u8; 256]);
+    client.deposit_with_memo(&depositor, &token, &1000, &commitment, &memo);
+
+    let expected = DepositToggledEvent {
+        token: token.clone(),
+        commitment: commitment.clone(),
+        amount: 1000,
+        memo_hash: Some(env.crypto().sha256(&memo).into()),
+    };
+    assert!(has_event(&env, &client.address, &expected));
+    assert_eq!(client.get_escrow_memo(&commitment), Some(memo));
+}
+
+#[test]
+fn test_deposit_with_memo_too_long_fails() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &1000);
+
+    let commitment = BytesN::from_array(&env, &[24; 32]);
+    let memo = Bytes::from_array(&env, &[0u8; 257]);
+    let result = client.try_deposit_with_memo(&depositor, &token, &1000, &commitment, &memo);
+    assert_eq!(result, Err(Ok(QuickexError::MemoTooLong)));
+    assert!(client.get_escrow_details(&commitment, &false).is_none());
+    assert_eq!(token::Client::new(&env, &token).balance(&depositor), 1000);
+}
+
+// ============================================================================
+// Viewing Key Tests
+// ============================================================================
+
+#[test]
+fn test_view_key_grants_view_access() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &1000);
+
+    let commitment = BytesN::from_array(&env, &[25; 32]);
+    client.deposit_with_commitment(&depositor, &token, &1000, &commitment);
+
+    let view_key = Bytes::from_slice(&env, b"auditor viewing key");
+    assert!(!client.verify_view_access(&commitment, &view_key));
+
+    let hash: BytesN<32> = env.crypto().sha256(&view_key).into();
+    client.set_view_key(&depositor, &commitment, &hash);
+    assert!(client.verify_view_access(&commitment, &view_key));
+    assert!(!client.verify_view_access(&commitment, &Bytes::from_slice(&env, b"wrong viewing key")));
+    assert!(!client.verify_view_access(&BytesN::from_array(&env, &[26; 32]), &view_key));
+}
+
+#[test]
+fn test_set_view_key_by_non_owner_fails() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &1000);
+
+    let commitment = BytesN::from_array(&env, &[27; 32]);
+    client.deposit_with_commitment(&depositor, &token, &1000, &commitment);
+
+    let view_key = Bytes::from_slice(&env, b"stranger view key");
+    let hash: BytesN<32> = env.crypto().sha256(&view_key).into();
+    let result = client.try_set_view_key(&stranger, &commitment, &hash);
+    assert_eq!(result, Err(Ok(QuickexError::Unauthorized)));
+    assert!(!client.verify_view_access(&commitment, &view_key));
+
+    let missing = BytesN::from_array(&env, &[28; 32]);
+    let result = client.try_set_view_key(&depositor, &missing, &hash);
+    assert_eq!(result, Err(Ok(QuickexError::CommitmentNotFound)));
+}
+
+// ============================================================================
+// Amount Bounds Tests
+// ============================================================================
+
+#[test]
+fn test_deposit_respects_amount_bounds() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let token = create_test_token(&env);
+    let from = Address::generate(&env);
+    client.initialize(&admin, &None);
+    token::StellarAssetClient::new(&env, &token).mint(&from, &10_000);
+
+    client.set_amount_bounds(&admin, &token, &100, &1_000);
+
+    let below = client.try_deposit_with_commitment(
+        &from,
+        &token,
+        &99,
+        &BytesN::from_array(&env, &[30; 32]),
+    );
+    assert_eq!(below, Err(Ok(QuickexError::AmountBelowMinimum)));
+
+    let above = client.try_deposit_with_commitment(
+        &from,
+        &token,
+        &1_001,
+        &BytesN::from_array(&env, &[31; 32]),
+    );
+    assert_eq!(above, Err(Ok(QuickexError::AmountAboveMaximum)));
+
+    client.deposit_with_commitment(&from, &token, &100, &BytesN::from_array(&env, &[32; 32]));
+    client.deposit_with_commitment(&from, &token, &1_000, &BytesN::from_array(&env, &[33; 32]));
+
+    let salt = Bytes::from_slice(&env, b"bounds_salt_0001");
+    let salted = client.try_deposit(&token, &5_000, &from, &salt);
+    assert_eq!(salted, Err(Ok(QuickexError::AmountAboveMaximum)));
+}
+
+#[test]
+fn test_unconfigured_token_bounds_are_permissive() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let token = create_test_token(&env);
+    let other_token = create_test_token(&env);
+    let from = Address::generate(&env);
+    client.initialize(&admin, &None);
+    token::StellarAssetClient::new(&env, &other_token).mint(&from, &1);
+
+    client.set_amount_bounds(&admin, &token, &100, &1_000);
+
+    let bounds = client.get_amount_bounds(&other_token);
+    assert_eq!(bounds.min, 1);
+    assert_eq!(bounds.max, i128::MAX);
+
+    client.deposit_with_commitment(
+        &from,
+        &other_token,
+        &1,
+        &BytesN::from_array(&env, &[34; 32]),
+    );
+}
+
+#[test]
+fn test_set_amount_bounds_rejects_inverted_range() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let token = create_test_token(&env);
+    client.initialize(&admin, &None);
+
+    let result = client.try_set_amount_bounds(&admin, &token, &1_000, &100);
+    assert_eq!(result, Err(Ok(QuickexError::InvalidAmount)));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_set_amount_bounds_by_non_admin_fails() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let non_admin = Address::generate(&env);
+    let token = create_test_token(&env);
+    client.initialize(&admin, &None);
+
+    client.set_amount_bounds(&non_admin, &token, &100, &1_000);
+}
+
+// ============================================================================
+// Token Allowlist Tests
+// ============================================================================
+
+#[test]
+fn test_token_allowlist() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let token = create_test_token(&env);
+    let other_token = create_test_token(&env);
+    let from = Address::generate(&env);
+    client.initialize(&admin, &None);
+    token::StellarAssetClient::new(&env, &token).mint(&from, &1_000);
+    token::StellarAssetClient::new(&env, &other_token).mint(&from, &1_000);
+
+    // Empty allowlist accepts every token
+    assert!(client.is_token_allowed(&other_token));
+
+    client.add_allowed_token(&admin, &token);
+    assert!(client.is_token_allowed(&token));
+    assert!(!client.is_token_allowed(&other_token));
+
+    client.deposit_with_commitment(&from, &token, &100, &BytesN::from_array(&env, &[40; 32]));
+
+    let unlisted = client.try_deposit_with_commitment(
+        &from,
+        &other_token,
+        &100,
+        &BytesN::from_array(&env, &[41; 32]),
+    );
+    assert_eq!(unlisted, Err(Ok(QuickexError::TokenNotAllowed)));
+
+    // Keep another token listed so removing `token` does not re-open the allowlist
+    client.add_allowed_token(&admin, &other_token);
+    client.remove_allowed_token(&admin, &token);
+    assert!(!client.is_token_allowed(&token));
+
+    let removed = client.try_deposit_with_commitment(
+        &from,
+        &token,
+        &100,
+        &BytesN::from_array(&env, &[42; 32]),
+    );
+    assert_eq!(removed, Err(Ok(QuickexError::TokenNotAllowed)));
+}
+
+#[test]
+fn test_removing_last_allowed_token_reopens_deposits() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let token = create_test_token(&env);
+    let other_token = create_test_token(&env);
+    client.initialize(&admin, &None);
+
+    client.add_allowed_token(&admin, &token);
+    client.add_allowed_token(&admin, &token);
+    assert!(!client.is_token_allowed(&other_token));
+
+    client.remove_allowed_token(&admin, &token);
+    assert!(client.is_token_allowed(&other_token));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #2)")]
+fn test_add_allowed_token_by_non_admin_fails() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let non_admin = Address::generate(&env);
+    let token = create_test_token(&env);
+    client.initialize(&admin, &None);
+
+    client.add_allowed_token(&non_admin, &token);
+}
+
+// ============================================================================
+// Basket Deposit Tests
+// ============================================================================
+
+fn basket(env: &Env, tokens: &[Address], seed: u8) -> soroban_sdk::Vec<BasketItem> {
+    let mut items = soroban_sdk::Vec::new(env);
+    for (i, token) in tokens.iter().enumerate() {
+        items.push_back(BasketItem {
+            token: token.clone(),
+            amount: 100,
+            commitment: BytesN::from_array(env, &[seed + i as u8; 32]),
+        });
+    }
+    items
+}
+
+#[test]
+fn test_deposit_basket_at_max_size() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let from = Address::generate(&env);
+    client.initialize(&admin, &None);
+    client.set_max_basket_size(&admin, &2);
+    assert_eq!(client.get_max_basket_size(), 2);
+
+    let token_a = create_test_token(&env);
+    let token_b = create_test_token(&env);
+    token::StellarAssetClient::new(&env, &token_a).mint(&from, &100);
+    token::StellarAssetClient::new(&env, &token_b).mint(&from, &100);
+
+    let items = basket(&env, &[token_a.clone(), token_b.clone()], 50);
+    client.deposit_basket(&from, &items);
+
+    for item in items.iter() {
+        let entry = client.get_escrow_details(&item.commitment, &false).unwrap();
+        assert_eq!(entry.token, item.token);
+        assert_eq!(entry.status, EscrowStatus::Pending);
+    }
+    assert_eq!(
+        token::Client::new(&env, &token_a).balance(&client.address),
+        100
+    );
+    assert_eq!(
+        token::Client::new(&env, &token_b).balance(&client.address),
+        100
+    );
+}
+
+#[test]
+fn test_deposit_basket_over_max_size_fails() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let from = Address::generate(&env);
+    client.initialize(&admin, &None);
+    client.set_max_basket_size(&admin, &2);
+
+    let tokens = [
+        create_test_token(&env),
+        create_test_token(&env),
+        create_test_token(&env),
+    ];
+    for token in tokens.iter() {
+        token::StellarAssetClient::new(&env, token).mint(&from, &100);
+    }
+
+    let items = basket(&env, &tokens, 60);
+    let result = client.try_deposit_basket(&from, &items);
+    assert_eq!(result, Err(Ok(QuickexError::InvalidInput)));
+    assert!(client
+        .get_escrow_details(&items.get(0).unwrap().commitment, &false)
+        .is_none());
+}
+
+#[test]
+fn test_deposit_basket_empty_fails() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let from = Address::generate(&env);
+
+    let result = client.try_deposit_basket(&from, &soroban_sdk::Vec::new(&env));
+    assert_eq!(result, Err(Ok(QuickexError::InvalidInput)));
+}
+
+#[test]
+fn test_deposit_split_each_recipient_withdraws_own_share() {
+    use crate::errors::QuickexError;
+    use crate::types::SplitShare;
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let from = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&from, &1_000);
+
+    let alice_salt = Bytes::from_slice(&env, b"split_alice_salt");
+    let bob_salt = Bytes::from_slice(&env, b"split_bob_salt01");
+    let shares = soroban_sdk::vec![
+        &env,
+        SplitShare {
+            commitment: client.create_amount_commitment(&alice, &token, &600, &alice_salt),
+            amount: 600,
+        },
+        SplitShare {
+            commitment: client.create_amount_commitment(&bob, &token, &400, &bob_salt),
+            amount: 400,
+        },
+    ];
+    client.deposit_split(&from, &token, &1_000, &shares);
+
+    let token_client = token::Client::new(&env, &token);
+    assert_eq!(token_client.balance(&client.address), 1_000);
+
+    let alice_commitment = shares.get(0).unwrap().commitment;
+    client.withdraw(&token, &600, &alice_commitment, &alice, &alice_salt);
+    assert_eq!(token_client.balance(&alice), 600);
+    assert_eq!(
+        client.get_commitment_state(&shares.get(1).unwrap().commitment),
+        Some(EscrowStatus::Pending)
+    );
+
+    let bob_commitment = shares.get(1).unwrap().commitment;
+    client.withdraw(&token, &400, &bob_commitment, &bob, &bob_salt);
+    assert_eq!(token_client.balance(&bob), 400);
+
+    // A third recipient without a commitment of their own cannot claim anything
+    let carol_salt = Bytes::from_slice(&env, b"split_carol_salt");
+    let result = client.try_withdraw(&token, &400, &bob_commitment, &carol, &carol_salt);
+    assert_eq!(result.err(), Some(Ok(QuickexError::CommitmentNotFound)));
+    assert_eq!(token_client.balance(&client.address), 0);
+}
+
+#[test]
+fn test_deposit_split_shares_must_total_amount() {
+    use crate::errors::QuickexError;
+    use crate::types::SplitShare;
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let from = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&from, &1_000);
+
+    let shares = soroban_sdk::vec![
+        &env,
+        SplitShare {
+            commitment: BytesN::from_array(&env, &[1; 32]),
+            amount: 600,
+        },
+        SplitShare {
+            commitment: BytesN::from_array(&env, &[2; 32]),
+            amount: 300,
+        },
+    ];
+    let result = client.try_deposit_split(&from, &token, &1_000, &shares);
+    assert_eq!(result, Err(Ok(QuickexError::InvalidAmount)));
+
+    let result = client.try_deposit_split(&from, &token, &0, &soroban_sdk::Vec::new(&env));
+    assert_eq!(result, Err(Ok(QuickexError::InvalidInput)));
+    assert_eq!(token::Client::new(&env, &token).balance(&from), 1_000);
+}
+
+#[test]
+fn test_deposit_same_salt_twice_fails() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let owner = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"repeated_salt001");
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &2_000);
+
+    client.deposit(&token, &1_000, &owner, &salt);
+    let result = client.try_deposit(&token, &500, &owner, &salt);
+    assert_eq!(result, Err(Ok(QuickexError::SaltReused)));
+    assert_eq!(token::Client::new(&env, &token).balance(&owner), 1_000);
+
+    // Salts are tracked per owner, so another owner may use the same one
+    let other = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&other, &1_000);
+    client.deposit(&token, &1_000, &other, &salt);
+}
+
+#[test]
+fn test_deposit_from_contract_address_fails() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&client.address, &1000);
+
+    let commitment = BytesN::from_array(&env, &[29; 32]);
+    let result = client.try_deposit_with_commitment(&client.address, &token, &1000, &commitment);
+    assert_eq!(result, Err(Ok(QuickexError::InvalidInput)));
+
+    let salt = Bytes::from_slice(&env, b"self_deposit_s01");
+    let result = client.try_deposit(&token, &1000, &client.address, &salt);
+    assert_eq!(result, Err(Ok(QuickexError::InvalidInput)));
+    assert!(client.get_escrow_details(&commitment, &false).is_none());
+    assert_eq!(client.get_total_escrowed(&token), 0);
+}
+
+#[test]
+fn test_identical_deposit_retry_is_idempotent() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let owner = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &3_000);
+    let token_client = token::Client::new(&env, &token);
+
+    let salt = Bytes::from_slice(&env, b"retried_salt_001");
+    let first = client.deposit(&token, &1_000, &owner, &salt);
+    assert_eq!(client.deposit(&token, &1_000, &owner, &salt), first);
+    assert_eq!(token_client.balance(&owner), 2_000);
+
+    let commitment = client.create_amount_commitment(&recipient, &token, &1_000, &salt);
+    client.deposit_with_commitment(&owner, &token, &1_000, &commitment);
+    client.deposit_with_commitment(&owner, &token, &1_000, &commitment);
+    assert_eq!(token_client.balance(&owner), 1_000);
+    assert_eq!(client.get_contract_stats().total_deposits, 2);
+}
+
+#[test]
+fn test_conflicting_commitment_reuse_fails() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let owner = Address::generate(&env);
+    let other = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &3_000);
+    token::StellarAssetClient::new(&env, &token).mint(&other, &1_000);
+
+    let salt = Bytes::from_slice(&env, b"conflict_salt001");
+    let commitment = client.create_amount_commitment(&recipient, &token, &1_000, &salt);
+    client.deposit_with_commitment(&owner, &token, &1_000, &commitment);
+
+    let result = client.try_deposit_with_commitment(&owner, &token, &999, &commitment);
+    assert_eq!(result, Err(Ok(QuickexError::CommitmentAlreadyExists)));
+    let result = client.try_deposit_with_commitment(&other, &token, &1_000, &commitment);
+    assert_eq!(result, Err(Ok(QuickexError::CommitmentAlreadyExists)));
+
+    // Once spent, the same parameters are a new deposit rather than a retry
+    client.withdraw(&token, &1_000, &commitment, &recipient, &salt);
+    let result = client.try_deposit_with_commitment(&owner, &token, &1_000, &commitment);
+    assert_eq!(result, Err(Ok(QuickexError::CommitmentAlreadyExists)));
+    assert_eq!(token::Client::new(&env, &token).balance(&owner), 2_000);
+}
+
+/// Whether `needle` occurs anywhere in the contract events of the last invocation
+fn contract_events_contain(env: &Env, needle: &[u8]) -> bool {
+    use soroban_sdk::xdr::{ContractEventType, Limits, WriteXdr};
+
+    env.host().get_events().unwrap().0.iter().any(|e| {
+        e.event.type_ == ContractEventType::Contract
+            && e.event
+                .to_xdr(Limits::none())
+                .unwrap()
+                .windows(needle.len())
+                .any(|w| w == needle)
+    })
+}
+
+#[test]
+fn test_raw_salt_never_stored_or_emitted() {
+    use soroban_sdk::xdr::{Limits, WriteXdr};
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let owner = Address::generate(&env);
+    let relayer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &2_000);
+    let raw = *b"never_leak_this_salt";
+    let salt = Bytes::from_slice(&env, &raw);
+    let other_raw = *b"neither_leak_this_01";
+    let other_salt = Bytes::from_slice(&env, &other_raw);
+
+    client.deposit(&token, &1_000, &owner, &salt);
+    assert!(!contract_events_contain(&env, &raw));
+    client.deposit(&token, &1_000, &owner, &other_salt);
+    client.withdraw(
+        &token,
+        &1_000,
+        &BytesN::from_array(&env, &[0; 32]),
+        &owner,
+        &salt,
+    );
+    assert!(!contract_events_contain(&env, &raw));
+    client.withdraw_relayed(&relayer, &owner, &token, &1_000, &other_salt, &10);
+    assert!(!contract_events_contain(&env, &other_raw));
+
+    // Reuse is still detected from the stored hash
+    assert!(client.try_deposit(&token, &1_000, &owner, &salt).is_err());
+
+    for (key, (entry, _)) in env.to_ledger_snapshot().ledger_entries {
+        for bytes in [
+            key.to_xdr(Limits::none()).unwrap(),
+            entry.to_xdr(Limits::none()).unwrap(),
+        ] {
+            assert!(!bytes.windows(raw.len()).any(|w| w == raw));
+            assert!(!bytes.windows(other_raw.len()).any(|w| w == other_raw));
+        }
+    }
+}
+
+#[test]
+fn test_max_pending_per_owner_limits_deposits() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let token = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    client.initialize(&admin, &None);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &4_000);
+
+    let result = client.try_set_max_pending_per_owner(&depositor, &2);
+    assert_eq!(result, Err(Ok(QuickexError::Unauthorized)));
+    client.set_max_pending_per_owner(&admin, &2);
+    assert_eq!(client.get_max_pending_per_owner(), 2);
+
+    let first = BytesN::from_array(&env, &[81; 32]);
+    client.deposit_with_commitment(&depositor, &token, &1_000, &first);
+    client.deposit_with_commitment(
+        &depositor,
+        &token,
+        &1_000,
+        &BytesN::from_array(&env, &[82; 32]),
+    );
+    let third = BytesN::from_array(&env, &[83; 32]);
+    let result = client.try_deposit_with_commitment(&depositor, &token, &1_000, &third);
+    assert_eq!(result, Err(Ok(QuickexError::TooManyPendingEscrows)));
+
+    // The limit is per owner
+    let other = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&other, &1_000);
+    client.deposit_with_commitment(&other, &token, &1_000, &BytesN::from_array(&env, &[84; 32]));
+
+    client.cancel_deposit(&depositor, &first);
+    client.deposit_with_commitment(&depositor, &token, &1_000, &third);
+}
+
+#[test]
+fn test_deposit_with_deadline() {
+    use crate::errors::QuickexError;
+    use soroban_sdk::testutils::Ledger as _;
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &2_000);
+    env.ledger().set_timestamp(1_000);
+
+    // The deadline itself is still in time
+    let on_time = BytesN::from_array(&env, &[85; 32]);
+    client.deposit_with_deadline(&depositor, &token, &1_000, &on_time, &1_000);
+    assert_eq!(
+        client.get_commitment_state(&on_time),
+        Some(EscrowStatus::Pending)
+    );
+
+    let late = BytesN::from_array(&env, &[86; 32]);
+    let result = client.try_deposit_with_deadline(&depositor, &token, &1_000, &late, &999);
+    assert_eq!(result, Err(Ok(QuickexError::DepositDeadlinePassed)));
+    assert_eq!(token::Client::new(&env, &token).balance(&depositor), 1_000);
+}
+
+// ============================================================================
+// Expiry Tests
+// ============================================================================
+
+#[test]
+fn test_deposit_applies_default_expiry() {
+    use crate::types::DEFAULT_EXPIRY_SECS;
+    use soroban_sdk::testutils::Ledger as _;
+
+    let (env, client) = setup();
+    env.ledger().set_timestamp(1_000);
+    let token = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &1000);
+
+    let commitment = BytesN::from_array(&env, &[40; 32]);
+    client.deposit_with_commitment(&depositor, &token, &1000, &commitment);
+
+    let entry = client.get_escrow_details(&commitment, &false).unwrap();
+    assert_eq!(entry.expires_at, 1_000 + DEFAULT_EXPIRY_SECS);
+}
+
+#[test]
+fn test_admin_default_expiry_applies_to_new_deposits() {
+    use crate::errors::QuickexError;
+    use crate::types::DEFAULT_EXPIRY_SECS;
+    use soroban_sdk::testutils::Ledger as _;
+
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &None);
+    env.ledger().set_timestamp(1_000);
+    let token = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &3000);
+
+    assert_eq!(client.get_default_expiry(), DEFAULT_EXPIRY_SECS);
+    let result = client.try_set_default_expiry(&depositor, &3_600);
+    assert_eq!(result, Err(Ok(QuickexError::Unauthorized)));
+
+    client.set_default_expiry(&admin, &3_600);
+    assert_eq!(client.get_default_expiry(), 3_600);
+
+    let commitment = BytesN::from_array(&env, &[41; 32]);
+    client.deposit_with_commitment(&depositor, &token, &1000, &commitment);
+    let entry = client.get_escrow_details(&commitment, &false).unwrap();
+    assert_eq!(entry.expires_at, 1_000 + 3_600);
+
+    // An explicit per-deposit expiry still wins
+    let explicit = BytesN::from_array(&env, &[42; 32]);
+    client.deposit_with_expiry(&depositor, &token, &1000, &explicit, &60);
+    assert_eq!(
+        client
+            .get_escrow_details(&explicit, &false)
+            .unwrap()
+            .expires_at,
+        1_000 + 60
+    );
+
+    client.set_default_expiry(&admin, &0);
+    let forever = BytesN::from_array(&env, &[43; 32]);
+    client.deposit_with_commitment(&depositor, &token, &1000, &forever);
+    assert_eq!(
+        client
+            .get_escrow_details(&forever, &false)
+            .unwrap()
+            .expires_at,
+        NO_EXPIRY
+    );
+}
+
+#[test]
+fn test_withdraw_after_expiry_fails() {
+    use crate::errors::QuickexError;
+    use soroban_sdk::testutils::Ledger as _;
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    let to = Address::generate(&env);
+    let amount: i128 = 1000;
+    let salt = Bytes::from_slice(&env, b"expiry_withdraw1");
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &amount);
+
+    let commitment = client.create_amount_commitment(&to, &token, &amount, &salt);
+    client.deposit_with_expiry(&depositor, &token, &amount, &commitment, &100);
+    assert!(client.verify_proof_view(&token, &amount, &salt, &to));
+
+    env.ledger().set_timestamp(env.ledger().timestamp() + 100);
+    assert!(!client.verify_proof_view(&token, &amount, &salt, &to));
+
+    let result = client.try_withdraw(&token, &amount, &commitment, &to, &salt);
+    assert_eq!(result, Err(Ok(QuickexError::EscrowExpired)));
+}
+
+#[test]
+fn test_withdraw_without_expiry_never_expires() {
+    use soroban_sdk::testutils::Ledger as _;
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    let to = Address::generate(&env);
+    let amount: i128 = 1000;
+    let salt = Bytes::from_slice(&env, b"no_expiry_withdraw");
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &amount);
+
+    let commitment = client.create_amount_commitment(&to, &token, &amount, &salt);
+    client.deposit_with_expiry(&depositor, &token, &amount, &commitment, &0);
+    assert_eq!(
+        client
+            .get_escrow_details(&commitment, &false)
+            .unwrap()
+            .expires_at,
+        NO_EXPIRY
+    );
+
+    env.ledger().set_timestamp(u64::MAX / 2);
+    assert_eq!(
+        client
+            .withdraw(&token, &amount, &commitment, &to, &salt)
+            .status,
+        EscrowStatus::Spent
+    );
+    assert_eq!(token::Client::new(&env, &token).balance(&to), amount);
+}
+
+#[test]
+fn test_refund_expired_returns_funds_to_depositor() {
+    use crate::events::EscrowRefundedEvent;
+    use soroban_sdk::testutils::Ledger as _;
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &1000);
+
+    let commitment = BytesN::from_array(&env, &[41; 32]);
+    client.deposit_with_expiry(&depositor, &token, &1000, &commitment, &50);
+
+    env.ledger().set_timestamp(env.ledger().timestamp() + 50);
+    client.refund_expired(&depositor, &commitment);
+
+    let expected = EscrowRefundedEvent {
+        owner: depositor.clone(),
+        commitment: commitment.clone(),
+        amount: 1000,
+        timestamp: env.ledger().timestamp(),
+    };
+    assert!(has_event(&env, &client.address, &expected));
+
+    assert_eq!(token::Client::new(&env, &token).balance(&depositor), 1000);
+    assert_eq!(
+        client.get_commitment_state(&commitment),
+        Some(EscrowStatus::Spent)
+    );
+}
+
+#[test]
+fn test_refund_before_expiry_fails() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &1000);
+
+    let commitment = BytesN::from_array(&env, &[42; 32]);
+    client.deposit_with_expiry(&depositor, &token, &1000, &commitment, &50);
+
+    let result = client.try_refund_expired(&depositor, &commitment);
+    assert_eq!(result, Err(Ok(QuickexError::EscrowNotExpired)));
+
+    let other = BytesN::from_array(&env, &[43; 32]);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &1000);
+    client.deposit_with_expiry(&depositor, &token, &1000, &other, &0);
+    let result = client.try_refund_expired(&depositor, &other);
+    assert_eq!(result, Err(Ok(QuickexError::EscrowNotExpired)));
+}
+
+#[test]
+fn test_refund_expired_by_non_owner_fails() {
+    use crate::errors::QuickexError;
+    use crate::events::AuthFailedEvent;
+    use soroban_sdk::testutils::Ledger as _;
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &1000);
+
+    let commitment = BytesN::from_array(&env, &[44; 32]);
+    client.deposit_with_expiry(&depositor, &token, &1000, &commitment, &10);
+    env.ledger().set_timestamp(env.ledger().timestamp() + 10);
+
+    let result = client.try_refund_expired(&stranger, &commitment);
+    assert_eq!(result, Err(Ok(QuickexError::Unauthorized)));
+    let failed = AuthFailedEvent {
+        caller: stranger.clone(),
+        operation: soroban_sdk::Symbol::new(&env, "refund_expired"),
+        timestamp: env.ledger().timestamp(),
+    };
+    assert!(has_failed_call_event(&env, &client.address, &failed));
+
+    client.refund_expired(&depositor, &commitment);
+    let result = client.try_refund_expired(&depositor, &commitment);
+    assert_eq!(result, Err(Ok(QuickexError::AlreadySpent)));
+}
+
+#[test]
+fn test_withdraw_all_expired_aggregates_per_token() {
+    use soroban_sdk::testutils::Ledger as _;
+
+    let (env, client) = setup();
+    let token_a = create_test_token(&env);
+    let token_b = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    let other = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token_a).mint(&depositor, &600);
+    token::StellarAssetClient::new(&env, &token_b).mint(&depositor, &1_000);
+    token::StellarAssetClient::new(&env, &token_a).mint(&other, &100);
+
+    let a1 = BytesN::from_array(&env, &[45; 32]);
+    let a2 = BytesN::from_array(&env, &[46; 32]);
+    let b1 = BytesN::from_array(&env, &[47; 32]);
+    let live = BytesN::from_array(&env, &[48; 32]);
+    let foreign = BytesN::from_array(&env, &[49; 32]);
+    client.deposit_with_expiry(&depositor, &token_a, &200, &a1, &10);
+    client.deposit_with_expiry(&depositor, &token_a, &300, &a2, &10);
+    client.deposit_with_expiry(&depositor, &token_b, &1_000, &b1, &10);
+    client.deposit_with_expiry(&depositor, &token_a, &100, &live, &0);
+    client.deposit_with_expiry(&other, &token_a, &100, &foreign, &10);
+    env.ledger().set_timestamp(env.ledger().timestamp() + 10);
+
+    // One of them was already swept to Expired; it is refunded all the same
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &None);
+    client.sweep_expired(&admin, &soroban_sdk::vec![&env, a2.clone()]);
+
+    let missing = BytesN::from_array(&env, &[50; 32]);
+    let commitments = soroban_sdk::vec![
+        &env,
+        a1.clone(),
+        a2.clone(),
+        b1.clone(),
+        live.clone(),
+        foreign.clone(),
+        missing,
+    ];
+    let totals = client.withdraw_all_expired(&depositor, &commitments);
+
+    assert_eq!(totals.len(), 2);
+    assert_eq!(totals.get(token_a.clone()), Some(500));
+    assert_eq!(totals.get(token_b.clone()), Some(1_000));
+    assert_eq!(token::Client::new(&env, &token_a).balance(&depositor), 500);
+    assert_eq!(
+        token::Client::new(&env, &token_b).balance(&depositor),
+        1_000
+    );
+    assert_eq!(client.get_commitment_state(&a1), Some(EscrowStatus::Spent));
+    assert_eq!(
+        client.get_commitment_state(&live),
+        Some(EscrowStatus::Pending)
+    );
+    assert_eq!(
+        client.get_commitment_state(&foreign),
+        Some(EscrowStatus::Pending)
+    );
+
+    // Nothing left to reclaim on a second pass
+    let totals = client.withdraw_all_expired(&depositor, &commitments);
+    assert_eq!(totals.len(), 0);
+}
+
+// ============================================================================
+// Contract Stats Tests
+// ============================================================================
+
+#[test]
+fn test_get_contract_stats_tracks_operations() {
+    use crate::types::ContractStats;
+    use soroban_sdk::testutils::Ledger as _;
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    let to = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &4000);
+
+    let empty = ContractStats {
+        total_deposits: 0,
+        total_withdrawals: 0,
+        total_refunds: 0,
+        pending_count: 0,
+    };
+    assert_eq!(client.get_contract_stats(), empty);
+
+    let salt = Bytes::from_slice(&env, b"stats_withdraw01");
+    let withdrawn = client.create_amount_commitment(&to, &token, &1000, &salt);
+    client.deposit_with_commitment(&depositor, &token, &1000, &withdrawn);
+    let cancelled = BytesN::from_array(&env, &[50; 32]);
+    client.deposit_with_commitment(&depositor, &token, &1000, &cancelled);
+    let expiring = BytesN::from_array(&env, &[51; 32]);
+    client.deposit_with_expiry(&depositor, &token, &1000, &expiring, &10);
+    let pending = BytesN::from_array(&env, &[52; 32]);
+    client.deposit_with_commitment(&depositor, &token, &1000, &pending);
+
+    let stats = client.get_contract_stats();
+    assert_eq!(stats.total_deposits, 4);
+    assert_eq!(stats.pending_count, 4);
+
+    client.withdraw(&token, &1000, &withdrawn, &to, &salt);
+    client.cancel_deposit(&depositor, &cancelled);
+    env.ledger().set_timestamp(env.ledger().timestamp() + 10);
+    client.refund_expired(&depositor, &expiring);
+
+    assert_eq!(
+        client.get_contract_stats(),
+        ContractStats {
+            total_deposits: 4,
+            total_withdrawals: 1,
+            total_refunds: 2,
+            pending_count: 1,
+        }
+    );
+}
+
+#[test]
+fn test_get_status_counts_follows_transitions() {
+    use crate::types::StatusCounts;
+    use soroban_sdk::testutils::Ledger as _;
+
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let token = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    let to = Address::generate(&env);
+    client.initialize(&admin, &None);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &4000);
+
+    let salt = Bytes::from_slice(&env, b"status_counts_01");
+    let withdrawn = client.create_amount_commitment(&to, &token, &1000, &salt);
+    client.deposit_with_commitment(&depositor, &token, &1000, &withdrawn);
+    let cancelled = BytesN::from_array(&env, &[53; 32]);
+    client.deposit_with_commitment(&depositor, &token, &1000, &cancelled);
+    let expiring = BytesN::from_array(&env, &[54; 32]);
+    client.deposit_with_expiry(&depositor, &token, &1000, &expiring, &10);
+    let pending = BytesN::from_array(&env, &[55; 32]);
+    client.deposit_with_expiry(&depositor, &token, &1000, &pending, &0);
+    assert_eq!(client.get_status_counts().pending, 4);
+
+    client.withdraw(&token, &1000, &withdrawn, &to, &salt);
+    client.cancel_deposit(&depositor, &cancelled);
+    env.ledger().set_timestamp(env.ledger().timestamp() + 10);
+    let batch = soroban_sdk::vec![&env, expiring.clone(), pending.clone()];
+    assert_eq!(client.sweep_expired(&admin, &batch), 1);
+
+    assert_eq!(
+        client.get_status_counts(),
+        StatusCounts {
+            pending: 1,
+            spent: 1,
+            expired: 1,
+            cancelled: 1,
+        }
+    );
+
+    // Refunding the swept escrow moves it from expired to spent
+    client.refund_expired(&depositor, &expiring);
+    let counts = client.get_status_counts();
+    assert_eq!((counts.expired, counts.spent), (0, 2));
+}
+
+#[test]
+fn test_get_contract_stats_ignores_failed_withdrawal() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    let to = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &1000);
+
+    let salt = Bytes::from_slice(&env, b"stats_failed_001");
+    let commitment = client.create_amount_commitment(&to, &token, &1000, &salt);
+    client.deposit_with_commitment(&depositor, &token, &1000, &commitment);
+
+    assert!(client
+        .try_withdraw(&token, &999, &commitment, &to, &salt)
+        .is_err());
+
+    let stats = client.get_contract_stats();
+    assert_eq!(stats.total_deposits, 1);
+    assert_eq!(stats.total_withdrawals, 0);
+    assert_eq!(stats.pending_count, 1);
+}
+
+// ============================================================================
+// Compact Status Code Tests
+// ============================================================================
+
+#[test]
+fn test_status_code_round_trip() {
+    use crate::types::{code_to_status, status_to_code};
+
+    let statuses = [
+        EscrowStatus::Pending,
+        EscrowStatus::Spent,
+        EscrowStatus::Expired,
+        EscrowStatus::Cancelled,
+    ];
+    for (i, status) in statuses.iter().enumerate() {
+        let code = status_to_code(*status);
+        assert_eq!(code, i as u32);
+        assert_eq!(code_to_status(code), Some(*status));
+    }
+}
+
+#[test]
+fn test_status_count_index_is_keyed_by_code() {
+    use crate::types::status_to_code;
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &1000);
+    let commitment = BytesN::from_array(&env, &[88; 32]);
+    client.deposit_with_commitment(&depositor, &token, &1000, &commitment);
+    client.cancel_deposit(&depositor, &commitment);
+
+    let code = status_to_code(EscrowStatus::Cancelled);
+    let count: Option<u64> = env.as_contract(&client.address, || {
+        env.storage().persistent().get(&DataKey::StatusCount(code))
+    });
+    assert_eq!(count, Some(1));
+    assert_eq!(client.get_status_counts().cancelled, 1);
+}
+
+#[test]
+fn test_unknown_status_code_is_rejected() {
+    use crate::types::code_to_status;
+
+    assert_eq!(code_to_status(4), None);
+    assert_eq!(code_to_status(u32::MAX), None);
+}
+
+// ============================================================================
+// Status Transition Tests
+// ============================================================================
+
+#[test]
+fn test_status_transition_matrix() {
+    use crate::errors::QuickexError;
+    use crate::types::transition_status;
+    use EscrowStatus::*;
+
+    let statuses = [Pending, Spent, Expired, Cancelled];
+    let legal = [
+        (Pending, Spent),
+        (Pending, Expired),
+        (Pending, Cancelled),
+        (Expired, Spent),
+    ];
+    for from in statuses {
+        for to in statuses {
+            let result = transition_status(from, to);
+            if legal.contains(&(from, to)) {
+                assert_eq!(result, Ok(to));
+            } else {
+                assert_eq!(result, Err(QuickexError::InvalidStatusTransition));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_set_status_rejects_illegal_transition() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let commitment = BytesN::from_array(&env, &[9; 32]);
+    let entry = EscrowEntry {
+        token: Address::generate(&env),
+        amount: 100,
+        owner: Address::generate(&env),
+        status: EscrowStatus::Spent,
+        created_at: 0,
+        expires_at: NO_EXPIRY,
+        payout: Payout::Deposited,
+        hash_algorithm: HashAlgorithm::Sha256,
+    };
+
+    env.as_contract(&client.address, || {
+        put_escrow(&env, &commitment.clone().into(), &entry);
+        for to in [
+            EscrowStatus::Pending,
+            EscrowStatus::Expired,
+            EscrowStatus::Cancelled,
+        ] {
+            let mut stored = entry.clone();
+            assert_eq!(
+                crate::escrow::set_status(&env, &commitment, &mut stored, to),
+                Err(QuickexError::InvalidStatusTransition)
+            );
+        }
+    });
+    assert_eq!(
+        client.get_commitment_state(&commitment),
+        Some(EscrowStatus::Spent)
+    );
+}
+
+// ============================================================================
+// Relayed Withdrawal Tests
+// ============================================================================
+
+#[test]
+fn test_withdraw_relayed_without_recipient_signature() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let to = Address::generate(&env);
+    let relayer = Address::generate(&env);
+    let amount: i128 = 10_000;
+    let salt = Bytes::from_slice(&env, b"relayed_withdraw");
+
+    let commitment = setup_withdrawable_escrow(&env, &client, &token, &to, amount, &salt);
+
+    assert!(client.withdraw_relayed(&relayer, &to, &token, &amount, &salt, &300));
+
+    // Only the relayer was asked to authorize
+    let auths = env.auths();
+    assert_eq!(auths.len(), 1);
+    assert_eq!(auths[0].0, relayer);
+
+    let token_client = token::Client::new(&env, &token);
+    assert_eq!(token_client.balance(&relayer), 300);
+    assert_eq!(token_client.balance(&to), 9_700);
+    assert_eq!(
+        client.get_commitment_state(&commitment),
+        Some(EscrowStatus::Spent)
+    );
+}
+
+#[test]
+fn test_withdraw_relayed_with_protocol_fee() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let collector = Address::generate(&env);
+    let token = create_test_token(&env);
+    let to = Address::generate(&env);
+    let relayer = Address::generate(&env);
+    let amount: i128 = 10_000;
+    let salt = Bytes::from_slice(&env, b"relayed_fee_salt");
+
+    client.initialize(&admin, &None);
+    client.set_fee(&admin, &100, &collector);
+    setup_withdrawable_escrow(&env, &client, &token, &to, amount, &salt);
+
+    client.withdraw_relayed(&relayer, &to, &token, &amount, &salt, &500);
+
+    let token_client = token::Client::new(&env, &token);
+    assert_eq!(token_client.balance(&collector), 100);
+    assert_eq!(token_client.balance(&relayer), 500);
+    assert_eq!(token_client.balance(&to), 9_400);
+}
+
+#[test]
+fn test_withdraw_relayed_rejects_excessive_fee() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let to = Address::generate(&env);
+    let relayer = Address::generate(&env);
+    let amount: i128 = 10_000;
+    let salt = Bytes::from_slice(&env, b"relayed_too_high");
+
+    let commitment = setup_withdrawable_escrow(&env, &client, &token, &to, amount, &salt);
+
+    let result = client.try_withdraw_relayed(&relayer, &to, &token, &amount, &salt, &501);
+    assert_eq!(result, Err(Ok(QuickexError::FeeTooHigh)));
+    let result = client.try_withdraw_relayed(&relayer, &to, &token, &amount, &salt, &-1);
+    assert_eq!(result, Err(Ok(QuickexError::InvalidAmount)));
+
+    assert_eq!(
+        client.get_commitment_state(&commitment),
+        Some(EscrowStatus::Pending)
+    );
+}
+
+#[test]
+fn test_withdraw_relayed_with_wrong_salt_fails() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let to = Address::generate(&env);
+    let relayer = Address::generate(&env);
+    let amount: i128 = 10_000;
+    let salt = Bytes::from_slice(&env, b"relayed_real_salt");
+    let wrong_salt = Bytes::from_slice(&env, b"relayed_fake_salt");
+
+    setup_withdrawable_escrow(&env, &client, &token, &to, amount, &salt);
+
+    let result = client.try_withdraw_relayed(&relayer, &to, &token, &amount, &wrong_salt, &0);
+    assert_eq!(result, Err(Ok(QuickexError::CommitmentNotFound)));
+}
+
+// ============================================================================
+// Merkle Tree Tests
+// ============================================================================
+
+fn merkle_hash(env: &Env, left: &BytesN<32>, right: &BytesN<32>) -> BytesN<32> {
+    let mut data = Bytes::new(env);
+    data.append(&left.clone().into());
+    data.append(&right.clone().into());
+    env.crypto().sha256(&data).into()
+}
+
+#[test]
+fn test_merkle_root_updates_on_deposit() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &3000);
+
+    // The empty root is the zero leaf hashed up 16 levels
+    let mut empty = BytesN::from_array(&env, &[0u8; 32]);
+    for _ in 0..16 {
+        empty = merkle_hash(&env, &empty, &empty);
+    }
+    assert_eq!(client.get_merkle_root(), empty);
+    assert_eq!(client.get_merkle_path(&0), None);
+
+    let mut roots = soroban_sdk::Vec::new(&env);
+    roots.push_back(client.get_merkle_root());
+    for i in 0..3u8 {
+        let commitment = BytesN::from_array(&env, &[60 + i; 32]);
+        client.deposit_with_commitment(&depositor, &token, &1000, &commitment);
+        assert_eq!(client.get_merkle_leaf_index(&commitment), Some(i as u32));
+
+        let root = client.get_merkle_root();
+        assert!(!roots.contains(&root));
+        roots.push_back(root);
+    }
+}
+
+#[test]
+fn test_deposit_succeeds_when_merkle_tree_full() {
+    use crate::merkle::MERKLE_CAPACITY;
+    use crate::storage::set_merkle_next_index;
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &3000);
+
+    let first = BytesN::from_array(&env, &[65; 32]);
+    client.deposit_with_commitment(&depositor, &token, &1000, &first);
+    let root = client.get_merkle_root();
+    env.as_contract(&client.address, || {
+        set_merkle_next_index(&env, MERKLE_CAPACITY);
+    });
+
+    // Deposits keep working; their commitments are simply left out of the tree
+    let late = BytesN::from_array(&env, &[66; 32]);
+    client.deposit_with_commitment(&depositor, &token, &1000, &late);
+    assert_eq!(
+        client.get_commitment_state(&late),
+        Some(EscrowStatus::Pending)
+    );
+    assert_eq!(client.get_merkle_leaf_index(&late), None);
+    assert_eq!(client.get_merkle_root(), root);
+
+    client.cancel_deposit(&depositor, &late);
+    let again = BytesN::from_array(&env, &[67; 32]);
+    client.deposit_with_commitment(&depositor, &token, &1000, &again);
+    assert_eq!(client.get_contract_stats().pending_count, 2);
+    assert_eq!(client.get_merkle_leaf_index(&first), Some(0));
+}
+
+#[test]
+fn test_merkle_paths_verify_for_each_leaf() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &3000);
+
+    let leaves = [
+        BytesN::from_array(&env, &[70; 32]),
+        BytesN::from_array(&env, &[71; 32]),
+        BytesN::from_array(&env, &[72; 32]),
+    ];
+    for leaf in leaves.iter() {
+        client.deposit_with_commitment(&depositor, &token, &1000, leaf);
+    }
+
+    for (i, leaf) in leaves.iter().enumerate() {
+        let path = client.get_merkle_path(&(i as u32)).unwrap();
+        assert_eq!(path.len(), 16);
+        assert!(client.verify_merkle_path(leaf, &(i as u32), &path));
+
+        // The proof does not verify for another leaf or position
+        let other = &leaves[(i + 1) % leaves.len()];
+        assert!(!client.verify_merkle_path(other, &(i as u32), &path));
+        assert!(!client.verify_merkle_path(leaf, &(i as u32 + 1), &path));
+    }
+
+    // Leaves 0 and 1 are siblings
+    let path = client.get_merkle_path(&0).unwrap();
+    assert_eq!(path.get(0).unwrap(), leaves[1]);
+    assert_eq!(
+        client.get_merkle_path(&1).unwrap().get(1).unwrap(),
+        merkle_hash(&env, &leaves[2], &BytesN::from_array(&env, &[0u8; 32]))
+    );
+}
+
+#[test]
+fn test_merkle_path_with_wrong_length_fails() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &1000);
+
+    let leaf = BytesN::from_array(&env, &[73; 32]);
+    client.deposit_with_commitment(&depositor, &token, &1000, &leaf);
+
+    let mut path = client.get_merkle_path(&0).unwrap();
+    path.pop_back();
+    assert!(!client.verify_merkle_path(&leaf, &0, &path));
+}
+
+// ============================================================================
+// Deposit Event Tests
+// ============================================================================
+
+#[test]
+fn test_deposit_event_topics_include_token() {
+    use crate::events::DepositToggledEvent;
+    use soroban_sdk::{IntoVal, Symbol, Val};
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let from = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&from, &1000);
+
+    let commitment = BytesN::from_array(&env, &[80; 32]);
+    client.deposit_with_commitment(&from, &token, &1000, &commitment);
+
+    let expected = DepositToggledEvent {
+        token: token.clone(),
+        commitment: commitment.clone(),
+        amount: 1000,
+        memo_hash: None,
+    };
+    assert!(has_event(&env, &client.address, &expected));
+
+    let topics: soroban_sdk::Vec<Val> = soroban_sdk::vec![
+        &env,
+        Symbol::new(&env, "Deposit").into_val(&env),
+        token.into_val(&env),
+        commitment.into_val(&env),
+    ];
+    assert_eq!(expected.topics(&env), topics);
+}
+
+#[test]
+fn test_withdraw_event_topics_include_recipient() {
+    use crate::events::WithdrawToggledEvent;
+    use soroban_sdk::{IntoVal, Symbol, Val};
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let to = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"withdraw_topic_1");
+    let commitment = setup_withdrawable_escrow(&env, &client, &token, &to, 1000, &salt);
+    client.withdraw(&token, &1000, &commitment, &to, &salt);
+
+    let expected = WithdrawToggledEvent {
+        to: to.clone(),
+        commitment,
+        timestamp: env.ledger().timestamp(),
+    };
+    assert!(has_event(&env, &client.address, &expected));
+
+    let topics: soroban_sdk::Vec<Val> = soroban_sdk::vec![
+        &env,
+        Symbol::new(&env, "WithdrawToggled").into_val(&env),
+        to.into_val(&env),
+    ];
+    assert_eq!(expected.topics(&env), topics);
+}
+
+// ============================================================================
+// Reclaim Window Tests
+// ============================================================================
+
+#[test]
+fn test_refund_phases_with_reclaim_window() {
+    use crate::errors::QuickexError;
+    use soroban_sdk::testutils::Ledger as _;
+
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let token = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &2000);
+
+    client.initialize(&admin, &None);
+    assert_eq!(
+        client.get_reclaim_window(),
+        crate::types::DEFAULT_RECLAIM_WINDOW_SECS
+    );
+    client.set_reclaim_window(&admin, &100);
+    assert_eq!(client.get_reclaim_window(), 100);
+
+    let by_admin = BytesN::from_array(&env, &[90; 32]);
+    let by_depositor = BytesN::from_array(&env, &[91; 32]);
+    client.deposit_with_expiry(&depositor, &token, &1000, &by_admin, &50);
+    client.deposit_with_expiry(&depositor, &token, &1000, &by_depositor, &50);
+    let start = env.ledger().timestamp();
+
+    // Pending: nobody can reclaim
+    let result = client.try_refund_expired(&depositor, &by_admin);
+    assert_eq!(result, Err(Ok(QuickexError::EscrowNotExpired)));
+    let result = client.try_refund_expired(&admin, &by_admin);
+    assert_eq!(result, Err(Ok(QuickexError::EscrowNotExpired)));
+
+    // Expired, inside the reclaim window: depositor only
+    env.ledger().set_timestamp(start + 50);
+    let result = client.try_refund_expired(&admin, &by_admin);
+    assert_eq!(result, Err(Ok(QuickexError::ReclaimWindowActive)));
+    env.ledger().set_timestamp(start + 149);
+    let result = client.try_refund_expired(&admin, &by_admin);
+    assert_eq!(result, Err(Ok(QuickexError::ReclaimWindowActive)));
+    client.refund_expired(&depositor, &by_depositor);
+
+    // Reclaim window over: the admin may trigger the refund, funds go to the depositor
+    env.ledger().set_timestamp(start + 150);
+    client.refund_expired(&admin, &by_admin);
+
+    let token_client = token::Client::new(&env, &token);
+    assert_eq!(token_client.balance(&depositor), 2000);
+    assert_eq!(token_client.balance(&admin), 0);
+    assert_eq!(
+        client.get_commitment_state(&by_admin),
+        Some(EscrowStatus::Spent)
+    );
+}
+
+#[test]
+fn test_set_reclaim_window_by_non_admin_fails() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let non_admin = Address::generate(&env);
+    client.initialize(&admin, &None);
+
+    let result = client.try_set_reclaim_window(&non_admin, &100);
+    assert_eq!(result, Err(Ok(QuickexError::Unauthorized)));
+}
+
+// ============================================================================
+// Privacy Batch Tests
+// ============================================================================
+
+#[test]
+fn test_set_privacy_batch() {
+    use crate::events::PrivacyToggledEvent;
+
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &None);
+
+    let accounts = soroban_sdk::vec![
+        &env,
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    client.set_privacy_batch(&admin, &accounts, &true);
+
+    // The batch emits exactly one contract event per account
+    let contract_events = env
+        .events()
+        .all()
+        .iter()
+        .filter(|(contract, _, _)| *contract == client.address)
+        .count();
+    assert_eq!(contract_events, 3);
+
+    for account in accounts.iter() {
+        let expected = PrivacyToggledEvent {
+            owner: account.clone(),
+            enabled: true,
+            timestamp: env.ledger().timestamp(),
+        };
+        assert!(has_event(&env, &client.address, &expected));
+    }
+    for account in accounts.iter() {
+        assert!(client.get_privacy(&account));
+    }
+
+    client.set_privacy_batch(&admin, &accounts, &false);
+    for account in accounts.iter() {
+        assert!(!client.get_privacy(&account));
+    }
+}
+
+#[test]
+fn test_set_privacy_batch_by_non_admin_fails() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let non_admin = Address::generate(&env);
+    client.initialize(&admin, &None);
+
+    let accounts = soroban_sdk::vec![&env, Address::generate(&env)];
+    let result = client.try_set_privacy_batch(&non_admin, &accounts, &true);
+    assert_eq!(result, Err(Ok(QuickexError::Unauthorized)));
+    assert!(!client.get_privacy(&accounts.get(0).unwrap()));
+}
+
+#[test]
+fn test_set_privacy_batch_too_large_fails() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &None);
+
+    let mut accounts = soroban_sdk::Vec::new(&env);
+    for _ in 0..=crate::MAX_BATCH {
+        accounts.push_back(Address::generate(&env));
+    }
+    let result = client.try_set_privacy_batch(&admin, &accounts, &true);
+    assert_eq!(result, Err(Ok(QuickexError::BatchTooLarge)));
+}
+
+#[test]
+fn test_batch_entrypoints_enforce_max_batch() {
+    use crate::{errors::QuickexError, types::SplitShare, MAX_BATCH};
+
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let from = Address::generate(&env);
+    let token = create_test_token(&env);
+    client.initialize(&admin, &None);
+    token::StellarAssetClient::new(&env, &token).mint(&from, &(200 * i128::from(MAX_BATCH)));
+
+    assert_eq!(
+        client.try_set_max_basket_size(&admin, &(MAX_BATCH + 1)),
+        Err(Ok(QuickexError::BatchTooLarge))
+    );
+    client.set_max_basket_size(&admin, &MAX_BATCH);
+    // Worst case per deposit: the fee transfer is one more token call
+    client.set_fee(&admin, &0, &Address::generate(&env));
+    client.set_deposit_fee(&admin, &100);
+
+    let mut accounts = soroban_sdk::Vec::new(&env);
+    let mut commitments = soroban_sdk::Vec::new(&env);
+    let mut escrows = soroban_sdk::Vec::new(&env);
+    let mut items = soroban_sdk::Vec::new(&env);
+    let mut shares = soroban_sdk::Vec::new(&env);
+    for i in 0..=MAX_BATCH {
+        let mut seed = [0u8; 32];
+        seed[0] = 0xB0;
+        seed[1] = i as u8;
+        let commitment = BytesN::from_array(&env, &seed);
+        seed[2] = 1;
+        let share_commitment = BytesN::from_array(&env, &seed);
+        accounts.push_back(Address::generate(&env));
+        commitments.push_back(commitment.clone());
+        escrows.push_back((
+            commitment.clone(),
+            EscrowEntry {
+                token: token.clone(),
+                amount: 1,
+                owner: from.clone(),
+                status: EscrowStatus::Spent,
+                created_at: 0,
+                expires_at: NO_EXPIRY,
+                payout: Payout::Deposited,
+                hash_algorithm: HashAlgorithm::Sha256,
+            },
+        ));
+        items.push_back(BasketItem {
+            token: token.clone(),
+            amount: 100,
+            commitment,
+        });
+        shares.push_back(SplitShare {
+            commitment: share_commitment,
+            amount: 100,
+        });
+    }
+    let too_large = Some(Ok(QuickexError::BatchTooLarge));
+    let over = 100 * (i128::from(MAX_BATCH) + 1);
+
+    // One element over the cap fails before any work is done
+    assert_eq!(client.try_get_privacy_batch(&accounts).err(), too_large);
+    assert_eq!(
+        client.try_set_privacy_batch(&admin, &accounts, &true).err(),
+        too_large
+    );
+    assert_eq!(
+        client.try_get_commitment_states(&commitments).err(),
+        too_large
+    );
+    assert_eq!(
+        client.try_sweep_expired(&admin, &commitments).err(),
+        too_large
+    );
+    assert_eq!(
+        client.try_withdraw_all_expired(&from, &commitments).err(),
+        too_large
+    );
+    assert_eq!(
+        client.try_import_escrows(&admin, &escrows, &false).err(),
+        too_large
+    );
+    assert_eq!(client.try_deposit_basket(&from, &items).err(), too_large);
+    assert_eq!(
+        client
+            .try_deposit_split(&from, &token, &over, &shares)
+            .err(),
+        too_large
+    );
+
+    // Exactly the cap is accepted
+    accounts.pop_back();
+    commitments.pop_back();
+    escrows.pop_back();
+    items.pop_back();
+    shares.pop_back();
+    let at_cap = 100 * i128::from(MAX_BATCH);
+    assert_eq!(client.get_privacy_batch(&accounts).len(), MAX_BATCH);
+    client.set_privacy_batch(&admin, &accounts, &true);
+    assert_eq!(client.get_commitment_states(&commitments).len(), MAX_BATCH);
+    assert_eq!(client.sweep_expired(&admin, &commitments), 0);
+    assert!(client.withdraw_all_expired(&from, &commitments).is_empty());
+    client.deposit_basket(&from, &items);
+    client.deposit_split(&from, &token, &at_cap, &shares);
+    assert_eq!(
+        client.get_contract_stats().pending_count,
+        2 * u64::from(MAX_BATCH)
+    );
+
+    let mut fresh = soroban_sdk::Vec::new(&env);
+    for (i, (_, entry)) in escrows.iter().enumerate() {
+        let mut seed = [0u8; 32];
+        seed[0] = 0xB1;
+        seed[1] = i as u8;
+        fresh.push_back((BytesN::from_array(&env, &seed), entry));
+    }
+    client.import_escrows(&admin, &fresh, &false);
+}
+
+#[test]
+fn test_get_privacy_batch() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    let third = Address::generate(&env);
+    client.set_privacy(&first, &true);
+    client.set_privacy(&third, &true);
+
+    let accounts = soroban_sdk::vec![&env, first, second, third];
+    assert_eq!(
+        client.get_privacy_batch(&accounts),
+        soroban_sdk::vec![&env, true, false, true]
+    );
+    assert_eq!(
+        client.get_privacy_batch(&soroban_sdk::Vec::new(&env)),
+        soroban_sdk::Vec::new(&env)
+    );
+
+    let mut too_many = soroban_sdk::Vec::new(&env);
+    for _ in 0..=crate::MAX_BATCH {
+        too_many.push_back(Address::generate(&env));
+    }
+    let result = client.try_get_privacy_batch(&too_many);
+    assert_eq!(result, Err(Ok(QuickexError::BatchTooLarge)));
+}
+
+// ============================================================================
+// Escrow Owner Tests
+// ============================================================================
+
+#[test]
+fn test_withdraw_is_bound_to_commitment_owner() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let owner = Address::generate(&env);
+    let other = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &1000);
+
+    // `deposit` binds the commitment to the depositing owner
+    let salt = Bytes::from_slice(&env, b"owner_binding_01");
+    let commitment = client.deposit(&token, &1000, &owner, &salt);
+    assert_eq!(
+        client
+            .get_escrow_details(&commitment, &false)
+            .unwrap()
+            .owner,
+        owner
+    );
+
+    // Knowing amount and salt is not enough for anyone else
+    let result = client.try_withdraw(&token, &1000, &commitment, &other, &salt);
+    assert_eq!(result, Err(Ok(QuickexError::CommitmentNotFound)));
+
+    assert_eq!(
+        client
+            .withdraw(&token, &1000, &commitment, &owner, &salt)
+            .status,
+        EscrowStatus::Spent
+    );
+}
+
+#[test]
+fn test_deposit_records_depositor_as_owner() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &2000);
+
+    let salt = Bytes::from_slice(&env, b"owner_field_salt");
+    let commitment = client.create_amount_commitment(&recipient, &token, &1000, &salt);
+    client.deposit_with_commitment(&depositor, &token, &1000, &commitment);
+
+    let entry = client.get_escrow_details(&commitment, &false).unwrap();
+    assert_eq!(entry.owner, depositor);
+    assert_eq!(entry.token, token);
+    assert_eq!(entry.amount, 1000);
+
+    let own = client.deposit(&token, &1000, &depositor, &salt);
+    assert_eq!(
+        client.get_escrow_details(&own, &false).unwrap().owner,
+        depositor
+    );
+
+    // The owner is kept once the escrow is spent
+    client.withdraw(&token, &1000, &commitment, &recipient, &salt);
+    assert_eq!(
+        client
+            .get_escrow_details(&commitment, &false)
+            .unwrap()
+            .owner,
+        depositor
+    );
+}
+
+// ============================================================================
+// Privacy Level Tests
+// ============================================================================
+
+#[test]
+fn test_enable_privacy_accepts_levels_zero_to_three() {
+    let (env, client) = setup();
+    let account = Address::generate(&env);
+
+    for level in 0..=3u32 {
+        assert!(client.enable_privacy(&account, &level));
+        assert_eq!(client.privacy_status(&account), Some(level));
+    }
+    assert_eq!(client.privacy_history(&account).len(), 4);
+}
+
+#[test]
+fn test_enable_privacy_rejects_level_above_three() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let account = Address::generate(&env);
+    client.enable_privacy(&account, &2);
+
+    let result = client.try_enable_privacy(&account, &4);
+    assert_eq!(result, Err(Ok(QuickexError::InvalidPrivacyLevel)));
+    assert_eq!(client.privacy_status(&account), Some(2));
+    assert_eq!(client.privacy_history(&account).len(), 1);
+}
+
+#[test]
+fn test_enable_privacy_emits_level_changed_event() {
+    use crate::events::PrivacyLevelChangedEvent;
+
+    let (env, client) = setup();
+    let account = Address::generate(&env);
+
+    client.enable_privacy(&account, &1);
+    let first = PrivacyLevelChangedEvent {
+        account: account.clone(),
+        old_level: None,
+        new_level: 1,
+        timestamp: env.ledger().timestamp(),
+    };
+    assert!(has_event(&env, &client.address, &first));
+
+    client.enable_privacy(&account, &3);
+    let second = PrivacyLevelChangedEvent {
+        account: account.clone(),
+        old_level: Some(1),
+        new_level: 3,
+        timestamp: env.ledger().timestamp(),
+    };
+    assert!(has_event(&env, &client.address, &second));
+}
+
+#[test]
+fn test_privacy_history_queries() {
+    let (env, client) = setup();
+    let account = Address::generate(&env);
+
+    assert_eq!(client.get_privacy_level_at(&account, &0), None);
+    assert_eq!(client.get_previous_privacy_level(&account), None);
+
+    client.enable_privacy(&account, &1);
+    assert_eq!(client.get_privacy_level_at(&account, &0), Some(1));
+    assert_eq!(client.get_previous_privacy_level(&account), None);
+
+    client.enable_privacy(&account, &3);
+    client.enable_privacy(&account, &0);
+
+    // Most recent first, matching privacy_history
+    assert_eq!(client.get_privacy_level_at(&account, &0), Some(0));
+    assert_eq!(client.get_privacy_level_at(&account, &1), Some(3));
+    assert_eq!(client.get_privacy_level_at(&account, &2), Some(1));
+    assert_eq!(client.get_privacy_level_at(&account, &3), None);
+    assert_eq!(client.get_previous_privacy_level(&account), Some(3));
+    assert_eq!(
+        client.privacy_history(&account),
+        soroban_sdk::vec![&env, 0u32, 3, 1]
+    );
+}
+
+// ============================================================================
+// Expiry Sweep Tests
+// ============================================================================
+
+#[test]
+fn test_sweep_expired_counts_only_expired_pending() {
+    use soroban_sdk::testutils::Ledger as _;
+
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let token = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &4000);
+    client.initialize(&admin, &None);
+
+    let expired_a = BytesN::from_array(&env, &[100; 32]);
+    let expired_b = BytesN::from_array(&env, &[101; 32]);
+    let still_pending = BytesN::from_array(&env, &[102; 32]);
+    let cancelled = BytesN::from_array(&env, &[103; 32]);
+    let missing = BytesN::from_array(&env, &[104; 32]);
+    client.deposit_with_expiry(&depositor, &token, &1000, &expired_a, &10);
+    client.deposit_with_expiry(&depositor, &token, &1000, &expired_b, &10);
+    client.deposit_with_expiry(&depositor, &token, &1000, &still_pending, &1000);
+    client.deposit_with_expiry(&depositor, &token, &1000, &cancelled, &10);
+    client.cancel_deposit(&depositor, &cancelled);
+
+    env.ledger().set_timestamp(env.ledger().timestamp() + 10);
+
+    let batch = soroban_sdk::vec![
+        &env,
+        expired_a.clone(),
+        still_pending.clone(),
+        missing.clone(),
+        cancelled.clone(),
+        expired_b.clone(),
+    ];
+    assert_eq!(client.sweep_expired(&admin, &batch), 2);
+
+    let expected = CommitmentStatusChangedEvent {
+        commitment: expired_a.clone(),
+        from: Some(EscrowStatus::Pending),
+        to: EscrowStatus::Expired,
+        timestamp: env.ledger().timestamp(),
+    };
+    assert!(has_event(&env, &client.address, &expected));
+
+    assert_eq!(
+        client.get_commitment_state(&expired_a),
+        Some(EscrowStatus::Expired)
+    );
+    assert_eq!(
+        client.get_commitment_state(&expired_b),
+        Some(EscrowStatus::Expired)
+    );
+    assert_eq!(
+        client.get_commitment_state(&still_pending),
+        Some(EscrowStatus::Pending)
+    );
+    assert_eq!(
+        client.get_commitment_state(&cancelled),
+        Some(EscrowStatus::Cancelled)
+    );
+    assert_eq!(client.get_contract_stats().pending_count, 1);
+
+    // Sweeping again is a no-op, and swept escrows remain refundable by the depositor
+    assert_eq!(client.sweep_expired(&admin, &batch), 0);
+    client.refund_expired(&depositor, &expired_a);
+    assert_eq!(token::Client::new(&env, &token).balance(&depositor), 2000);
+}
+
+#[test]
+fn test_sweep_expired_emits_escrow_expired_event() {
+    use crate::events::EscrowExpiredEvent;
+    use soroban_sdk::testutils::Ledger as _;
+
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let token = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &2000);
+    client.initialize(&admin, &None);
+
+    let expiring = BytesN::from_array(&env, &[106; 32]);
+    let lasting = BytesN::from_array(&env, &[107; 32]);
+    client.deposit_with_expiry(&depositor, &token, &1000, &expiring, &10);
+    client.deposit_with_expiry(&depositor, &token, &1000, &lasting, &0);
+
+    env.ledger().set_timestamp(env.ledger().timestamp() + 10);
+    let batch = soroban_sdk::vec![&env, expiring.clone(), lasting.clone()];
+    assert_eq!(client.sweep_expired(&admin, &batch), 1);
+
+    let expected = EscrowExpiredEvent {
+        commitment: expiring.clone(),
+        owner: depositor.clone(),
+        timestamp: env.ledger().timestamp(),
+    };
+    assert!(has_event(&env, &client.address, &expected));
+    let not_expected = EscrowExpiredEvent {
+        commitment: lasting,
+        owner: depositor,
+        timestamp: env.ledger().timestamp(),
+    };
+    assert!(!has_event(&env, &client.address, &not_expected));
+    assert_eq!(
+        client.get_event_count(&soroban_sdk::Symbol::new(&env, "EscrowExpired")),
+        1
+    );
+}
+
+#[test]
+fn test_sweep_expired_by_non_admin_fails() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let non_admin = Address::generate(&env);
+    client.initialize(&admin, &None);
+
+    let batch = soroban_sdk::vec![&env, BytesN::from_array(&env, &[105; 32])];
+    let result = client.try_sweep_expired(&non_admin, &batch);
+    assert_eq!(result, Err(Ok(QuickexError::Unauthorized)));
+}
+
+#[test]
+fn test_sweep_does_not_shorten_reclaim_window() {
+    use crate::errors::QuickexError;
+    use soroban_sdk::testutils::Ledger as _;
+
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let token = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &1000);
+    client.initialize(&admin, &None);
+
+    let commitment = BytesN::from_array(&env, &[106; 32]);
+    client.deposit_with_expiry(&depositor, &token, &1000, &commitment, &10);
+    env.ledger().set_timestamp(env.ledger().timestamp() + 10);
+    client.sweep_expired(&admin, &soroban_sdk::vec![&env, commitment.clone()]);
+
+    let result = client.try_refund_expired(&admin, &commitment);
+    assert_eq!(result, Err(Ok(QuickexError::ReclaimWindowActive)));
+}
+
+// ============================================================================
+// Re-entrancy Tests
+// ============================================================================
+
+mod reentrant_token {
+    use crate::{errors::QuickexError, QuickexContract, QuickexContractClient};
+    use soroban_sdk::{contract, contractimpl, contracttype, Address, Bytes, BytesN, Env};
+
+    #[contracttype]
+    #[derive(Clone)]
+    pub enum Key {
+        Target,
+        HostReentry,
+        DirectReentry,
+    }
+
+    /// Withdrawal the token replays from inside `transfer`
+    #[contracttype]
+    #[derive(Clone)]
+    pub struct Target {
+        pub quickex: Address,
+        pub amount: i128,
+        pub commitment: BytesN<32>,
+        pub to: Address,
+        pub salt: Bytes,
+    }
+
+    /// Outcome of a re-entrant withdraw attempt
+    #[contracttype]
+    #[derive(Clone, Debug, PartialEq)]
+    pub enum Outcome {
+        Succeeded,
+        Failed(u32),
+        RejectedByHost,
+    }
+
+    /// Token mock whose `transfer` tries to withdraw the same escrow again
+    #[contract]
+    pub struct ReentrantToken;
+
+    #[contractimpl]
+    impl ReentrantToken {
+        pub fn arm(env: Env, target: Target) {
+            env.storage().instance().set(&Key::Target, &target);
+        }
+
+        pub fn transfer(env: Env, _from: Address, _to: Address, _amount: i128) {
+            let Some(target) = env.storage().instance().get::<_, Target>(&Key::Target) else {
+                return;
+            };
+            env.storage().instance().remove(&Key::Target);
+
+            // Re-enter through a regular cross-contract call
+            let client = QuickexContractClient::new(&env, &target.quickex);
+            let host = match client.try_withdraw(
+                &env.current_contract_address(),
+                &target.amount,
+                &target.commitment,
+                &target.to,
+                &target.salt,
+            ) {
+                Ok(_) => Outcome::Succeeded,
+                Err(Ok(error)) => Outcome::Failed(error as u32),
+                Err(Err(_)) => Outcome::RejectedByHost,
+            };
+            env.storage().instance().set(&Key::HostReentry, &host);
+
+            // Re-enter the contract code directly, bypassing the host's re-entry guard, to
+            // check that the escrow state was already committed before this transfer
+            let token = env.current_contract_address();
+            let direct = env.as_contract(&target.quickex, || {
+                QuickexContract::withdraw(
+                    env.clone(),
+                    &token,
+                    target.amount,
+                    target.commitment.clone(),
+                    target.to.clone(),
+                    target.salt.clone(),
+                )
+            });
+            let direct = match direct {
+                Ok(_) => Outcome::Succeeded,
+                Err(error) => Outcome::Failed(error as u32),
+            };
+            env.storage().instance().set(&Key::DirectReentry, &direct);
+        }
+
+        pub fn host_reentry(env: Env) -> Option<Outcome> {
+            env.storage().instance().get(&Key::HostReentry)
+        }
+
+        pub fn direct_reentry(env: Env) -> Option<Outcome> {
+            env.storage().instance().get(&Key::DirectReentry)
+        }
+
+        pub fn already_spent_code() -> u32 {
+            QuickexError::AlreadySpent as u32
+        }
+    }
+}
+
+#[test]
+fn test_reentrant_withdraw_cannot_double_spend() {
+    use reentrant_token::{Outcome, ReentrantToken, ReentrantTokenClient, Target};
+
+    let (env, client) = setup();
+    let token = env.register(ReentrantToken, ());
+    let token_client = ReentrantTokenClient::new(&env, &token);
+    let to = Address::generate(&env);
+    let amount: i128 = 1000;
+    let salt = Bytes::from_slice(&env, b"reentrancy_salt1");
+
+    let commitment = client.create_amount_commitment(&to, &token, &amount, &salt);
+    setup_escrow(&env, &client.address, &token, amount, commitment.clone());
+
+    token_client.arm(&Target {
+        quickex: client.address.clone(),
+        amount,
+        commitment: commitment.clone(),
+        to: to.clone(),
+        salt: salt.clone(),
+    });
+
+    assert_eq!(
+        client
+            .withdraw(&token, &amount, &commitment, &to, &salt)
+            .status,
+        EscrowStatus::Spent
+    );
+
+    // The host refuses to re-enter the contract at all
+    assert_eq!(token_client.host_reentry(), Some(Outcome::RejectedByHost));
+    // Even without that guard, the escrow is already spent when the token is called
+    assert_eq!(
+        token_client.direct_reentry(),
+        Some(Outcome::Failed(token_client.already_spent_code()))
+    );
+    assert_eq!(
+        client.get_commitment_state(&commitment),
+        Some(EscrowStatus::Spent)
+    );
+}
+
+mod fee_on_transfer_token {
+    use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, MuxedAddress};
+
+    #[contracttype]
+    #[derive(Clone)]
+    pub enum Key {
+        Balance(Address),
+    }
+
+    /// Token mock that burns 1% of every transfer
+    #[contract]
+    pub struct FeeOnTransferToken;
+
+    #[contractimpl]
+    impl FeeOnTransferToken {
+        pub fn mint(env: Env, to: Address, amount: i128) {
+            let balance = Self::balance(env.clone(), to.clone());
+            env.storage()
+                .instance()
+                .set(&Key::Balance(to), &(balance + amount));
+        }
+
+        pub fn balance(env: Env, id: Address) -> i128 {
+            env.storage().instance().get(&Key::Balance(id)).unwrap_or(0)
+        }
+
+        pub fn transfer(env: Env, from: Address, to: MuxedAddress, amount: i128) {
+            from.require_auth();
+            let to = to.address();
+            let from_balance = Self::balance(env.clone(), from.clone());
+            env.storage()
+                .instance()
+                .set(&Key::Balance(from), &(from_balance - amount));
+            Self::mint(env, to, amount - amount / 100);
+        }
+    }
+}
+
+#[test]
+fn test_deposit_rejects_fee_on_transfer_token() {
+    use crate::errors::QuickexError;
+    use fee_on_transfer_token::{FeeOnTransferToken, FeeOnTransferTokenClient};
+
+    let (env, client) = setup();
+    let token = env.register(FeeOnTransferToken, ());
+    let token_client = FeeOnTransferTokenClient::new(&env, &token);
+    let depositor = Address::generate(&env);
+    token_client.mint(&depositor, &1000);
+
+    let commitment = BytesN::from_array(&env, &[87; 32]);
+    let result = client.try_deposit_with_commitment(&depositor, &token, &1000, &commitment);
+    assert_eq!(result, Err(Ok(QuickexError::AmountMismatch)));
+    assert!(client.get_escrow_details(&commitment, &false).is_none());
+    assert_eq!(token_client.balance(&depositor), 1000);
+}
+
+#[test]
+fn test_private_depositor_event_omits_amount() {
+    use crate::events::{DepositToggledEvent, PrivateDepositEvent};
+    use soroban_sdk::{Map, Symbol, TryFromVal, Val};
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let from = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&from, &1000);
+    client.set_privacy(&from, &true);
+
+    let commitment = BytesN::from_array(&env, &[81; 32]);
+    client.deposit_with_commitment(&from, &token, &1000, &commitment);
+
+    let private = PrivateDepositEvent {
+        token: token.clone(),
+        commitment: commitment.clone(),
+        memo_hash: None,
+    };
+    assert!(has_event(&env, &client.address, &private));
+    let public = DepositToggledEvent {
+        token,
+        commitment,
+        amount: 1000,
+        memo_hash: None,
+    };
+    assert!(!has_event(&env, &client.address, &public));
+
+    // No event emitted by the contract carries the amount
+    let mut checked = 0;
+    for (contract, _, data) in env.events().all().iter() {
+        if contract != client.address {
+            continue;
+        }
+        let fields = Map::<Symbol, Val>::try_from_val(&env, &data).unwrap();
+        assert!(!fields.contains_key(Symbol::new(&env, "amount")));
+        checked += 1;
+    }
+    assert!(checked > 0);
+}
+
+// ============================================================================
+// Two-step Admin Transfer Tests
+// ============================================================================
+
+#[test]
+fn test_propose_and_accept_admin() {
+    use crate::events::{AdminChangedEvent, AdminTransferProposedEvent};
+
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let new_admin = Address::generate(&env);
+    client.initialize(&admin, &None);
+
+    client.propose_admin(&admin, &new_admin);
+    let proposed = AdminTransferProposedEvent {
+        current_admin: admin.clone(),
+        pending_admin: new_admin.clone(),
+        timestamp: env.ledger().timestamp(),
+    };
+    assert!(has_event(&env, &client.address, &proposed));
+    assert_eq!(client.get_pending_admin(), Some(new_admin.clone()));
+    assert_eq!(client.get_admin(), Some(admin.clone()));
+
+    client.accept_admin(&new_admin);
+    let changed = AdminChangedEvent {
+        old_admin: admin,
+        new_admin: new_admin.clone(),
+        timestamp: env.ledger().timestamp(),
+    };
+    assert!(has_event(&env, &client.address, &changed));
+    assert_eq!(client.get_admin(), Some(new_admin));
+    assert_eq!(client.get_pending_admin(), None);
+}
+
+#[test]
+fn test_accept_admin_by_wrong_account_fails() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let new_admin = Address::generate(&env);
+    let other = Address::generate(&env);
+    client.initialize(&admin, &None);
+
+    let result = client.try_accept_admin(&new_admin);
+    assert_eq!(result, Err(Ok(QuickexError::Unauthorized)));
+
+    client.propose_admin(&admin, &new_admin);
+    let result = client.try_accept_admin(&other);
+    assert_eq!(result, Err(Ok(QuickexError::Unauthorized)));
+    assert_eq!(client.get_admin(), Some(admin));
+}
+
+#[test]
+fn test_set_admin_withdraws_pending_proposal() {
+    use crate::errors::QuickexError;
+    use crate::events::AdminChangedEvent;
+
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let proposed = Address::generate(&env);
+    let replacement = Address::generate(&env);
+    client.initialize(&admin, &None);
+
+    client.propose_admin(&admin, &proposed);
+    client.set_admin(&admin, &replacement);
+    assert!(has_event(
+        &env,
+        &client.address,
+        &AdminChangedEvent {
+            old_admin: admin.clone(),
+            new_admin: replacement.clone(),
+            timestamp: env.ledger().timestamp(),
+        }
+    ));
+    assert_eq!(client.get_pending_admin(), None);
+
+    // The stale proposal can no longer be used to take over from the new admin
+    let result = client.try_accept_admin(&proposed);
+    assert_eq!(result, Err(Ok(QuickexError::Unauthorized)));
+    assert_eq!(client.get_admin(), Some(replacement));
+}
+
+#[test]
+fn test_cancel_admin_transfer() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let new_admin = Address::generate(&env);
+    client.initialize(&admin, &None);
+
+    client.propose_admin(&admin, &new_admin);
+    let result = client.try_cancel_admin_transfer(&new_admin);
+    assert_eq!(result, Err(Ok(QuickexError::Unauthorized)));
+
+    client.cancel_admin_transfer(&admin);
+    assert_eq!(client.get_pending_admin(), None);
+
+    let result = client.try_accept_admin(&new_admin);
+    assert_eq!(result, Err(Ok(QuickexError::Unauthorized)));
+    assert_eq!(client.get_admin(), Some(admin));
+}
+
+// ============================================================================
+// Native Token Tests
+// ============================================================================
+
+#[test]
+fn test_deposit_native_and_withdraw() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let native = create_test_token(&env);
+    let from = Address::generate(&env);
+    let to = Address::generate(&env);
+    let amount: i128 = 50_000_000;
+    let salt = Bytes::from_slice(&env, b"native_xlm_salt1");
+    token::StellarAssetClient::new(&env, &native).mint(&from, &amount);
+
+    client.initialize(&admin, &None);
+    client.set_native_token(&admin, &native);
+    assert_eq!(client.get_native_token(), Some(native.clone()));
+
+    let commitment = client.create_amount_commitment(&to, &native, &amount, &salt);
+    client.deposit_native(&from, &amount, &commitment);
+    assert_eq!(
+        client
+            .get_escrow_details(&commitment, &false)
+            .unwrap()
+            .token,
+        native
+    );
+
+    client.withdraw(&native, &amount, &commitment, &to, &salt);
+    let native_client = token::Client::new(&env, &native);
+    assert_eq!(native_client.balance(&to), amount);
+    assert_eq!(native_client.balance(&from), 0);
+}
+
+#[test]
+fn test_deposit_native_without_configured_token_fails() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let from = Address::generate(&env);
+    let commitment = BytesN::from_array(&env, &[110; 32]);
+
+    let result = client.try_deposit_native(&from, &1000, &commitment);
+    assert_eq!(result, Err(Ok(QuickexError::NativeTokenNotSet)));
+}
+
+#[test]
+fn test_set_native_token_by_non_admin_fails() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let non_admin = Address::generate(&env);
+    client.initialize(&admin, &None);
+
+    let result = client.try_set_native_token(&non_admin, &create_test_token(&env));
+    assert_eq!(result, Err(Ok(QuickexError::Unauthorized)));
+}
+
+// ============================================================================
+// Withdrawal Dry-Run Tests
+// ============================================================================
+
+#[test]
+fn test_can_withdraw_does_not_spend() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let to = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&to, &1000);
+    let salt = Bytes::from_slice(&env, b"dry_run_salt_001");
+    let commitment = client.deposit(&token, &1000, &to, &salt);
+    let stats = client.get_contract_stats();
+
+    assert_eq!(
+        client.try_can_withdraw(&to, &token, &1000, &salt),
+        Ok(Ok(true))
+    );
+
+    // Nothing moved and nothing changed
+    let token_client = token::Client::new(&env, &token);
+    assert_eq!(token_client.balance(&to), 0);
+    assert_eq!(token_client.balance(&client.address), 1000);
+    assert_eq!(
+        client.get_commitment_state(&commitment),
+        Some(EscrowStatus::Pending)
+    );
+    assert_eq!(client.get_contract_stats(), stats);
+
+    assert_eq!(
+        client
+            .withdraw(&token, &1000, &commitment, &to, &salt)
+            .status,
+        EscrowStatus::Spent
+    );
+    assert_eq!(token_client.balance(&to), 1000);
+}
+
+#[test]
+fn test_can_withdraw_reports_withdraw_errors() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let to = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&to, &2000);
+    let salt = Bytes::from_slice(&env, b"dry_run_salt_002");
+    let commitment = client.deposit(&token, &1000, &to, &salt);
+
+    assert_eq!(
+        client.try_can_withdraw(&to, &token, &0, &salt),
+        Err(Ok(QuickexError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_can_withdraw(&to, &token, &999, &salt),
+        Err(Ok(QuickexError::CommitmentNotFound))
+    );
+
+    client.withdraw(&token, &1000, &commitment, &to, &salt);
+    assert_eq!(
+        client.try_can_withdraw(&to, &token, &1000, &salt),
+        Err(Ok(QuickexError::AlreadySpent))
+    );
+
+    let cancel_salt = Bytes::from_slice(&env, b"dry_run_salt_003");
+    let cancelled = client.deposit(&token, &1000, &to, &cancel_salt);
+    client.cancel_deposit(&to, &cancelled);
+    assert_eq!(
+        client.try_can_withdraw(&to, &token, &1000, &cancel_salt),
+        Err(Ok(QuickexError::EscrowCancelled))
+    );
+}
+
+#[test]
+fn test_can_withdraw_rejects_expired_escrow() {
+    use crate::errors::QuickexError;
+    use soroban_sdk::testutils::Ledger as _;
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let to = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&to, &1000);
+    let salt = Bytes::from_slice(&env, b"dry_run_salt_004");
+    let commitment = client.deposit(&token, &1000, &to, &salt);
+
+    let expires_at = client
+        .get_escrow_details(&commitment, &false)
+        .unwrap()
+        .expires_at;
+    env.ledger().set_timestamp(expires_at);
+    assert_eq!(
+        client.try_can_withdraw(&to, &token, &1000, &salt),
+        Err(Ok(QuickexError::EscrowExpired))
+    );
+    assert_eq!(
+        client.get_commitment_state(&commitment),
+        Some(EscrowStatus::Pending)
+    );
+}
+
+#[test]
+fn test_can_withdraw_while_paused_fails() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &None);
+    let token = create_test_token(&env);
+    let to = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&to, &1000);
+    let salt = Bytes::from_slice(&env, b"dry_run_salt_005");
+    client.deposit(&token, &1000, &to, &salt);
+
+    client.set_paused(&admin, &true, &None);
+    assert_eq!(
+        client.try_can_withdraw(&to, &token, &1000, &salt),
+        Err(Ok(QuickexError::ContractPaused))
+    );
+
+    client.set_paused(&admin, &false, &None);
+    assert!(client.can_withdraw(&to, &token, &1000, &salt));
+}
+
+// ============================================================================
+// Swap-on-Withdraw Tests
+// ============================================================================
+
+#[test]
+fn test_deposit_with_swap_same_token_pays_one_to_one() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let from = Address::generate(&env);
+    let to = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&from, &1000);
+    let salt = Bytes::from_slice(&env, b"swap_same_salt_1");
+    let commitment = client.create_amount_commitment(&to, &token, &1000, &salt);
+
+    // No rate is needed when the target is the deposited token
+    client.deposit_with_swap(&from, &token, &1000, &commitment, &token);
+    assert!(matches!(
+        client
+            .get_escrow_details(&commitment, &false)
+            .unwrap()
+            .payout,
+        Payout::Deposited
+    ));
+
+    client.withdraw(&token, &1000, &commitment, &to, &salt);
+    assert_eq!(token::Client::new(&env, &token).balance(&to), 1000);
+}
+
+#[test]
+fn test_deposit_with_swap_pays_target_token_at_rate() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &None);
+    let token_a = create_test_token(&env);
+    let token_b = create_test_token(&env);
+    let from = Address::generate(&env);
+    let to = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token_a).mint(&from, &1000);
+    token::StellarAssetClient::new(&env, &token_b).mint(&client.address, &5000);
+
+    // 1 A = 2.5 B
+    client.set_swap_rate(&admin, &token_a, &token_b, &5, &2);
+    let salt = Bytes::from_slice(&env, b"swap_rate_salt_1");
+    let commitment = client.create_amount_commitment(&to, &token_a, &1000, &salt);
+    client.deposit_with_swap(&from, &token_a, &1000, &commitment, &token_b);
+
+    // Later rate changes do not affect the escrow
+    client.set_swap_rate(&admin, &token_a, &token_b, &1, &1);
+
+    client.withdraw(&token_a, &1000, &commitment, &to, &salt);
+    let a = token::Client::new(&env, &token_a);
+    let b = token::Client::new(&env, &token_b);
+    assert_eq!(b.balance(&to), 2500);
+    assert_eq!(a.balance(&to), 0);
+    assert_eq!(b.balance(&client.address), 2500);
+    assert_eq!(a.balance(&client.address), 1000);
+}
+
+#[test]
+fn test_swap_withdraw_with_insufficient_reserve_fails() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &None);
+    let token_a = create_test_token(&env);
+    let token_b = create_test_token(&env);
+    let from = Address::generate(&env);
+    let to = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token_a).mint(&from, &1000);
+    token::StellarAssetClient::new(&env, &token_b).mint(&client.address, &1999);
+
+    client.set_swap_rate(&admin, &token_a, &token_b, &2, &1);
+    let salt = Bytes::from_slice(&env, b"swap_reserve_slt");
+    let commitment = client.create_amount_commitment(&to, &token_a, &1000, &salt);
+    client.deposit_with_swap(&from, &token_a, &1000, &commitment, &token_b);
+
+    let result = client.try_withdraw(&token_a, &1000, &commitment, &to, &salt);
+    assert_eq!(result, Err(Ok(QuickexError::InsufficientReserve)));
+    assert_eq!(
+        client.get_commitment_state(&commitment),
+        Some(EscrowStatus::Pending)
+    );
+
+    // Once the reserve is topped up the withdrawal goes through
+    token::StellarAssetClient::new(&env, &token_b).mint(&client.address, &1);
+    client.withdraw(&token_a, &1000, &commitment, &to, &salt);
+    assert_eq!(token::Client::new(&env, &token_b).balance(&to), 2000);
+}
+
+#[test]
+fn test_swap_withdraw_cannot_spend_other_escrows_funds() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &None);
+    let token_a = create_test_token(&env);
+    let token_b = create_test_token(&env);
+    let from = Address::generate(&env);
+    let to = Address::generate(&env);
+    let other = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token_a).mint(&from, &1000);
+    token::StellarAssetClient::new(&env, &token_b).mint(&other, &5000);
+
+    // The only token B the contract holds backs another pending escrow
+    let salt_b = Bytes::from_slice(&env, b"swap_other_slt_b");
+    client.deposit(&token_b, &5000, &other, &salt_b);
+
+    client.set_swap_rate(&admin, &token_a, &token_b, &2, &1);
+    let salt = Bytes::from_slice(&env, b"swap_other_slt_a");
+    let commitment = client.create_amount_commitment(&to, &token_a, &1000, &salt);
+    client.deposit_with_swap(&from, &token_a, &1000, &commitment, &token_b);
+
+    let result = client.try_withdraw(&token_a, &1000, &commitment, &to, &salt);
+    assert_eq!(result, Err(Ok(QuickexError::InsufficientReserve)));
+    assert_eq!(
+        client.get_commitment_state(&commitment),
+        Some(EscrowStatus::Pending)
+    );
+
+    // The other escrow's funds are intact
+    assert_eq!(client.reconcile(&token_b), 0);
+    let commitment_b = client.create_amount_commitment(&other, &token_b, &5000, &salt_b);
+    client.withdraw(&token_b, &5000, &commitment_b, &other, &salt_b);
+    assert_eq!(token::Client::new(&env, &token_b).balance(&other), 5000);
+}
+
+#[test]
+fn test_deposit_with_swap_without_rate_fails() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let token_a = create_test_token(&env);
+    let token_b = create_test_token(&env);
+    let from = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token_a).mint(&from, &1000);
+
+    let commitment = BytesN::from_array(&env, &[61; 32]);
+    let result = client.try_deposit_with_swap(&from, &token_a, &1000, &commitment, &token_b);
+    assert_eq!(result, Err(Ok(QuickexError::SwapRateNotSet)));
+}
+
+#[test]
+fn test_set_swap_rate_validation() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &None);
+    let token_a = create_test_token(&env);
+    let token_b = create_test_token(&env);
+
+    let result = client.try_set_swap_rate(&admin, &token_a, &token_b, &0, &1);
+    assert_eq!(result, Err(Ok(QuickexError::InvalidInput)));
+    let result = client.try_set_swap_rate(&admin, &token_a, &token_b, &1, &-1);
+    assert_eq!(result, Err(Ok(QuickexError::InvalidInput)));
+    let result = client.try_set_swap_rate(&Address::generate(&env), &token_a, &token_b, &1, &1);
+    assert_eq!(result, Err(Ok(QuickexError::Unauthorized)));
+
+    client.set_swap_rate(&admin, &token_a, &token_b, &3, &4);
+    let rate = client.get_swap_rate(&token_a, &token_b).unwrap();
+    assert_eq!((rate.numerator, rate.denominator), (3, 4));
+    assert_eq!(client.get_swap_rate(&token_b, &token_a), None);
+}
+
+// ============================================================================
+// Event Count Tests
+// ============================================================================
+
+#[test]
+fn test_event_counts_track_deposit_and_withdraw() {
+    use soroban_sdk::Symbol;
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let to = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&to, &2000);
+    let count = |topic: &str| client.get_event_count(&Symbol::new(&env, topic));
+
+    assert_eq!(count("Deposit"), 0);
+
+    let salt = Bytes::from_slice(&env, b"event_count_salt");
+    let commitment = client.deposit(&token, &1000, &to, &salt);
+    assert_eq!(count("Deposit"), 1);
+    assert_eq!(count("CommitmentStatusChanged"), 1);
+    assert_eq!(count("WithdrawToggled"), 0);
+
+    client.withdraw(&token, &1000, &commitment, &to, &salt);
+    assert_eq!(count("Deposit"), 1);
+    assert_eq!(count("CommitmentStatusChanged"), 2);
+    assert_eq!(count("WithdrawToggled"), 1);
+
+    let other = Bytes::from_slice(&env, b"event_count_slt2");
+    client.deposit(&token, &1000, &to, &other);
+    assert_eq!(count("Deposit"), 2);
+    assert_eq!(count("Unknown"), 0);
+}
+
+// ============================================================================
+// Event Verbosity Tests
+// ============================================================================
+
+#[test]
+fn test_normal_verbosity_suppresses_verbose_only_events() {
+    use crate::errors::QuickexError;
+    use crate::events::{AuthFailedEvent, DepositToggledEvent, PrivacyToggledEvent};
+    use crate::types::EventVerbosity;
+    use soroban_sdk::Symbol;
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let admin = Address::generate(&env);
+    let depositor = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    client.initialize(&admin, &None);
+    token::StellarAssetClient::new(&env, &token).mint(&depositor, &1000);
+    let count = |topic: &str| client.get_event_count(&Symbol::new(&env, topic));
+    assert_eq!(client.get_event_verbosity(), EventVerbosity::Verbose);
+
+    client.set_event_verbosity(&admin, &EventVerbosity::Normal);
+    let commitment = BytesN::from_array(&env, &[90; 32]);
+    client.deposit_with_commitment(&depositor, &token, &1000, &commitment);
+    assert!(has_event(
+        &env,
+        &client.address,
+        &DepositToggledEvent {
+            token: token.clone(),
+            commitment: commitment.clone(),
+            amount: 1000,
+            memo_hash: None,
+        }
+    ));
+    assert_eq!(count("Deposit"), 1);
+    assert_eq!(count("CommitmentStatusChanged"), 0);
+
+    assert_eq!(
+        client.try_cancel_deposit(&stranger, &commitment),
+        Err(Ok(QuickexError::Unauthorized))
+    );
+    assert!(!has_failed_call_event(
+        &env,
+        &client.address,
+        &AuthFailedEvent {
+            caller: stranger,
+            operation: Symbol::new(&env, "cancel_deposit"),
+            timestamp: env.ledger().timestamp(),
+        }
+    ));
+
+    // Normal still publishes privacy changes; Off keeps only the core events
+    client.set_privacy(&depositor, &true);
+    assert_eq!(count("PrivacyToggled"), 1);
+    client.set_event_verbosity(&admin, &EventVerbosity::Off);
+    client.set_privacy(&depositor, &false);
+    assert!(!has_event(
+        &env,
+        &client.address,
+        &PrivacyToggledEvent {
+            owner: depositor.clone(),
+            enabled: false,
+            timestamp: env.ledger().timestamp(),
+        }
+    ));
+    assert_eq!(count("PrivacyToggled"), 1);
+
+    client.cancel_deposit(&depositor, &commitment);
+    assert_eq!(count("DepositCancelled"), 1);
+    assert_eq!(token::Client::new(&env, &token).balance(&depositor), 1000);
+}
+
+// ============================================================================
+// Total Escrowed Accounting Tests
+// ============================================================================
+
+#[test]
+fn test_total_escrowed_tracks_deposits_and_payouts() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let to = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&to, &3000);
+
+    let salt = Bytes::from_slice(&env, b"total_escrow_s01");
+    let commitment = client.deposit(&token, &1000, &to, &salt);
+    let cancelled = client.deposit(
+        &token,
+        &2000,
+        &to,
+        &Bytes::from_slice(&env, b"total_escrow_s02"),
+    );
+    assert_eq!(client.get_total_escrowed(&token), 3000);
+
+    client.withdraw(&token, &1000, &commitment, &to, &salt);
+    assert_eq!(client.get_total_escrowed(&token), 2000);
+
+    client.cancel_deposit(&to, &cancelled);
+    assert_eq!(client.get_total_escrowed(&token), 0);
+}
+
+#[test]
+fn test_reconcile_reports_surplus() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let depositor = Address::generate(&env);
+    let sac = token::StellarAssetClient::new(&env, &token);
+    sac.mint(&depositor, &1000);
+
+    assert_eq!(client.reconcile(&token), 0);
+    let salt = Bytes::from_slice(&env, b"reconcile_salt01");
+    client.deposit(&token, &1000, &depositor, &salt);
+    assert_eq!(client.reconcile(&token), 0);
+
+    // Tokens sent straight to the contract are not backing any escrow
+    sac.mint(&client.address, &250);
+    assert_eq!(client.reconcile(&token), 250);
+}
+
+#[test]
+fn test_total_escrowed_overflow_is_a_clean_error() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let big = Address::generate(&env);
+    let small = Address::generate(&env);
+    let sac = token::StellarAssetClient::new(&env, &token);
+    sac.mint(&big, &(i128::MAX - 10));
+    sac.mint(&small, &11);
+
+    let first = BytesN::from_array(&env, &[70; 32]);
+    client.deposit_with_commitment(&big, &token, &(i128::MAX - 10), &first);
+
+    let second = BytesN::from_array(&env, &[71; 32]);
+    let result = client.try_deposit_with_commitment(&small, &token, &11, &second);
+    assert_eq!(result, Err(Ok(QuickexError::ArithmeticOverflow)));
+    assert_eq!(client.get_total_escrowed(&token), i128::MAX - 10);
+    assert_eq!(token::Client::new(&env, &token).balance(&small), 11);
+}
+
+#[test]
+fn test_import_escrows_overflow_is_a_clean_error() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let token = create_test_token(&env);
+    client.initialize(&admin, &None);
+
+    let half = i128::MAX / 2 + 1;
+    let escrows = soroban_sdk::vec![
+        &env,
+        (
+            BytesN::from_array(&env, &[72; 32]),
+            import_entry(&env, &token, half)
+        ),
+        (
+            BytesN::from_array(&env, &[73; 32]),
+            import_entry(&env, &token, half)
+        ),
+    ];
+
+    let result = client.try_import_escrows(&admin, &escrows, &false);
+    assert_eq!(result, Err(Ok(QuickexError::ArithmeticOverflow)));
+}
+
+// ============================================================================
+// Recipient Deny-List Tests
+// ============================================================================
+
+#[test]
+fn test_blocked_recipient_cannot_withdraw_until_unblocked() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &None);
+    let token = create_test_token(&env);
+    let to = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"blocked_rcpt_slt");
+    let commitment = setup_withdrawable_escrow(&env, &client, &token, &to, 1000, &salt);
+
+    assert!(!client.is_blocked(&to));
+    client.block_address(&admin, &to);
+    assert!(client.is_blocked(&to));
+
+    let result = client.try_withdraw(&token, &1000, &commitment, &to, &salt);
+    assert_eq!(result, Err(Ok(QuickexError::RecipientBlocked)));
+    assert_eq!(
+        client.try_can_withdraw(&to, &token, &1000, &salt),
+        Err(Ok(QuickexError::RecipientBlocked))
+    );
+    assert_eq!(
+        client.get_commitment_state(&commitment),
+        Some(EscrowStatus::Pending)
+    );
+
+    client.unblock_address(&admin, &to);
+    assert!(!client.is_blocked(&to));
+    assert_eq!(
+        client
+            .withdraw(&token, &1000, &commitment, &to, &salt)
+            .status,
+        EscrowStatus::Spent
+    );
+    assert_eq!(token::Client::new(&env, &token).balance(&to), 1000);
+}
+
+#[test]
+fn test_blocked_recipient_cannot_receive_private_withdrawal() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &None);
+    let token = create_test_token(&env);
+    let owner = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"blocked_priv_slt");
+    setup_withdrawable_escrow(&env, &client, &token, &owner, 1000, &salt);
+
+    client.block_address(&admin, &recipient);
+    let result = client.try_withdraw_private(&owner, &recipient, &token, &1000, &salt);
+    assert_eq!(result, Err(Ok(QuickexError::RecipientBlocked)));
+}
+
+#[test]
+fn test_block_address_by_non_admin_fails() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    client.initialize(&Address::generate(&env), &None);
+    let target = Address::generate(&env);
+
+    let result = client.try_block_address(&Address::generate(&env), &target);
+    assert_eq!(result, Err(Ok(QuickexError::Unauthorized)));
+    assert!(!client.is_blocked(&target));
+}
+
+// ============================================================================
+// Bucketed Commitment Tests
+// ============================================================================
+
+#[test]
+fn test_withdraw_bucketed_amount_inside_bucket() {
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let from = Address::generate(&env);
+    let to = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&from, &500);
+    let salt = Bytes::from_slice(&env, b"bucket_salt_0001");
+
+    // Bucket 2 covers 100..=999
+    let commitment = client.create_bucketed_commitment(&to, &token, &2, &salt);
+    assert_ne!(
+        commitment,
+        client.create_amount_commitment(&to, &token, &500, &salt)
+    );
+    assert!(client.verify_bucketed_commitment(&commitment, &to, &token, &2, &500, &salt));
+    assert!(!client.verify_bucketed_commitment(&commitment, &to, &token, &2, &1000, &salt));
+    assert!(!client.verify_bucketed_commitment(&commitment, &to, &token, &3, &500, &salt));
+
+    client.deposit_with_commitment(&from, &token, &500, &commitment);
+    assert_eq!(client.withdraw_bucketed(&to, &token, &2, &salt), 500);
+    assert_eq!(token::Client::new(&env, &token).balance(&to), 500);
+    assert_eq!(
+        client.get_commitment_state(&commitment),
+        Some(EscrowStatus::Spent)
+    );
+}
+
+#[test]
+fn test_withdraw_bucketed_amount_outside_bucket_fails() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let token = create_test_token(&env);
+    let from = Address::generate(&env);
+    let to = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&from, &5000);
+    let salt = Bytes::from_slice(&env, b"bucket_salt_0002");
+
+    let commitment = client.create_bucketed_commitment(&to, &token, &2, &salt);
+    client.deposit_with_commitment(&from, &token, &5000, &commitment);
+
+    let result = client.try_withdraw_bucketed(&to, &token, &2, &salt);
+    assert_eq!(result, Err(Ok(QuickexError::InvalidCommitment)));
+    let result = client.try_withdraw_bucketed(&to, &token, &3, &salt);
+    assert_eq!(result, Err(Ok(QuickexError::CommitmentNotFound)));
+    assert_eq!(
+        client.get_commitment_state(&commitment),
+        Some(EscrowStatus::Pending)
+    );
+}
+
+#[test]
+fn test_bucketed_commitment_binds_token() {
+    use crate::errors::QuickexError;
+
+    let (env, client) = setup();
+    let token_a = create_test_token(&env);
+    let token_b = create_test_token(&env);
+    let from = Address::generate(&env);
+    let to = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token_b).mint(&from, &500);
+    let salt = Bytes::from_slice(&env, b"bucket_salt_0004");
+
+    let commitment = client.create_bucketed_commitment(&to, &token_a, &2, &salt);
+    assert_ne!(
+        commitment,
+        client.create_bucketed_commitment(&to, &token_b, &2, &salt)
+    );
+    assert!(!client.verify_bucketed_commitment(&commitment, &to, &token_b, &2, &500, &salt));
+
+    // A deposit in token B under a commitment agreed for token A cannot be spent as either
+    client.deposit_with_commitment(&from, &token_b, &500, &commitment);
+    let result = client.try_withdraw_bucketed(&to, &token_b, &2, &salt);
+    assert_eq!(result, Err(Ok(QuickexError::CommitmentNotFound)));
+    let result = client.try_withdraw_bucketed(&to, &token_a, &2, &salt);
+    assert_eq!(result, Err(Ok(QuickexError::InvalidCommitment)));
+    assert_eq!(
+        client.get_commitment_state(&commitment),
+        Some(EscrowStatus::Pending)
+    );
+}
+
+#[test]
+fn test_bucket_bounds() {
+    use crate::commitment::{bucket_bounds, is_in_bucket};
+    use crate::errors::QuickexError;
+
+    assert_eq!(bucket_bounds(0), Some((1, 9)));
+    assert_eq!(bucket_bounds(3), Some((1_000, 9_999)));
+    assert_eq!(bucket_bounds(38), Some((10i128.pow(38), i128::MAX)));
+    assert_eq!(bucket_bounds(39), None);
+    assert!(!is_in_bucket(0, 0));
+    assert!(is_in_bucket(i128::MAX, 38));
+
+    let (env, client) = setup();
+    let salt = Bytes::from_slice(&env, b"bucket_salt_0003");
+    let result = client.try_create_bucketed_commitment(
+        &Address::generate(&env),
+        &Address::generate(&env),
+        &39,
+        &salt,
+    );
+    assert_eq!(result, Err(Ok(QuickexError::InvalidInput)));
+}
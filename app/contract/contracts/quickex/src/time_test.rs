@@ -0,0 +1,260 @@
+#![cfg(test)]
+//! End-to-end tests for time-gated behavior: expiry, reclaim windows, deposit deadlines,
+//! withdrawal cooldowns, and storage TTLs. Each test deposits, moves the ledger forward
+//! with the helpers below, and checks the behavior on both sides of the boundary.
+use soroban_sdk::{
+    testutils::{storage::Persistent as _, Address as _, Ledger as _},
+    token, Address, Bytes, BytesN, Env,
+};
+
+use crate::{
+    errors::QuickexError,
+    storage::DataKey,
+    types::{EscrowStatus, TtlConfig},
+    QuickexContract, QuickexContractClient,
+};
+
+fn setup<'a>() -> (Env, QuickexContractClient<'a>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+    let contract_id = env.register(QuickexContract, ());
+    let client = QuickexContractClient::new(&env, &contract_id);
+    let token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    (env, client, token)
+}
+
+/// Move the ledger clock forward by `secs` seconds
+fn advance_time(env: &Env, secs: u64) {
+    env.ledger().with_mut(|li| li.timestamp += secs);
+}
+
+/// Close `ledgers` ledgers, moving the clock forward 5 seconds per ledger
+fn advance_ledgers(env: &Env, ledgers: u32) {
+    env.ledger().with_mut(|li| {
+        li.sequence_number += ledgers;
+        li.timestamp += 5 * u64::from(ledgers);
+    });
+}
+
+/// Deposit `amount` from a fresh depositor under a commitment to `to` and `salt`;
+/// returns the depositor and the commitment
+fn deposit_expiring(
+    env: &Env,
+    client: &QuickexContractClient,
+    token: &Address,
+    to: &Address,
+    amount: i128,
+    salt: &Bytes,
+    expires_in: u64,
+) -> (Address, BytesN<32>) {
+    let owner = Address::generate(env);
+    token::StellarAssetClient::new(env, token).mint(&owner, &amount);
+    let commitment = client.create_amount_commitment(to, token, &amount, salt);
+    client.deposit_with_expiry(&owner, token, &amount, &commitment, &expires_in);
+    (owner, commitment)
+}
+
+fn escrow_ttl(env: &Env, client: &QuickexContractClient, commitment: &BytesN<32>) -> u32 {
+    env.as_contract(&client.address, || {
+        let key = DataKey::Escrow(commitment.clone().into());
+        env.storage().persistent().get_ttl(&key)
+    })
+}
+
+#[test]
+fn test_time_helpers_advance_ledger() {
+    let (env, _client, _token) = setup();
+    let sequence = env.ledger().sequence();
+
+    advance_time(&env, 60);
+    assert_eq!(env.ledger().timestamp(), 1_060);
+    assert_eq!(env.ledger().sequence(), sequence);
+
+    advance_ledgers(&env, 12);
+    assert_eq!(env.ledger().timestamp(), 1_120);
+    assert_eq!(env.ledger().sequence(), sequence + 12);
+}
+
+#[test]
+fn test_escrow_withdrawable_until_expiry_then_refundable() {
+    let (env, client, token) = setup();
+    let to = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"time_expiry_salt");
+    let (owner, commitment) = deposit_expiring(&env, &client, &token, &to, 1000, &salt, 100);
+
+    assert_eq!(
+        client.try_refund_expired(&owner, &commitment),
+        Err(Ok(QuickexError::EscrowNotExpired))
+    );
+
+    advance_time(&env, 99);
+    assert!(client.can_withdraw(&to, &token, &1000, &salt));
+
+    advance_time(&env, 1);
+    assert_eq!(
+        client.try_can_withdraw(&to, &token, &1000, &salt),
+        Err(Ok(QuickexError::EscrowExpired))
+    );
+    let result = client.try_withdraw(&token, &1000, &commitment, &to, &salt);
+    assert_eq!(result.err(), Some(Ok(QuickexError::EscrowExpired)));
+
+    client.refund_expired(&owner, &commitment);
+    let balance = token::Client::new(&env, &token);
+    assert_eq!(balance.balance(&owner), 1000);
+    assert_eq!(balance.balance(&to), 0);
+    assert_eq!(
+        client.get_commitment_state(&commitment),
+        Some(EscrowStatus::Spent)
+    );
+}
+
+#[test]
+fn test_escrow_without_expiry_survives_time_travel() {
+    let (env, client, token) = setup();
+    let to = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"time_forever_slt");
+    let (owner, commitment) = deposit_expiring(&env, &client, &token, &to, 1000, &salt, 0);
+
+    advance_time(&env, 10 * 365 * 24 * 60 * 60);
+    assert_eq!(
+        client.try_refund_expired(&owner, &commitment),
+        Err(Ok(QuickexError::EscrowNotExpired))
+    );
+    client.withdraw(&token, &1000, &commitment, &to, &salt);
+    assert_eq!(token::Client::new(&env, &token).balance(&to), 1000);
+}
+
+#[test]
+fn test_default_expiry_and_sweep() {
+    let (env, client, token) = setup();
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &None);
+    client.set_default_expiry(&admin, &3_600);
+
+    let owner = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &1000);
+    let commitment = BytesN::from_array(&env, &[40; 32]);
+    client.deposit_with_commitment(&owner, &token, &1000, &commitment);
+    let commitments = soroban_sdk::vec![&env, commitment.clone()];
+
+    advance_time(&env, 3_599);
+    assert_eq!(client.sweep_expired(&admin, &commitments), 0);
+    assert_eq!(
+        client.get_commitment_state(&commitment),
+        Some(EscrowStatus::Pending)
+    );
+
+    advance_time(&env, 1);
+    assert_eq!(client.sweep_expired(&admin, &commitments), 1);
+    assert_eq!(
+        client.get_commitment_state(&commitment),
+        Some(EscrowStatus::Expired)
+    );
+}
+
+#[test]
+fn test_admin_reclaim_waits_for_reclaim_window() {
+    let (env, client, token) = setup();
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &None);
+    client.set_reclaim_window(&admin, &500);
+
+    let to = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"time_reclaim_slt");
+    let (owner, commitment) = deposit_expiring(&env, &client, &token, &to, 1000, &salt, 100);
+
+    advance_time(&env, 100);
+    assert_eq!(
+        client.try_refund_expired(&admin, &commitment),
+        Err(Ok(QuickexError::ReclaimWindowActive))
+    );
+
+    advance_time(&env, 499);
+    assert_eq!(
+        client.try_refund_expired(&admin, &commitment),
+        Err(Ok(QuickexError::ReclaimWindowActive))
+    );
+
+    advance_time(&env, 1);
+    client.refund_expired(&admin, &commitment);
+    assert_eq!(token::Client::new(&env, &token).balance(&owner), 1000);
+}
+
+#[test]
+fn test_deposit_deadline_boundary() {
+    let (env, client, token) = setup();
+    let owner = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&owner, &2000);
+    let not_after = env.ledger().timestamp() + 60;
+
+    advance_time(&env, 60);
+    let on_time = BytesN::from_array(&env, &[41; 32]);
+    client.deposit_with_deadline(&owner, &token, &1000, &on_time, &not_after);
+
+    advance_time(&env, 1);
+    let late = BytesN::from_array(&env, &[42; 32]);
+    assert_eq!(
+        client.try_deposit_with_deadline(&owner, &token, &1000, &late, &not_after),
+        Err(Ok(QuickexError::DepositDeadlinePassed))
+    );
+}
+
+#[test]
+fn test_withdraw_cooldown_elapses_with_ledgers() {
+    let (env, client, token) = setup();
+    let admin = Address::generate(&env);
+    client.initialize(&admin, &None);
+    client.set_withdraw_cooldown(&admin, &20);
+
+    let to = Address::generate(&env);
+    let first_salt = Bytes::from_slice(&env, b"time_cooldown_01");
+    let second_salt = Bytes::from_slice(&env, b"time_cooldown_02");
+    let (_, first) = deposit_expiring(&env, &client, &token, &to, 500, &first_salt, 0);
+    let (_, second) = deposit_expiring(&env, &client, &token, &to, 700, &second_salt, 0);
+
+    client.withdraw(&token, &500, &first, &to, &first_salt);
+
+    // Wall-clock time alone does not end a ledger-based cooldown
+    advance_time(&env, 24 * 60 * 60);
+    assert_eq!(
+        client.try_can_withdraw(&to, &token, &700, &second_salt),
+        Err(Ok(QuickexError::WithdrawCooldownActive))
+    );
+
+    advance_ledgers(&env, 19);
+    let result = client.try_withdraw(&token, &700, &second, &to, &second_salt);
+    assert_eq!(result.err(), Some(Ok(QuickexError::WithdrawCooldownActive)));
+
+    advance_ledgers(&env, 1);
+    client.withdraw(&token, &700, &second, &to, &second_salt);
+    assert_eq!(token::Client::new(&env, &token).balance(&to), 1200);
+}
+
+#[test]
+fn test_escrow_ttl_counts_down_and_is_bumped_on_write() {
+    let (env, client, token) = setup();
+    let config = TtlConfig {
+        threshold: 10_000,
+        extend_to: 50_000,
+    };
+    client.initialize(&Address::generate(&env), &Some(config));
+
+    let to = Address::generate(&env);
+    let salt = Bytes::from_slice(&env, b"time_ttl_salt_01");
+    let (_, commitment) = deposit_expiring(&env, &client, &token, &to, 1000, &salt, 0);
+    assert_eq!(escrow_ttl(&env, &client, &commitment), config.extend_to);
+
+    advance_ledgers(&env, 45_000);
+    assert_eq!(escrow_ttl(&env, &client, &commitment), 5_000);
+
+    // The entry is still live; writing it again brings it back to `extend_to`
+    client.withdraw(&token, &1000, &commitment, &to, &salt);
+    assert_eq!(escrow_ttl(&env, &client, &commitment), config.extend_to);
+    assert_eq!(
+        client.get_commitment_state(&commitment),
+        Some(EscrowStatus::Spent)
+    );
+}
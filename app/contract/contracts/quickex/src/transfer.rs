@@ -0,0 +1,27 @@
+use soroban_sdk::{token, Address, Env};
+
+use crate::errors::QuickexError;
+
+/// Pull `amount` of `token` from `from` into the contract and check it all arrived.
+///
+/// The contract's balance is read before and after the transfer. Fee-on-transfer or
+/// otherwise misbehaving tokens that deliver a different amount are rejected with
+/// `AmountMismatch`, so an escrow never records more than the contract received.
+pub fn transfer_in(
+    env: &Env,
+    token: &Address,
+    from: &Address,
+    amount: i128,
+) -> Result<(), QuickexError> {
+    let token_client = token::Client::new(env, token);
+    let contract = env.current_contract_address();
+
+    let before = token_client.balance(&contract);
+    token_client.transfer(from, &contract, &amount);
+    let after = token_client.balance(&contract);
+
+    if after.checked_sub(before) != Some(amount) {
+        return Err(QuickexError::AmountMismatch);
+    }
+    Ok(())
+}
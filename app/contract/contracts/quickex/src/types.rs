@@ -1,4 +1,6 @@
-use soroban_sdk::{contracttype, Address};
+use soroban_sdk::{contracttype, Address, BytesN};
+
+use crate::errors::QuickexError;
 
 /// Escrow entry status
 #[contracttype]
@@ -7,6 +9,273 @@ pub enum EscrowStatus {
     Pending,
     Spent,
     Expired,
+    /// Cancelled by the depositor before withdrawal; funds returned to the depositor
+    Cancelled,
+}
+
+/// Privileged role an account may hold, see `get_role`.
+///
+/// Granted roles are recorded for access-control audits; admin-only entrypoints still
+/// check for the admin alone.
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Role {
+    /// The contract admin; held by exactly one account and moved with `set_admin` or the
+    /// two-step `propose_admin` / `accept_admin` handoff
+    Admin,
+    /// Audit label for an operations account; grants no entrypoint access
+    Operator,
+    /// Audit label for an incident-response account; grants no entrypoint access
+    Pauser,
+}
+
+/// Hash function applied to commitment preimages, see `set_hash_algorithm`
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HashAlgorithm {
+    /// SHA-256; the default
+    Sha256,
+    /// Keccak-256, for commitments that must also be checked on EVM chains
+    Keccak256,
+}
+
+impl HashAlgorithm {
+    /// Every supported algorithm, tried in this order when opening a commitment
+    pub const ALL: [HashAlgorithm; 2] = [HashAlgorithm::Sha256, HashAlgorithm::Keccak256];
+}
+
+/// Which optional events the contract publishes, see `set_event_verbosity`.
+///
+/// Money movements (deposits, withdrawals, refunds, cancels, top-ups, transfers) and admin
+/// changes are always published; each level adds to the one before it.
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum EventVerbosity {
+    /// Only the events that are always published
+    Off,
+    /// Also `PrivacyToggled`, `PrivacyLevelChanged` and `EscrowExpired`
+    Normal,
+    /// Also `AuthFailed` and `CommitmentStatusChanged`; the default
+    Verbose,
+}
+
+/// Validate moving an escrow from status `from` to status `to`.
+///
+/// This is the legal transition graph; every status change goes through it:
+/// - `Pending -> Spent` (withdrawal, or refund once expired)
+/// - `Pending -> Expired` (expiry sweep)
+/// - `Pending -> Cancelled` (depositor cancels)
+/// - `Expired -> Spent` (refund)
+///
+/// `Spent` and `Cancelled` are terminal. Returns `to` on success.
+pub fn transition_status(
+    from: EscrowStatus,
+    to: EscrowStatus,
+) -> Result<EscrowStatus, QuickexError> {
+    match (from, to) {
+        (EscrowStatus::Pending, EscrowStatus::Spent)
+        | (EscrowStatus::Pending, EscrowStatus::Expired)
+        | (EscrowStatus::Pending, EscrowStatus::Cancelled)
+        | (EscrowStatus::Expired, EscrowStatus::Spent) => Ok(to),
+        _ => Err(QuickexError::InvalidStatusTransition),
+    }
+}
+
+/// Compact `u32` code for an [`EscrowStatus`].
+///
+/// Secondary indexes (the `DataKey::StatusCount` counters) store this instead of the full
+/// enum to keep serialized keys small. `EscrowEntry` itself keeps the enum. Codes are part
+/// of the storage format and must never be reassigned.
+pub fn status_to_code(status: EscrowStatus) -> u32 {
+    match status {
+        EscrowStatus::Pending => 0,
+        EscrowStatus::Spent => 1,
+        EscrowStatus::Expired => 2,
+        EscrowStatus::Cancelled => 3,
+    }
+}
+
+/// Decode a compact status code produced by [`status_to_code`]; `None` for unknown codes.
+///
+/// The contract only ever writes codes, so this is used by tests to pin the mapping that
+/// off-chain readers of the index keys rely on.
+#[cfg(test)]
+pub fn code_to_status(code: u32) -> Option<EscrowStatus> {
+    match code {
+        0 => Some(EscrowStatus::Pending),
+        1 => Some(EscrowStatus::Spent),
+        2 => Some(EscrowStatus::Expired),
+        3 => Some(EscrowStatus::Cancelled),
+        _ => None,
+    }
+}
+
+/// Per-token deposit amount bounds (inclusive)
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct AmountBounds {
+    pub min: i128,
+    pub max: i128,
+}
+
+/// Deposit bounds for tokens the admin has not configured: any positive amount
+pub const DEFAULT_AMOUNT_BOUNDS: AmountBounds = AmountBounds {
+    min: 1,
+    max: i128::MAX,
+};
+
+/// A single asset in a multi-asset basket deposit
+#[contracttype]
+#[derive(Clone)]
+pub struct BasketItem {
+    pub token: Address,
+    pub amount: i128,
+    pub commitment: BytesN<32>,
+}
+
+/// One recipient's share of a `deposit_split` deposit
+#[contracttype]
+#[derive(Clone)]
+pub struct SplitShare {
+    /// Commitment to the recipient, token, share amount, and a salt of their own
+    pub commitment: BytesN<32>,
+    pub amount: i128,
+}
+
+/// Aggregated contract metrics returned by `get_contract_stats`
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ContractStats {
+    /// Number of escrows funded through a deposit entrypoint
+    pub total_deposits: u64,
+    /// Number of escrows paid out through a withdrawal
+    pub total_withdrawals: u64,
+    /// Number of escrows returned to their depositor (cancelled or refunded after expiry)
+    pub total_refunds: u64,
+    /// Number of escrows currently pending
+    pub pending_count: u64,
+}
+
+/// Number of escrows in each status, returned by `get_status_counts`
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct StatusCounts {
+    pub pending: u64,
+    pub spent: u64,
+    pub expired: u64,
+    pub cancelled: u64,
+}
+
+/// Contract state summary returned by `get_health`
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct HealthStatus {
+    pub is_paused: bool,
+    /// Whether `initialize` has set an admin
+    pub admin_set: bool,
+    /// Current value of the `create_escrow` counter
+    pub escrow_counter: u64,
+    /// Contract interface version (`get_version`)
+    pub version: u32,
+}
+
+/// Admin-tunable parameters in one read, returned by `get_config`
+#[contracttype]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ContractConfig {
+    pub admin: Option<Address>,
+    pub paused: bool,
+    /// Set once by `shutdown`; deposits are refused for good
+    pub shutdown: bool,
+    /// Withdrawal fee in basis points
+    pub fee_bps: u32,
+    /// Deposit fee in basis points
+    pub deposit_fee_bps: u32,
+    pub fee_collector: Option<Address>,
+    /// Lifetime in seconds of escrows deposited without an explicit expiry (`0` = never)
+    pub default_expiry: u64,
+    /// Seconds after expiry during which only the depositor may reclaim
+    pub reclaim_window: u64,
+    /// Ledgers a recipient must wait between withdrawals (`0` = disabled)
+    pub withdraw_cooldown: u32,
+    /// Privacy flag reported for accounts that never set their own
+    pub default_privacy: bool,
+    /// Deposit bounds applied to tokens without their own `set_amount_bounds`
+    pub default_amount_bounds: AmountBounds,
+    pub max_basket_size: u32,
+    /// `0` = unlimited
+    pub max_pending_per_owner: u32,
+    pub hash_algorithm: HashAlgorithm,
+    pub event_verbosity: EventVerbosity,
+    pub ttl_config: TtlConfig,
+}
+
+/// Persistent-entry TTL settings, in ledgers, chosen at `initialize`
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TtlConfig {
+    /// Remaining TTL at or below which an escrow entry is bumped when written
+    pub threshold: u32,
+    /// TTL an escrow entry is bumped to
+    pub extend_to: u32,
+}
+
+/// Approximate number of ledgers closed per day (5 second ledgers)
+pub const DAY_IN_LEDGERS: u32 = 17_280;
+
+/// Default TTL settings used when `initialize` is not given any
+pub const DEFAULT_TTL_CONFIG: TtlConfig = TtlConfig {
+    threshold: 30 * DAY_IN_LEDGERS,
+    extend_to: 60 * DAY_IN_LEDGERS,
+};
+
+/// Fixed conversion rate for swap-on-withdraw: `numerator / denominator` units of the
+/// target token per deposited unit
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SwapRate {
+    pub numerator: i128,
+    pub denominator: i128,
+}
+
+/// Convert `amount` at `rate`, rounding down; `None` on overflow
+pub fn convert_amount(amount: i128, rate: &SwapRate) -> Option<i128> {
+    amount
+        .checked_mul(rate.numerator)
+        .map(|scaled| scaled / rate.denominator)
+}
+
+/// Token and rate an escrow pays out in when it differs from the deposited token
+#[contracttype]
+#[derive(Clone)]
+pub struct SwapTarget {
+    pub token: Address,
+    /// Rate fixed at deposit time; later admin changes do not affect the escrow
+    pub rate: SwapRate,
+}
+
+/// What an escrow pays out on withdrawal
+#[contracttype]
+#[derive(Clone)]
+pub enum Payout {
+    /// The deposited token, 1:1
+    Deposited,
+    /// Another token at a rate fixed at deposit (swap-on-withdraw)
+    Swap(SwapTarget),
+}
+
+/// Outcome of a successful `withdraw`, for client confirmation screens
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct WithdrawReceipt {
+    /// Amount transferred to the recipient, in the payout token
+    pub amount_out: i128,
+    /// Fees deducted from the payout; `amount_out + fee` is the full payout
+    pub fee: i128,
+    /// Escrow status after the withdrawal (`Spent`)
+    pub status: EscrowStatus,
+    /// Ledger timestamp of the withdrawal
+    pub timestamp: u64,
 }
 
 /// Escrow entry structure
@@ -15,7 +284,48 @@ pub enum EscrowStatus {
 pub struct EscrowEntry {
     pub token: Address,
     pub amount: i128,
+    /// Depositor who funded the escrow; the only address that may cancel, refund or
+    /// update the memo. The recipient is not stored and is bound by the commitment instead.
     pub owner: Address,
     pub status: EscrowStatus,
     pub created_at: u64,
+    /// Ledger timestamp from which the escrow counts as expired, or [`NO_EXPIRY`]
+    pub expires_at: u64,
+    /// What withdrawal pays out; refunds always return `token`
+    pub payout: Payout,
+    /// Hash function the commitment was made with, recorded at deposit so withdrawals
+    /// keep working after `set_hash_algorithm` changes the default
+    pub hash_algorithm: HashAlgorithm,
+}
+
+/// `expires_at` sentinel for escrows that never expire
+pub const NO_EXPIRY: u64 = 0;
+
+/// `amount` sentinel in escrow details redacted because the owner has privacy enabled
+pub const REDACTED_AMOUNT: i128 = -1;
+
+/// Default lifetime of an escrow when the depositor does not choose one (30 days)
+pub const DEFAULT_EXPIRY_SECS: u64 = 30 * 24 * 60 * 60;
+
+/// Check whether `entry` has expired at ledger timestamp `now`.
+///
+/// This is the single source of truth for expiry; every spend path must use it so
+/// that withdrawals and refunds agree on when an escrow expires.
+pub fn is_expired(entry: &EscrowEntry, now: u64) -> bool {
+    entry.expires_at != NO_EXPIRY && now >= entry.expires_at
+}
+
+/// Default reclaim window after expiry during which only the depositor may reclaim (7 days)
+pub const DEFAULT_RECLAIM_WINDOW_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// Check whether the admin may reclaim `entry` at ledger timestamp `now`.
+///
+/// For the first `reclaim_window` seconds after expiry only the depositor may reclaim;
+/// after that the admin may as well. Entries marked `Expired` without an expiry time
+/// (e.g. imported ones) are reclaimable by the admin immediately.
+pub fn is_admin_reclaimable(entry: &EscrowEntry, now: u64, reclaim_window: u64) -> bool {
+    if entry.expires_at == NO_EXPIRY {
+        return entry.status == EscrowStatus::Expired;
+    }
+    now >= entry.expires_at.saturating_add(reclaim_window)
 }